@@ -0,0 +1,198 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse_macro_input, Data, DeriveInput, Fields, GenericArgument, LitChar, LitStr, PathArguments,
+    Type,
+};
+
+#[proc_macro_derive(Arguments, attributes(arg))]
+pub fn derive_arguments(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(&input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+enum Kind {
+    Flag,
+    Optional(Type),
+    Many(Type),
+    Required(Type),
+}
+
+#[derive(Default)]
+struct Options {
+    short: Option<char>,
+    long: Option<String>,
+    positional: bool,
+    default: Option<String>,
+    env: Option<String>,
+    value_delimiter: Option<char>,
+    sensitive: bool,
+    path: bool,
+    global: bool,
+}
+
+fn expand(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    input,
+                    "#[derive(Arguments)] needs a struct with named fields",
+                ))
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                input,
+                "#[derive(Arguments)] only supports structs",
+            ))
+        }
+    };
+    let mut builders = Vec::new();
+    let mut conversions = Vec::new();
+    for field in fields {
+        let ident = field.ident.as_ref().unwrap();
+        let field_name = ident.to_string();
+        let name = field_name.trim_start_matches("r#").replace('_', "-");
+        let options = parse_options(field, &name)?;
+        let kind = classify(&field.ty);
+        let mut calls = vec![quote! { .arg(#name) }];
+        if options.positional {
+            if matches!(kind, Kind::Flag) {
+                return Err(syn::Error::new_spanned(
+                    field,
+                    "a bool field cannot be positional",
+                ));
+            }
+            if matches!(kind, Kind::Many(_)) {
+                calls.push(quote! { .positional_multiple(#name) });
+            } else {
+                calls.push(quote! { .positional(#name) });
+            }
+        } else {
+            if let Some(short) = options.short {
+                calls.push(quote! { .short(#name, #short) });
+            }
+            let long = options.long.as_deref().unwrap_or(&name);
+            calls.push(quote! { .long(#name, #long) });
+            if !matches!(kind, Kind::Flag) {
+                calls.push(quote! { .takes_value(#name) });
+            }
+            if matches!(kind, Kind::Many(_)) {
+                calls.push(quote! { .multiple(#name) });
+            }
+        }
+        match &kind {
+            Kind::Optional(ty) | Kind::Many(ty) | Kind::Required(ty) => {
+                calls.push(quote! { .value_parser::<#ty>(#name) });
+            }
+            Kind::Flag => {}
+        }
+        if let Some(default) = &options.default {
+            calls.push(quote! { .default(#name, #default) });
+        } else if matches!(kind, Kind::Required(_)) {
+            calls.push(quote! { .required(#name) });
+        }
+        if let Some(env) = &options.env {
+            calls.push(quote! { .env(#name, #env) });
+        }
+        if let Some(delimiter) = options.value_delimiter {
+            calls.push(quote! { .value_delimiter(#name, #delimiter) });
+        }
+        if options.sensitive {
+            calls.push(quote! { .sensitive(#name) });
+        }
+        if options.path {
+            calls.push(quote! { .path(#name) });
+        }
+        if options.global {
+            calls.push(quote! { .global(#name) });
+        }
+        builders.push(quote! { #(#calls)* });
+        conversions.push(match kind {
+            Kind::Flag => quote! {
+                #ident: matches.flags.get(#name).copied().unwrap_or(false)
+            },
+            Kind::Optional(_) => quote! { #ident: matches.field(#field_name, #name)? },
+            Kind::Many(_) => quote! { #ident: matches.field_many(#field_name, #name)? },
+            Kind::Required(_) => quote! { #ident: matches.required_field(#field_name, #name)? },
+        });
+    }
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::rust_arguments::Arguments for #ident #ty_generics #where_clause {
+            fn arg_parser() -> ::rust_arguments::ArgParser {
+                ::rust_arguments::ArgParser::new() #(#builders)*
+            }
+        }
+
+        impl #impl_generics ::rust_arguments::FromArgMatches for #ident #ty_generics #where_clause {
+            fn from_arg_matches(
+                matches: &::rust_arguments::ArgMatches,
+            ) -> ::std::result::Result<Self, ::rust_arguments::FieldError> {
+                ::std::result::Result::Ok(Self { #(#conversions,)* })
+            }
+        }
+    })
+}
+
+fn parse_options(field: &syn::Field, name: &str) -> syn::Result<Options> {
+    let mut options = Options::default();
+    for attr in field.attrs.iter().filter(|a| a.path().is_ident("arg")) {
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("short") {
+                options.short = Some(if meta.input.peek(syn::Token![=]) {
+                    meta.value()?.parse::<LitChar>()?.value()
+                } else {
+                    name.chars().next().unwrap_or_default()
+                });
+            } else if meta.path.is_ident("long") {
+                options.long = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("default") {
+                options.default = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("env") {
+                options.env = Some(meta.value()?.parse::<LitStr>()?.value());
+            } else if meta.path.is_ident("value_delimiter") {
+                options.value_delimiter = Some(meta.value()?.parse::<LitChar>()?.value());
+            } else if meta.path.is_ident("positional") {
+                options.positional = true;
+            } else if meta.path.is_ident("sensitive") {
+                options.sensitive = true;
+            } else if meta.path.is_ident("path") {
+                options.path = true;
+            } else if meta.path.is_ident("global") {
+                options.global = true;
+            } else {
+                return Err(meta.error("unknown #[arg] setting"));
+            }
+            Ok(())
+        })?;
+    }
+    Ok(options)
+}
+
+fn classify(ty: &Type) -> Kind {
+    if let Type::Path(path) = ty {
+        if path.qself.is_none() && path.path.is_ident("bool") {
+            return Kind::Flag;
+        }
+        if let Some(segment) = path.path.segments.last() {
+            if let PathArguments::AngleBracketed(args) = &segment.arguments {
+                if let Some(GenericArgument::Type(inner)) = args.args.first() {
+                    if segment.ident == "Option" {
+                        return Kind::Optional(inner.clone());
+                    }
+                    if segment.ident == "Vec" {
+                        return Kind::Many(inner.clone());
+                    }
+                }
+            }
+        }
+    }
+    Kind::Required(ty.clone())
+}
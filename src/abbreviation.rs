@@ -0,0 +1,122 @@
+use crate::{ArgParser, SpecError};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Abbreviation {
+    pub token: String,
+    pub expansion: Vec<String>,
+}
+
+impl ArgParser {
+    pub fn abbreviation(mut self, token: &str, expansion: &[&str]) -> Self {
+        self.abbreviations.retain(|a| a.token != token);
+        self.abbreviations.push(Abbreviation {
+            token: token.to_string(),
+            expansion: expansion.iter().map(|e| e.to_string()).collect(),
+        });
+        self
+    }
+
+    pub fn abbreviations(self, dictionary: &[(&str, &[&str])]) -> Self {
+        dictionary.iter().fold(self, |parser, (token, expansion)| {
+            parser.abbreviation(token, expansion)
+        })
+    }
+
+    fn takes_next_value(&self, token: &str) -> bool {
+        if let Some(long) = token.strip_prefix("--") {
+            return !long.contains('=')
+                && self
+                    .args
+                    .iter()
+                    .any(|a| a.takes_value && a.matches_long(long));
+        }
+        let Some(shorts) = token.strip_prefix('-') else {
+            return false;
+        };
+        for c in shorts.chars() {
+            match self.args.iter().find(|a| a.short == Some(c)) {
+                Some(arg) if arg.takes_value => return shorts.ends_with(c),
+                Some(_) => {}
+                None => return false,
+            }
+        }
+        false
+    }
+
+    pub(crate) fn expand_abbreviations(&self, tokens: Vec<String>) -> Vec<String> {
+        let mut out = Vec::with_capacity(tokens.len());
+        let mut value_expected = false;
+        let mut tokens = tokens.into_iter();
+        for token in tokens.by_ref() {
+            if token == "--" {
+                out.push(token);
+                break;
+            }
+            let abbreviation = self.abbreviations.iter().find(|a| a.token == token);
+            match abbreviation.filter(|_| !value_expected) {
+                Some(abbreviation) => {
+                    out.extend(abbreviation.expansion.iter().cloned());
+                    value_expected = abbreviation
+                        .expansion
+                        .last()
+                        .is_some_and(|last| self.takes_next_value(last));
+                }
+                None => {
+                    value_expected = !value_expected && self.takes_next_value(&token);
+                    out.push(token);
+                }
+            }
+        }
+        out.extend(tokens);
+        out
+    }
+
+    fn known_flag(&self, word: &str) -> bool {
+        if let Some(long) = word.strip_prefix("--") {
+            let long = long.split_once('=').map_or(long, |(name, _)| name);
+            return long == "help" || self.args.iter().any(|a| a.matches_long(long));
+        }
+        let Some(shorts) = word.strip_prefix('-') else {
+            return true;
+        };
+        for c in shorts.chars() {
+            match self.args.iter().find(|a| a.short == Some(c)) {
+                Some(arg) if arg.takes_value => return true,
+                Some(_) => {}
+                None if c == 'h' => {}
+                None => return false,
+            }
+        }
+        !shorts.is_empty()
+    }
+
+    pub(crate) fn check_abbreviations(&self) -> Result<(), SpecError> {
+        for abbreviation in &self.abbreviations {
+            let invalid = |message: String| SpecError::InvalidAbbreviation {
+                token: abbreviation.token.clone(),
+                message,
+            };
+            if abbreviation.expansion.is_empty() {
+                return Err(invalid("it expands to nothing".to_string()));
+            }
+            let token = &abbreviation.token;
+            if self.find_subcommand(token).is_some() {
+                return Err(invalid(format!("it shadows subcommand '{}'", token)));
+            }
+            if token.starts_with('-') && self.known_flag(token) {
+                return Err(invalid(format!("it shadows option {}", token)));
+            }
+            if let Some(word) = abbreviation
+                .expansion
+                .iter()
+                .take_while(|word| *word != "--")
+                .filter(|word| word.starts_with('-') && *word != "-")
+                .find(|word| !self.known_flag(word))
+            {
+                return Err(invalid(format!("it expands to unknown option {}", word)));
+            }
+        }
+        Ok(())
+    }
+}
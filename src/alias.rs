@@ -0,0 +1,72 @@
+use crate::{Arg, ArgParser};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct Alias {
+    pub name: String,
+    pub visible: bool,
+}
+
+impl Alias {
+    fn new(name: &str, visible: bool) -> Self {
+        Alias {
+            name: name.to_string(),
+            visible,
+        }
+    }
+}
+
+impl Arg {
+    pub fn matches_long(&self, name: &str) -> bool {
+        self.long.as_deref() == Some(name) || self.aliases.iter().any(|a| a.name == name)
+    }
+}
+
+impl ArgParser {
+    pub fn alias(self, name: &str, alias: &str) -> Self {
+        self.push_alias(name, alias, false)
+    }
+
+    pub fn visible_alias(self, name: &str, alias: &str) -> Self {
+        self.push_alias(name, alias, true)
+    }
+
+    fn push_alias(mut self, name: &str, alias: &str, visible: bool) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.aliases.push(Alias::new(alias, visible));
+        }
+        self
+    }
+
+    pub fn subcommand_alias(self, name: &str, aliases: &[&str]) -> Self {
+        self.push_subcommand_aliases(name, aliases, false)
+    }
+
+    pub fn visible_subcommand_alias(self, name: &str, aliases: &[&str]) -> Self {
+        self.push_subcommand_aliases(name, aliases, true)
+    }
+
+    fn push_subcommand_aliases(mut self, name: &str, aliases: &[&str], visible: bool) -> Self {
+        if let Some(sub) = self.subcommands.get_mut(name) {
+            sub.aliases
+                .extend(aliases.iter().map(|alias| Alias::new(alias, visible)));
+        }
+        self
+    }
+
+    pub fn hide_internal_aliases(mut self) -> Self {
+        self.hide_internal_aliases = true;
+        self
+    }
+
+    pub(crate) fn find_subcommand(&self, token: &str) -> Option<(&str, &ArgParser)> {
+        if let Some((name, sub)) = self.subcommands.get_key_value(token) {
+            return Some((name, sub));
+        }
+        self.subcommands
+            .iter()
+            .find(|(_, sub)| sub.aliases.iter().any(|a| a.name == token))
+            .map(|(name, sub)| (name.as_str(), sub))
+    }
+}
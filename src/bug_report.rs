@@ -0,0 +1,135 @@
+use serde_json::{json, Map, Value};
+
+use crate::{Arg, ArgMatches, ArgParser, ExplainStep, REDACTED};
+
+pub const BUG_REPORT: &str = "bug-report";
+
+impl ArgParser {
+    pub fn bug_report_flag(self) -> Self {
+        self.arg(BUG_REPORT)
+            .long(BUG_REPORT, BUG_REPORT)
+            .global(BUG_REPORT)
+    }
+
+    pub(crate) fn bug_report_requested(&self, args: &[String]) -> bool {
+        let flag = format!("--{}", BUG_REPORT);
+        self.args.iter().any(|a| a.name == BUG_REPORT)
+            && args
+                .iter()
+                .skip(1)
+                .take_while(|token| *token != "--")
+                .any(|token| *token == flag)
+    }
+
+    pub fn render_bug_report(&self, args: &[String]) -> String {
+        let flag = format!("--{}", BUG_REPORT);
+        let args: Vec<String> = args.iter().filter(|t| **t != flag).cloned().collect();
+        let program = self.program(&args);
+        let outcome = match self.try_parse(&args) {
+            Ok(matches) => {
+                let mut provenance = Map::new();
+                collect_provenance(&matches, String::new(), &mut provenance);
+                json!({ "ok": true, "provenance": provenance })
+            }
+            Err(err) => json!({ "ok": false, "error": err.to_string() }),
+        };
+        let spec = serde_json::from_str::<Value>(&self.to_json_spec(&program))
+            .expect("command specs always serialize");
+        let mut report = json!({
+            "crate_version": env!("CARGO_PKG_VERSION"),
+            "program": program,
+            "version": self.version,
+            "argv": self.anonymize(&args),
+            "outcome": outcome,
+            "spec": spec,
+        });
+        if let Some(home) = std::env::var_os("HOME").filter(|home| home.len() > 1) {
+            hide_home(&mut report, &home.to_string_lossy());
+        }
+        let mut out = serde_json::to_string_pretty(&report).expect("reports always serialize");
+        out.push('\n');
+        out
+    }
+
+    fn anonymize(&self, args: &[String]) -> Vec<String> {
+        let mut argv: Vec<String> = args.to_vec();
+        let mut parser = self;
+        let mut known: Vec<&Arg> = self.args.iter().collect();
+        let mut i = 1;
+        while i < argv.len() {
+            let token = argv[i].clone();
+            if token == "--" {
+                break;
+            }
+            let sensitive = |arg: &&Arg| arg.sensitive && arg.takes_value;
+            if let Some(long) = token.strip_prefix("--") {
+                let name = long.split_once('=').map_or(long, |(name, _)| name);
+                if let Some(arg) = known.iter().find(|a| a.matches_long(name)) {
+                    if long.contains('=') && sensitive(arg) {
+                        argv[i] = format!("--{}={}", name, REDACTED);
+                    } else if arg.takes_value && !long.contains('=') {
+                        if sensitive(arg) && i + 1 < argv.len() {
+                            argv[i + 1] = REDACTED.to_string();
+                        }
+                        i += 1;
+                    }
+                }
+            } else if token.starts_with('-') && token != "-" {
+                for (offset, c) in token.char_indices().skip(1) {
+                    let Some(arg) = known.iter().find(|a| a.short == Some(c)) else {
+                        continue;
+                    };
+                    if !arg.takes_value {
+                        continue;
+                    }
+                    let rest = offset + c.len_utf8();
+                    if rest < token.len() {
+                        if sensitive(arg) {
+                            argv[i] = format!("{}{}", &token[..rest], REDACTED);
+                        }
+                    } else {
+                        if sensitive(arg) && i + 1 < argv.len() {
+                            argv[i + 1] = REDACTED.to_string();
+                        }
+                        i += 1;
+                    }
+                    break;
+                }
+            } else if let Some((_, sub)) = parser.find_subcommand(&token) {
+                parser = sub;
+                known.extend(sub.args.iter());
+            }
+            i += 1;
+        }
+        for step in self.explain(args).steps {
+            if let ExplainStep::Positional { index, value, .. } = step {
+                if value == REDACTED && index < argv.len() {
+                    argv[index] = REDACTED.to_string();
+                }
+            }
+        }
+        argv
+    }
+}
+
+fn collect_provenance(matches: &ArgMatches, command: String, out: &mut Map<String, Value>) {
+    let sources = serde_json::to_value(matches.source_map()).expect("provenance always serializes");
+    out.insert(command.clone(), sources);
+    if let Some((name, sub)) = matches.subcommand() {
+        let command = if command.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} {}", command, name)
+        };
+        collect_provenance(sub, command, out);
+    }
+}
+
+fn hide_home(value: &mut Value, home: &str) {
+    match value {
+        Value::String(text) => *text = text.replace(home, "~"),
+        Value::Array(items) => items.iter_mut().for_each(|item| hide_home(item, home)),
+        Value::Object(map) => map.values_mut().for_each(|item| hide_home(item, home)),
+        _ => {}
+    }
+}
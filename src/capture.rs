@@ -0,0 +1,48 @@
+use crate::{Arg, ArgParser};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Capture {
+    #[default]
+    Greedy,
+    Lazy,
+}
+
+impl ArgParser {
+    pub fn capture(mut self, name: &str, capture: Capture) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.capture = capture;
+        }
+        self
+    }
+}
+
+// Splits `words` free words over the remaining positional slots. Fixed slots
+// are filled first, then every required variadic gets one word, and whatever
+// is left goes to the greedy variadic (or the last one if all are lazy).
+pub(crate) fn distribute(slots: &[&Arg], words: usize, version: Option<&str>) -> Vec<usize> {
+    let mut counts = vec![0; slots.len()];
+    let mut left = words;
+    for variadic_pass in [false, true] {
+        for (count, slot) in counts.iter_mut().zip(slots) {
+            let wants = if variadic_pass {
+                slot.multiple && slot.is_required(version)
+            } else {
+                !slot.multiple
+            };
+            if wants && left > 0 {
+                *count = 1;
+                left -= 1;
+            }
+        }
+    }
+    let variadics: Vec<usize> = (0..slots.len()).filter(|&i| slots[i].multiple).collect();
+    let target = variadics
+        .iter()
+        .find(|&&i| slots[i].capture == Capture::Greedy)
+        .or(variadics.last());
+    if let Some(&i) = target {
+        counts[i] += left;
+    }
+    counts
+}
@@ -0,0 +1,772 @@
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::{Alias, Arg, ArgParser, Shell};
+
+pub const PRINT_COMPLETION_CANDIDATES: &str = "--print-completion-candidates";
+pub const LIST_SUBCOMMANDS: &str = "list-subcommands";
+pub const LIST_OPTIONS: &str = "list-options";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CandidateSource {
+    Flag(String),
+    FlagAlias(String),
+    Subcommand,
+    SubcommandAlias(String),
+    Shorthand(String),
+    Choice(String),
+    Files(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompletionCandidate {
+    pub value: String,
+    pub source: CandidateSource,
+}
+
+impl fmt::Display for CompletionCandidate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}\t", self.value)?;
+        match &self.source {
+            CandidateSource::Flag(arg) => write!(f, "flag of {}", arg),
+            CandidateSource::FlagAlias(arg) => write!(f, "alias flag of {}", arg),
+            CandidateSource::Subcommand => write!(f, "subcommand"),
+            CandidateSource::SubcommandAlias(name) => write!(f, "alias of subcommand {}", name),
+            CandidateSource::Shorthand(expansion) => write!(f, "shorthand for {}", expansion),
+            CandidateSource::Choice(flag) => write!(f, "choice for {}", flag),
+            CandidateSource::Files(flag) => write!(f, "files for {}", flag),
+        }
+    }
+}
+
+struct NodeArg<'a> {
+    arg: &'a Arg,
+    longs: Vec<String>,
+}
+
+struct Node<'a> {
+    path: String,
+    names: Vec<String>,
+    about: Option<&'a str>,
+    args: Vec<NodeArg<'a>>,
+    subcommands: Vec<String>,
+    shorthands: Vec<(String, String)>,
+}
+
+impl Node<'_> {
+    fn parent_path(&self) -> &str {
+        self.path.rsplit_once('/').map_or("", |(parent, _)| parent)
+    }
+}
+
+fn find_node<'n, 'a>(nodes: &'n [Node<'a>], words: &[String]) -> Option<&'n Node<'a>> {
+    let mut path = String::new();
+    for word in words {
+        let next = nodes
+            .iter()
+            .find(|n| !n.path.is_empty() && n.parent_path() == path && n.names.contains(word));
+        if let Some(next) = next {
+            path = next.path.clone();
+        }
+    }
+    nodes.iter().find(|n| n.path == path)
+}
+
+fn alias_names(aliases: &[Alias], include_internal: bool) -> impl Iterator<Item = String> + '_ {
+    aliases
+        .iter()
+        .filter(move |a| a.visible || include_internal)
+        .map(|a| a.name.clone())
+}
+
+fn collect_nodes<'a>(
+    parser: &'a ArgParser,
+    path: String,
+    names: Vec<String>,
+    inherited: Vec<&'a Arg>,
+    include_internal: bool,
+    version: Option<&str>,
+    nodes: &mut Vec<Node<'a>>,
+) {
+    let version = parser.version.as_deref().or(version);
+    let mut args: Vec<&Arg> = parser.args.iter().collect();
+    for global in inherited {
+        if !parser.disallowed_globals.contains(&global.name)
+            && !args.iter().any(|a| a.name == global.name)
+        {
+            args.push(global);
+        }
+    }
+    let globals: Vec<&Arg> = args.iter().copied().filter(|a| a.global).collect();
+    let mut children: Vec<&String> = parser.subcommands.keys().collect();
+    children.sort();
+    let mut subcommands = Vec::new();
+    for name in children {
+        let sub = &parser.subcommands[name];
+        let mut sub_names = vec![name.clone()];
+        sub_names.extend(alias_names(&sub.aliases, include_internal));
+        subcommands.extend(sub_names.iter().cloned());
+        collect_nodes(
+            sub,
+            format!("{}/{}", path, name),
+            sub_names,
+            globals.clone(),
+            include_internal,
+            version,
+            nodes,
+        );
+    }
+    let args = args
+        .into_iter()
+        .filter(|a| !a.is_hidden() && !a.is_removed(version))
+        .map(|arg| NodeArg {
+            arg,
+            longs: arg
+                .long
+                .iter()
+                .cloned()
+                .chain(alias_names(&arg.aliases, include_internal))
+                .chain(
+                    arg.long
+                        .iter()
+                        .filter(|_| arg.negatable)
+                        .map(|long| format!("no-{}", long)),
+                )
+                .collect(),
+        })
+        .collect();
+    let shorthands = parser
+        .abbreviations
+        .iter()
+        .map(|a| (a.token.clone(), a.expansion.join(" ")))
+        .collect();
+    nodes.push(Node {
+        path,
+        names,
+        about: parser.about.as_deref(),
+        args,
+        subcommands,
+        shorthands,
+    });
+}
+
+fn option_words(arg: &NodeArg) -> Vec<String> {
+    let mut words: Vec<String> = arg.longs.iter().map(|l| format!("--{}", l)).collect();
+    if let Some(short) = arg.arg.short {
+        words.push(format!("-{}", short));
+    }
+    words
+}
+
+fn value_choices(arg: &Arg) -> Vec<String> {
+    if !arg.choices.is_empty() {
+        return arg.choices.clone();
+    }
+    arg.range
+        .and_then(|range| range.values(100))
+        .unwrap_or_default()
+}
+
+fn node_words(node: &Node) -> Vec<String> {
+    let mut words: Vec<String> = node.args.iter().flat_map(option_words).collect();
+    words.extend(node.subcommands.iter().cloned());
+    words.extend(node.shorthands.iter().map(|(token, _)| token.clone()));
+    words
+}
+
+fn function_name(program: &str) -> String {
+    let name: String = program
+        .chars()
+        .map(|c| if c.is_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("_{}", name)
+}
+
+fn render_bash(program: &str, nodes: &[Node]) -> String {
+    let shell = Shell::Bash;
+    let function = function_name(program);
+    let mut out = String::new();
+    out.push_str(&format!("{}() {{\n", function));
+    out.push_str("    local cur prev path next i\n");
+    out.push_str("    cur=\"${COMP_WORDS[COMP_CWORD]}\"\n");
+    out.push_str("    prev=\"${COMP_WORDS[COMP_CWORD-1]}\"\n");
+    out.push_str("    path=\"\"\n");
+    let subcommand_nodes: Vec<&Node> = nodes.iter().filter(|n| !n.path.is_empty()).collect();
+    if !subcommand_nodes.is_empty() {
+        out.push_str("    for ((i = 1; i < COMP_CWORD; i++)); do\n");
+        out.push_str("        next=\"${path}/${COMP_WORDS[i]}\"\n");
+        out.push_str("        case \"$next\" in\n");
+        for node in subcommand_nodes {
+            let patterns: Vec<String> = node
+                .names
+                .iter()
+                .map(|name| shell.quote(&format!("{}/{}", node.parent_path(), name)))
+                .collect();
+            out.push_str(&format!(
+                "            {}) path={} ;;\n",
+                patterns.join("|"),
+                shell.quote(&node.path)
+            ));
+        }
+        out.push_str("        esac\n");
+        out.push_str("    done\n");
+    }
+    out.push_str("    case \"$path\" in\n");
+    for node in nodes {
+        out.push_str(&format!("        {})\n", shell.quote(&node.path)));
+        let value_args: Vec<&NodeArg> = node.args.iter().filter(|a| a.arg.takes_value).collect();
+        if !value_args.is_empty() {
+            out.push_str("            case \"$prev\" in\n");
+            for node_arg in value_args {
+                let words = option_words(node_arg);
+                if words.is_empty() {
+                    continue;
+                }
+                let arg = node_arg.arg;
+                let choices = value_choices(arg);
+                let reply = if !choices.is_empty() {
+                    format!(
+                        "COMPREPLY=($(compgen -W {} -- \"$cur\"))",
+                        shell.quote(&shell.quote_command(&choices))
+                    )
+                } else if arg.is_path {
+                    "COMPREPLY=($(compgen -f -- \"$cur\"))".to_string()
+                } else {
+                    "COMPREPLY=()".to_string()
+                };
+                out.push_str(&format!(
+                    "                {}) {}; return ;;\n",
+                    words.join("|"),
+                    reply
+                ));
+            }
+            out.push_str("            esac\n");
+        }
+        out.push_str(&format!(
+            "            COMPREPLY=($(compgen -W {} -- \"$cur\"))\n",
+            shell.quote(&node_words(node).join(" "))
+        ));
+        out.push_str("            ;;\n");
+    }
+    out.push_str("    esac\n");
+    out.push_str("}\n");
+    out.push_str(&format!(
+        "complete -F {} {}\n",
+        function,
+        shell.quote(program)
+    ));
+    out
+}
+
+fn zsh_escape(text: &str, special: &[char]) -> String {
+    let mut out = String::new();
+    for c in text.chars() {
+        if c == '\\' || special.contains(&c) {
+            out.push('\\');
+        }
+        out.push(c);
+    }
+    out
+}
+
+fn zsh_action(arg: &Arg) -> String {
+    let choices = value_choices(arg);
+    if !choices.is_empty() {
+        let choices: Vec<String> = choices
+            .iter()
+            .map(|c| zsh_escape(c, &[' ', '(', ')', ':']))
+            .collect();
+        format!("({})", choices.join(" "))
+    } else if arg.is_path {
+        "_files".to_string()
+    } else {
+        " ".to_string()
+    }
+}
+
+fn zsh_arg_specs(node_arg: &NodeArg) -> Vec<String> {
+    let arg = node_arg.arg;
+    let description = arg
+        .help
+        .as_deref()
+        .map(|help| format!("[{}]", zsh_escape(help, &['[', ']'])))
+        .unwrap_or_default();
+    let value = arg.display_name.as_deref().unwrap_or(&arg.name);
+    let value = format!(":{}:{}", zsh_escape(value, &[':']), zsh_action(arg));
+    if arg.positional {
+        let repeat = if arg.multiple { "*" } else { "" };
+        return vec![format!("{}{}", repeat, value)];
+    }
+    let words = option_words(node_arg);
+    let repeatable = arg.multiple || !arg.levels.is_empty();
+    let exclusions = if repeatable {
+        "*".to_string()
+    } else if words.len() > 1 {
+        format!("({})", words.join(" "))
+    } else {
+        String::new()
+    };
+    words
+        .iter()
+        .map(|word| {
+            let suffix = match (arg.takes_value, word.starts_with("--")) {
+                (false, _) => "",
+                (true, true) => "=",
+                (true, false) => "+",
+            };
+            let value = if arg.takes_value { value.as_str() } else { "" };
+            format!("{}{}{}{}{}", exclusions, word, suffix, description, value)
+        })
+        .collect()
+}
+
+fn zsh_function_name(program: &str, node: &Node) -> String {
+    function_name(&format!("{}{}", program, node.path.replace('/', "__")))
+}
+
+fn render_zsh(program: &str, nodes: &[Node]) -> String {
+    let shell = Shell::Zsh;
+    let mut out = format!("#compdef {}\n", program);
+    for node in nodes {
+        let children: Vec<&Node> = nodes
+            .iter()
+            .filter(|n| !n.path.is_empty() && n.parent_path() == node.path)
+            .collect();
+        let has_commands = !children.is_empty() || !node.shorthands.is_empty();
+        out.push_str(&format!("\n{}() {{\n", zsh_function_name(program, node)));
+        out.push_str("    local curcontext=\"$curcontext\" state line ret=1\n");
+        out.push_str("    _arguments -s -S -C");
+        for node_arg in &node.args {
+            if has_commands && node_arg.arg.positional {
+                continue;
+            }
+            for spec in zsh_arg_specs(node_arg) {
+                out.push_str(&format!(" \\\n        {}", shell.quote(&spec)));
+            }
+        }
+        if has_commands {
+            out.push_str(" \\\n        ': :->command' \\\n        '*:: :->args'");
+        }
+        out.push_str(" && ret=0\n");
+        if has_commands {
+            let mut entries = Vec::new();
+            for child in &children {
+                for (i, name) in child.names.iter().enumerate() {
+                    let name = zsh_escape(name, &[':']);
+                    entries.push(match (i, child.about) {
+                        (0, Some(about)) => format!("{}:{}", name, about),
+                        (0, None) => name,
+                        _ => format!("{}:alias of {}", name, child.names[0]),
+                    });
+                }
+            }
+            for (token, expansion) in &node.shorthands {
+                entries.push(format!(
+                    "{}:shorthand for {}",
+                    zsh_escape(token, &[':']),
+                    expansion
+                ));
+            }
+            out.push_str("    case $state in\n");
+            out.push_str("        command)\n");
+            out.push_str("            local -a commands\n");
+            out.push_str("            commands=(\n");
+            for entry in entries {
+                out.push_str(&format!("                {}\n", shell.quote(&entry)));
+            }
+            out.push_str("            )\n");
+            out.push_str("            _describe -t commands command commands && ret=0\n");
+            out.push_str("            ;;\n");
+            out.push_str("        args)\n");
+            out.push_str("            case $words[1] in\n");
+            for child in &children {
+                out.push_str(&format!(
+                    "                {}) {} && ret=0 ;;\n",
+                    child
+                        .names
+                        .iter()
+                        .map(|name| shell.quote(name))
+                        .collect::<Vec<_>>()
+                        .join("|"),
+                    zsh_function_name(program, child)
+                ));
+            }
+            out.push_str("            esac\n");
+            out.push_str("            ;;\n");
+            out.push_str("    esac\n");
+        }
+        out.push_str("    return ret\n");
+        out.push_str("}\n");
+    }
+    let function = function_name(program);
+    out.push_str(&format!(
+        "\nif [ \"$funcstack[1]\" = {} ]; then\n    {} \"$@\"\nelse\n    compdef {} {}\nfi\n",
+        shell.quote(&function),
+        function,
+        function,
+        shell.quote(program)
+    ));
+    out
+}
+
+fn render_fish(program: &str, nodes: &[Node]) -> String {
+    let shell = Shell::Fish;
+    let program = shell.quote(program);
+    let mut out = String::new();
+    for node in nodes {
+        let condition = if node.path.is_empty() {
+            "__fish_use_subcommand".to_string()
+        } else {
+            format!("__fish_seen_subcommand_from {}", node.names.join(" "))
+        };
+        let condition = shell.quote(&condition);
+        for sub in &node.subcommands {
+            out.push_str(&format!(
+                "complete -c {} -n {} -f -a {}\n",
+                program,
+                condition,
+                shell.quote(sub)
+            ));
+        }
+        for (token, expansion) in &node.shorthands {
+            out.push_str(&format!(
+                "complete -c {} -n {} -f -a {} -d {}\n",
+                program,
+                condition,
+                shell.quote(token),
+                shell.quote(&format!("shorthand for {}", expansion))
+            ));
+        }
+        for node_arg in &node.args {
+            let arg = node_arg.arg;
+            let mut line = format!("complete -c {} -n {}", program, condition);
+            for long in &node_arg.longs {
+                line.push_str(&format!(" -l {}", shell.quote(long)));
+            }
+            if let Some(short) = arg.short {
+                line.push_str(&format!(" -s {}", shell.quote(&short.to_string())));
+            }
+            if arg.takes_value {
+                let choices = value_choices(arg);
+                if !choices.is_empty() {
+                    line.push_str(&format!(
+                        " -x -a {}",
+                        shell.quote(&shell.quote_command(&choices))
+                    ));
+                } else if arg.is_path {
+                    line.push_str(" -r -F");
+                } else {
+                    line.push_str(" -x");
+                }
+                if let Some(range) = arg.range {
+                    line.push_str(&format!(" -d {}", shell.quote(&format!("[{}]", range))));
+                }
+            }
+            out.push_str(&line);
+            out.push('\n');
+        }
+    }
+    out
+}
+
+fn render_powershell(program: &str, nodes: &[Node]) -> String {
+    let shell = Shell::PowerShell;
+    let list = |words: &[String]| {
+        let quoted: Vec<String> = words
+            .iter()
+            .map(|w| format!("'{}'", w.replace('\'', "''")))
+            .collect();
+        format!("@({})", quoted.join(", "))
+    };
+    let mut paths = Vec::new();
+    for node in nodes.iter().filter(|n| !n.path.is_empty()) {
+        for name in &node.names {
+            paths.push(format!(
+                "'{}/{}' = '{}'",
+                node.parent_path().replace('\'', "''"),
+                name.replace('\'', "''"),
+                node.path.replace('\'', "''")
+            ));
+        }
+    }
+    let mut out = String::new();
+    out.push_str(&format!(
+        "Register-ArgumentCompleter -Native -CommandName {} -ScriptBlock {{\n",
+        shell.quote(program)
+    ));
+    out.push_str("    param($wordToComplete, $commandAst, $cursorPosition)\n");
+    out.push_str("    $path = ''\n");
+    out.push_str("    $prev = ''\n");
+    out.push_str(&format!("    $subcommands = @{{ {} }}\n", paths.join("; ")));
+    out.push_str(
+        "    foreach ($element in $commandAst.CommandElements | Select-Object -Skip 1) {\n",
+    );
+    out.push_str("        if ($element.Extent.StartOffset -ge $cursorPosition) { break }\n");
+    out.push_str("        $word = $element.ToString()\n");
+    out.push_str("        if ($word -eq $wordToComplete) { break }\n");
+    out.push_str(
+        "        if ($subcommands.ContainsKey(\"$path/$word\")) { $path = $subcommands[\"$path/$word\"] }\n",
+    );
+    out.push_str("        $prev = $word\n");
+    out.push_str("    }\n");
+    out.push_str("    $candidates = switch -CaseSensitive (\"$path|$prev\") {\n");
+    for node in nodes {
+        for node_arg in node.args.iter().filter(|a| a.arg.takes_value) {
+            for word in option_words(node_arg) {
+                out.push_str(&format!(
+                    "        {} {{ {}; break }}\n",
+                    shell.quote(&format!("{}|{}", node.path, word)),
+                    list(&value_choices(node_arg.arg))
+                ));
+            }
+        }
+    }
+    out.push_str("        default {\n");
+    out.push_str("            switch ($path) {\n");
+    for node in nodes {
+        out.push_str(&format!(
+            "                {} {{ {} }}\n",
+            format_args!("'{}'", node.path),
+            list(&node_words(node))
+        ));
+    }
+    out.push_str("            }\n");
+    out.push_str("        }\n");
+    out.push_str("    }\n");
+    out.push_str(
+        "    $candidates | Where-Object { $_ -like \"$wordToComplete*\" } | ForEach-Object {\n",
+    );
+    out.push_str("        [System.Management.Automation.CompletionResult]::new($_, $_, 'ParameterValue', $_)\n");
+    out.push_str("    }\n");
+    out.push_str("}\n");
+    out
+}
+
+fn home_dir() -> Option<PathBuf> {
+    env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from)
+}
+
+fn xdg_dir(var: &str, fallback: &str) -> Option<PathBuf> {
+    env::var_os(var)
+        .map(PathBuf::from)
+        .or_else(|| home_dir().map(|home| home.join(fallback)))
+}
+
+impl Shell {
+    pub fn detect() -> Option<Shell> {
+        if let Some(shell) = env::var_os("SHELL") {
+            let shell = PathBuf::from(shell);
+            let name = shell.file_stem()?.to_string_lossy().to_string();
+            return name.parse().ok();
+        }
+        if env::var_os("PSModulePath").is_some() {
+            return Some(Shell::PowerShell);
+        }
+        None
+    }
+
+    pub fn completion_path(self, program: &str) -> Option<PathBuf> {
+        match self {
+            Shell::Bash => xdg_dir("XDG_DATA_HOME", ".local/share")
+                .map(|dir| dir.join("bash-completion/completions").join(program)),
+            Shell::Zsh => home_dir().map(|home| home.join(".zfunc").join(format!("_{}", program))),
+            Shell::Fish => xdg_dir("XDG_CONFIG_HOME", ".config").map(|dir| {
+                dir.join("fish/completions")
+                    .join(format!("{}.fish", program))
+            }),
+            Shell::PowerShell => xdg_dir("XDG_CONFIG_HOME", ".config").map(|dir| {
+                dir.join("powershell/completions")
+                    .join(format!("{}.ps1", program))
+            }),
+        }
+    }
+}
+
+impl ArgParser {
+    fn completion_nodes(&self) -> Vec<Node<'_>> {
+        let mut nodes = Vec::new();
+        collect_nodes(
+            self,
+            String::new(),
+            Vec::new(),
+            Vec::new(),
+            !self.hide_internal_aliases,
+            None,
+            &mut nodes,
+        );
+        nodes.sort_by(|a, b| a.path.cmp(&b.path));
+        nodes
+    }
+
+    pub fn render_completion(&self, program: &str, shell: Shell) -> String {
+        crate::timings::time("completion rendering", || {
+            let nodes = self.completion_nodes();
+            match shell {
+                Shell::Bash => render_bash(program, &nodes),
+                Shell::Zsh => render_zsh(program, &nodes),
+                Shell::Fish => render_fish(program, &nodes),
+                Shell::PowerShell => render_powershell(program, &nodes),
+            }
+        })
+    }
+
+    pub fn completion_candidates(&self, words: &[String]) -> Vec<CompletionCandidate> {
+        let nodes = self.completion_nodes();
+        let (cur, before) = match words.split_last() {
+            Some((cur, before)) => (cur.as_str(), before),
+            None => ("", words),
+        };
+        let Some(node) = find_node(&nodes, before) else {
+            return Vec::new();
+        };
+        let prev = before.last().map(String::as_str).unwrap_or("");
+        let value_arg = node
+            .args
+            .iter()
+            .filter(|a| a.arg.takes_value)
+            .find(|a| option_words(a).iter().any(|w| w == prev));
+        let mut candidates = Vec::new();
+        if let Some(node_arg) = value_arg {
+            let arg = node_arg.arg;
+            let choices = value_choices(arg);
+            if !choices.is_empty() {
+                for choice in choices {
+                    candidates.push(CompletionCandidate {
+                        value: choice,
+                        source: CandidateSource::Choice(prev.to_string()),
+                    });
+                }
+            } else if arg.is_path {
+                candidates.push(CompletionCandidate {
+                    value: cur.to_string(),
+                    source: CandidateSource::Files(prev.to_string()),
+                });
+            }
+        } else {
+            for node_arg in &node.args {
+                for word in option_words(node_arg) {
+                    let is_alias = word
+                        .strip_prefix("--")
+                        .is_some_and(|long| node_arg.arg.long.as_deref() != Some(long));
+                    let name = node_arg.arg.name.clone();
+                    candidates.push(CompletionCandidate {
+                        value: word,
+                        source: if is_alias {
+                            CandidateSource::FlagAlias(name)
+                        } else {
+                            CandidateSource::Flag(name)
+                        },
+                    });
+                }
+            }
+            for child in nodes
+                .iter()
+                .filter(|n| !n.path.is_empty() && n.parent_path() == node.path)
+            {
+                for (i, name) in child.names.iter().enumerate() {
+                    candidates.push(CompletionCandidate {
+                        value: name.clone(),
+                        source: if i == 0 {
+                            CandidateSource::Subcommand
+                        } else {
+                            CandidateSource::SubcommandAlias(child.names[0].clone())
+                        },
+                    });
+                }
+            }
+            for (token, expansion) in &node.shorthands {
+                candidates.push(CompletionCandidate {
+                    value: token.clone(),
+                    source: CandidateSource::Shorthand(expansion.clone()),
+                });
+            }
+        }
+        candidates
+            .retain(|c| matches!(c.source, CandidateSource::Files(_)) || c.value.starts_with(cur));
+        candidates
+    }
+
+    pub fn print_completion_candidates(&self, words: &[String]) {
+        for candidate in self.completion_candidates(words) {
+            println!("{}", candidate);
+        }
+    }
+
+    pub fn list_flags(self) -> Self {
+        self.arg(LIST_SUBCOMMANDS)
+            .long(LIST_SUBCOMMANDS, LIST_SUBCOMMANDS)
+            .global(LIST_SUBCOMMANDS)
+            .hidden(LIST_SUBCOMMANDS)
+            .arg(LIST_OPTIONS)
+            .long(LIST_OPTIONS, LIST_OPTIONS)
+            .global(LIST_OPTIONS)
+            .hidden(LIST_OPTIONS)
+    }
+
+    pub fn render_list(&self, words: &[String]) -> Option<String> {
+        let (index, flag) = words.iter().enumerate().find_map(|(i, word)| {
+            let name = word.strip_prefix("--")?;
+            [LIST_SUBCOMMANDS, LIST_OPTIONS]
+                .into_iter()
+                .find(|flag| *flag == name && self.args.iter().any(|a| a.name == *flag))
+                .map(|flag| (i, flag))
+        })?;
+        let nodes = self.completion_nodes();
+        let node = find_node(&nodes, &words[..index])?;
+        let lines: Vec<String> = if flag == LIST_SUBCOMMANDS {
+            nodes
+                .iter()
+                .filter(|n| !n.path.is_empty() && n.parent_path() == node.path)
+                .map(|n| n.names[0].clone())
+                .collect()
+        } else {
+            node.args
+                .iter()
+                .filter_map(|a| a.arg.long.as_ref())
+                .map(|long| format!("--{}", long))
+                .collect()
+        };
+        Some(lines.iter().map(|line| format!("{}\n", line)).collect())
+    }
+
+    pub fn install_completions(&self, program: &str, shell: Option<Shell>) -> io::Result<PathBuf> {
+        let shell = shell.or_else(Shell::detect).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "could not detect the current shell",
+            )
+        })?;
+        let path = shell.completion_path(program).ok_or_else(|| {
+            io::Error::new(
+                io::ErrorKind::NotFound,
+                "could not determine the home directory",
+            )
+        })?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&path, self.render_completion(program, shell))?;
+        println!(
+            "Installed {} completions for {} to {}",
+            shell,
+            program,
+            path.display()
+        );
+        match shell {
+            Shell::Zsh => println!(
+                "Add `fpath=(~/.zfunc $fpath)` before `compinit` in ~/.zshrc if it is not there yet."
+            ),
+            Shell::PowerShell => println!(
+                "Add `. {}` to your $PROFILE to load them.",
+                Shell::PowerShell.escape_path(&path.display().to_string())
+            ),
+            Shell::Bash | Shell::Fish => println!("Open a new shell to start using them."),
+        }
+        Ok(path)
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::BTreeMap;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+
+use crate::{Arg, ArgParser, ConfigFormat, ParseError, Scope};
+
+#[derive(Debug, Default)]
+pub(crate) struct ConfigTable {
+    values: BTreeMap<String, Vec<String>>,
+    tables: BTreeMap<String, ConfigTable>,
+}
+
+#[derive(Debug)]
+pub(crate) struct ConfigFile {
+    path: PathBuf,
+    depth: usize,
+    root: ConfigTable,
+}
+
+impl ArgParser {
+    pub fn with_config_file(mut self, path: &str) -> Self {
+        self.config_files.push(expand_home(path));
+        self
+    }
+
+    pub(crate) fn load_config_files(&self, scope: &Scope) -> Result<Scope, ParseError> {
+        let mut scope = scope.clone();
+        for path in &self.config_files {
+            if let Some(root) = read_config_file(path)? {
+                scope.config_files.push(Rc::new(ConfigFile {
+                    path: path.to_path_buf(),
+                    depth: scope.command_path.len(),
+                    root,
+                }));
+            }
+        }
+        Ok(scope)
+    }
+}
+
+pub(crate) fn config_value(arg: &Arg, scope: &Scope) -> Option<(Vec<String>, String, PathBuf)> {
+    for file in scope.config_files.iter().rev() {
+        let path = scope.command_path.get(file.depth..).unwrap_or_default();
+        let mut tables = vec![&file.root];
+        for name in path {
+            match tables.last().and_then(|table| table.tables.get(name)) {
+                Some(table) => tables.push(table),
+                None => break,
+            }
+        }
+        for (depth, table) in tables.iter().enumerate().rev() {
+            if depth < path.len() && !arg.global {
+                continue;
+            }
+            if let Some(values) = table.values.get(&arg.name) {
+                let mut key = path[..depth].to_vec();
+                key.push(arg.name.clone());
+                return Some((values.clone(), key.join("."), file.path.clone()));
+            }
+        }
+    }
+    None
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    let home = std::env::var_os("HOME").or_else(|| std::env::var_os("USERPROFILE"));
+    match (path.strip_prefix("~/"), home) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+fn config_error(path: &Path, line: Option<usize>, message: &str) -> ParseError {
+    ParseError::ConfigFile {
+        path: path.display().to_string(),
+        line,
+        message: message.to_string(),
+    }
+}
+
+fn read_config_file(path: &Path) -> Result<Option<ConfigTable>, ParseError> {
+    let format = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .and_then(|ext| ext.parse::<ConfigFormat>().ok())
+        .ok_or_else(|| config_error(path, None, "expected a .toml or .json file"))?;
+    #[cfg_attr(not(any(feature = "toml", feature = "json")), allow(unused_variables))]
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(config_error(path, None, &err.to_string())),
+    };
+    match format {
+        #[cfg(feature = "toml")]
+        ConfigFormat::Toml => parse_toml(&contents)
+            .map(Some)
+            .map_err(|(line, message)| config_error(path, line, &message)),
+        #[cfg(feature = "json")]
+        ConfigFormat::Json => parse_json(&contents)
+            .map(Some)
+            .map_err(|message| config_error(path, None, &message)),
+        #[allow(unreachable_patterns)]
+        format => Err(config_error(
+            path,
+            None,
+            &format!("{} config files need the `{}` feature", format, format),
+        )),
+    }
+}
+
+#[cfg(feature = "json")]
+fn parse_json(contents: &str) -> Result<ConfigTable, String> {
+    use serde_json::Value;
+
+    fn scalar(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Bool(b) => Some(b.to_string()),
+            Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }
+    }
+
+    fn table(object: &serde_json::Map<String, Value>, at: &str) -> Result<ConfigTable, String> {
+        let mut out = ConfigTable::default();
+        for (key, value) in object {
+            let values = match value {
+                Value::Null => continue,
+                Value::Object(inner) => {
+                    out.tables
+                        .insert(key.clone(), table(inner, &format!("{}{}.", at, key))?);
+                    continue;
+                }
+                Value::Array(items) => items.iter().map(scalar).collect::<Option<Vec<_>>>(),
+                value => scalar(value).map(|value| vec![value]),
+            };
+            let values = values.ok_or_else(|| format!("unsupported value for '{}{}'", at, key))?;
+            out.values.insert(key.clone(), values);
+        }
+        Ok(out)
+    }
+
+    match serde_json::from_str(contents).map_err(|err| err.to_string())? {
+        Value::Object(object) => table(&object, ""),
+        _ => Err("expected a JSON object".to_string()),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn parse_toml(contents: &str) -> Result<ConfigTable, (Option<usize>, String)> {
+    use toml::Value;
+
+    fn scalar(value: &Value) -> Option<String> {
+        match value {
+            Value::String(s) => Some(s.clone()),
+            Value::Integer(n) => Some(n.to_string()),
+            Value::Float(f) => Some(f.to_string()),
+            Value::Boolean(b) => Some(b.to_string()),
+            Value::Datetime(d) => Some(d.to_string()),
+            Value::Array(_) | Value::Table(_) => None,
+        }
+    }
+
+    fn table(map: &toml::Table, at: &str) -> Result<ConfigTable, String> {
+        let mut out = ConfigTable::default();
+        for (key, value) in map {
+            let values = match value {
+                Value::Table(inner) => {
+                    out.tables
+                        .insert(key.clone(), table(inner, &format!("{}{}.", at, key))?);
+                    continue;
+                }
+                Value::Array(items) if items.iter().all(Value::is_table) && !items.is_empty() => {
+                    continue;
+                }
+                Value::Array(items) => items.iter().map(scalar).collect::<Option<Vec<_>>>(),
+                value => scalar(value).map(|value| vec![value]),
+            };
+            let values = values.ok_or_else(|| format!("unsupported value for '{}{}'", at, key))?;
+            out.values.insert(key.clone(), values);
+        }
+        Ok(out)
+    }
+
+    let map: toml::Table = toml::from_str(contents).map_err(|err| {
+        let line = err
+            .span()
+            .map(|span| contents[..span.start].matches('\n').count() + 1);
+        (line, err.message().to_string())
+    })?;
+    table(&map, "").map_err(|message| (None, message))
+}
@@ -0,0 +1,97 @@
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use crate::{Arg, ArgParser, ParseError};
+
+pub type ContextValidator = Arc<dyn Fn(&str, &Context) -> Result<(), String> + Send + Sync>;
+
+pub struct Context<'a> {
+    values: &'a HashMap<String, String>,
+    multi_values: &'a HashMap<String, Vec<String>>,
+    flags: &'a HashMap<String, bool>,
+    command_path: &'a [String],
+    env_file_vars: &'a HashMap<String, (String, PathBuf)>,
+}
+
+impl<'a> Context<'a> {
+    pub(crate) fn new(
+        values: &'a HashMap<String, String>,
+        multi_values: &'a HashMap<String, Vec<String>>,
+        flags: &'a HashMap<String, bool>,
+        command_path: &'a [String],
+        env_file_vars: &'a HashMap<String, (String, PathBuf)>,
+    ) -> Self {
+        Context {
+            values,
+            multi_values,
+            flags,
+            command_path,
+            env_file_vars,
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn get_many(&self, name: &str) -> Vec<&str> {
+        match self.multi_values.get(name) {
+            Some(values) => values.iter().map(String::as_str).collect(),
+            None => self.get(name).into_iter().collect(),
+        }
+    }
+
+    pub fn flag(&self, name: &str) -> bool {
+        self.flags.get(name).copied().unwrap_or(false)
+    }
+
+    pub fn command_path(&self) -> &[String] {
+        self.command_path
+    }
+
+    pub fn env(&self, var: &str) -> Option<String> {
+        std::env::var(var)
+            .ok()
+            .or_else(|| self.env_file_vars.get(var).map(|(value, _)| value.clone()))
+    }
+}
+
+impl Arg {
+    pub(crate) fn check_context(
+        &self,
+        flag: &str,
+        values: &[String],
+        context: &Context,
+    ) -> Result<(), ParseError> {
+        let Some(validator) = &self.context_validator else {
+            return Ok(());
+        };
+        for value in values {
+            if let Err(message) = validator(value, context) {
+                return Err(ParseError::InvalidValue {
+                    arg: flag.to_string(),
+                    value: if self.sensitive {
+                        crate::REDACTED.to_string()
+                    } else {
+                        value.clone()
+                    },
+                    message: Some(message).filter(|m| !m.is_empty()),
+                });
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ArgParser {
+    pub fn validator_with_context<F>(mut self, name: &str, validator: F) -> Self
+    where
+        F: 'static + Fn(&str, &Context) -> Result<(), String> + Send + Sync,
+    {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.context_validator = Some(Arc::new(validator));
+        }
+        self
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::{ArgMatches, ValueParser};
+
+pub trait FromArgValue: Sized {
+    fn from_arg_value(value: &str, arg: &str) -> Result<Self, String>;
+}
+
+impl<T> FromArgValue for T
+where
+    T: FromStr,
+    T::Err: fmt::Display,
+{
+    fn from_arg_value(value: &str, _arg: &str) -> Result<Self, String> {
+        value.parse().map_err(|err: T::Err| err.to_string())
+    }
+}
+
+pub(crate) fn value_parser<T: FromArgValue>(arg: &str) -> ValueParser {
+    let arg = arg.to_string();
+    Arc::new(move |value: &str| {
+        T::from_arg_value(value, &arg).map(|_| ()).map_err(|err| {
+            let type_name = std::any::type_name::<T>();
+            let type_name = type_name.rsplit("::").next().unwrap_or(type_name);
+            format!("expected {}: {}", type_name, err)
+        })
+    })
+}
+
+pub trait FromArgMatches: Sized {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, FieldError>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldError {
+    Missing {
+        field: String,
+        arg: String,
+    },
+    Invalid {
+        field: String,
+        arg: String,
+        value: String,
+        message: String,
+    },
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldError::Missing { field, arg } => {
+                write!(f, "Field '{}': missing value for argument {}", field, arg)
+            }
+            FieldError::Invalid {
+                field,
+                arg,
+                value,
+                message,
+            } => write!(
+                f,
+                "Field '{}': invalid value '{}' for argument {}: {}",
+                field, value, arg, message
+            ),
+        }
+    }
+}
+
+impl std::error::Error for FieldError {}
+
+impl ArgMatches {
+    pub fn try_into_struct<T: FromArgMatches>(&self) -> Result<T, FieldError> {
+        T::from_arg_matches(self)
+    }
+
+    pub fn get<T: FromArgValue>(&self, name: &str) -> Option<T> {
+        T::from_arg_value(self.values.get(name)?, name).ok()
+    }
+
+    pub fn get_or<T: FromArgValue>(&self, name: &str, default: T) -> T {
+        self.get(name).unwrap_or(default)
+    }
+
+    pub fn get_one<T: FromArgValue>(&self, name: &str) -> Option<T> {
+        self.get(name)
+    }
+
+    pub fn field<T: FromArgValue>(&self, field: &str, arg: &str) -> Result<Option<T>, FieldError> {
+        let Some(raw) = self.values.get(arg) else {
+            return Ok(None);
+        };
+        T::from_arg_value(raw, arg)
+            .map(Some)
+            .map_err(|message| FieldError::Invalid {
+                field: field.to_string(),
+                arg: arg.to_string(),
+                value: self.redacted_value(arg).unwrap_or(raw).to_string(),
+                message,
+            })
+    }
+
+    pub fn field_many<T: FromArgValue>(
+        &self,
+        field: &str,
+        arg: &str,
+    ) -> Result<Vec<T>, FieldError> {
+        self.get_many(arg)
+            .into_iter()
+            .map(|raw| {
+                T::from_arg_value(&raw, arg).map_err(|message| FieldError::Invalid {
+                    field: field.to_string(),
+                    arg: arg.to_string(),
+                    value: if self.is_sensitive(arg) {
+                        crate::REDACTED.to_string()
+                    } else {
+                        raw
+                    },
+                    message,
+                })
+            })
+            .collect()
+    }
+
+    pub fn required_field<T: FromArgValue>(&self, field: &str, arg: &str) -> Result<T, FieldError> {
+        self.field(field, arg)?.ok_or_else(|| FieldError::Missing {
+            field: field.to_string(),
+            arg: arg.to_string(),
+        })
+    }
+}
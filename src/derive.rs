@@ -0,0 +1,23 @@
+use crate::{ArgParser, FromArgMatches, ParseError};
+
+pub trait Arguments: FromArgMatches {
+    fn arg_parser() -> ArgParser;
+
+    fn from_args() -> Self {
+        let matches = Self::arg_parser().parse_env();
+        match Self::from_arg_matches(&matches) {
+            Ok(value) => value,
+            Err(err) => {
+                eprintln!("error: {}", err);
+                std::process::exit(2);
+            }
+        }
+    }
+
+    fn try_from_args(args: &[String]) -> Result<Self, ParseError> {
+        let matches = Self::arg_parser().try_parse(args)?;
+        Self::from_arg_matches(&matches).map_err(|err| ParseError::ValidationFailed {
+            message: err.to_string(),
+        })
+    }
+}
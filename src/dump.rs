@@ -0,0 +1,145 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::{ArgMatches, ArgParser};
+
+pub const DUMP_CONFIG: &str = "dump-config";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigFormat {
+    Toml,
+    Json,
+}
+
+impl fmt::Display for ConfigFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ConfigFormat::Toml => "toml",
+            ConfigFormat::Json => "json",
+        })
+    }
+}
+
+impl FromStr for ConfigFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "toml" => Ok(ConfigFormat::Toml),
+            "json" => Ok(ConfigFormat::Json),
+            other => Err(format!("unknown config format '{}'", other)),
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn dump_config_flag(self) -> Self {
+        self.arg(DUMP_CONFIG)
+            .long(DUMP_CONFIG, DUMP_CONFIG)
+            .takes_value(DUMP_CONFIG)
+            .choices(DUMP_CONFIG, &["toml", "json"])
+            .global(DUMP_CONFIG)
+    }
+}
+
+impl ArgMatches {
+    pub fn dump_config_format(&self) -> Option<ConfigFormat> {
+        self.values.get(DUMP_CONFIG)?.parse().ok()
+    }
+
+    pub fn render_config(&self, format: ConfigFormat) -> String {
+        let entries: Vec<(String, String, bool, String)> = self
+            .source_map()
+            .into_iter()
+            .filter(|(name, _)| name != DUMP_CONFIG)
+            .map(|(name, provenance)| {
+                let is_flag = !self.values.contains_key(&name);
+                let mut source = provenance.source.to_string();
+                if let Some(index) = provenance.argv_index {
+                    source.push_str(&format!(" (argv[{}])", index));
+                }
+                (name, provenance.value, is_flag, source)
+            })
+            .collect();
+        let mut out = String::new();
+        match format {
+            ConfigFormat::Toml => {
+                for (name, value, is_flag, source) in entries {
+                    out.push_str(&format!("# from {}\n", source));
+                    let value = if is_flag { value } else { quote(&value) };
+                    out.push_str(&format!("{} = {}\n", quote_key(&name), value));
+                }
+            }
+            ConfigFormat::Json => {
+                out.push('{');
+                for (i, (name, value, is_flag, source)) in entries.into_iter().enumerate() {
+                    if i > 0 {
+                        out.push(',');
+                    }
+                    let value = if is_flag { value } else { quote(&value) };
+                    out.push_str(&format!(
+                        "\n  {}: {{ \"value\": {}, \"source\": {} }}",
+                        quote(&name),
+                        value,
+                        quote(&source)
+                    ));
+                }
+                out.push_str("\n}\n");
+            }
+        }
+        out
+    }
+}
+
+fn quote_key(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+    {
+        key.to_string()
+    } else {
+        quote(key)
+    }
+}
+
+fn quote(value: &str) -> String {
+    let mut out = String::from("\"");
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn formats_parse_case_insensitively() {
+        assert_eq!("TOML".parse(), Ok(ConfigFormat::Toml));
+        assert_eq!("json".parse(), Ok(ConfigFormat::Json));
+        assert_eq!(
+            "yaml".parse::<ConfigFormat>(),
+            Err("unknown config format 'yaml'".to_string())
+        );
+        assert_eq!(ConfigFormat::Json.to_string(), "json");
+    }
+
+    #[test]
+    fn keys_are_quoted_only_when_needed() {
+        assert_eq!(quote_key("log-level"), "log-level");
+        assert_eq!(quote_key("a.b"), "\"a.b\"");
+        assert_eq!(quote_key(""), "\"\"");
+        assert_eq!(quote("a\"b\\c\n\u{1}"), "\"a\\\"b\\\\c\\n\\u0001\"");
+    }
+}
@@ -0,0 +1,143 @@
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use crate::{ArgParser, ParseError, Scope};
+
+impl ArgParser {
+    pub fn env_file(mut self, path: &str) -> Self {
+        self.env_files.push(PathBuf::from(path));
+        self
+    }
+
+    pub(crate) fn load_env_files(&self, scope: &Scope) -> Result<Scope, ParseError> {
+        let mut scope = scope.clone();
+        for path in &self.env_files {
+            for (key, value) in read_env_file(path)? {
+                scope.env_file_vars.insert(key, (value, path.clone()));
+            }
+        }
+        Ok(scope)
+    }
+}
+
+fn read_env_file(path: &Path) -> Result<Vec<(String, String)>, ParseError> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(err) => {
+            return Err(ParseError::EnvFile {
+                path: path.display().to_string(),
+                line: None,
+                message: err.to_string(),
+            })
+        }
+    };
+    let mut vars = Vec::new();
+    for (index, line) in contents.lines().enumerate() {
+        let entry = parse_line(line).map_err(|message| ParseError::EnvFile {
+            path: path.display().to_string(),
+            line: Some(index + 1),
+            message: message.to_string(),
+        })?;
+        vars.extend(entry);
+    }
+    Ok(vars)
+}
+
+fn parse_line(line: &str) -> Result<Option<(String, String)>, &'static str> {
+    let line = line.trim();
+    if line.is_empty() || line.starts_with('#') {
+        return Ok(None);
+    }
+    let line = line.strip_prefix("export ").unwrap_or(line);
+    let Some((key, value)) = line.split_once('=') else {
+        return Err("expected KEY=VALUE");
+    };
+    let key = key.trim();
+    if key.is_empty()
+        || !key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.')
+    {
+        return Err("invalid variable name");
+    }
+    let value = value.trim_start();
+    let (value, rest) = if let Some(quoted) = value.strip_prefix('"') {
+        let mut out = String::new();
+        let mut chars = quoted.char_indices();
+        let mut end = None;
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    end = Some(i + 1);
+                    break;
+                }
+                '\\' => match chars.next().map(|(_, c)| c) {
+                    Some('n') => out.push('\n'),
+                    Some('t') => out.push('\t'),
+                    Some(other) => out.push(other),
+                    None => return Err("unterminated quoted value"),
+                },
+                c => out.push(c),
+            }
+        }
+        let Some(end) = end else {
+            return Err("unterminated quoted value");
+        };
+        (out, &quoted[end..])
+    } else if let Some(quoted) = value.strip_prefix('\'') {
+        let Some(end) = quoted.find('\'') else {
+            return Err("unterminated quoted value");
+        };
+        (quoted[..end].to_string(), &quoted[end + 1..])
+    } else {
+        let value = match value.find(" #") {
+            Some(comment) => &value[..comment],
+            None => value,
+        };
+        (value.trim_end().to_string(), "")
+    };
+    let rest = rest.trim_start();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err("unexpected text after quoted value");
+    }
+    Ok(Some((key.to_string(), value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pair(key: &str, value: &str) -> Result<Option<(String, String)>, &'static str> {
+        Ok(Some((key.to_string(), value.to_string())))
+    }
+
+    #[test]
+    fn parses_plain_and_exported_lines() {
+        assert_eq!(parse_line("KEY=value"), pair("KEY", "value"));
+        assert_eq!(
+            parse_line("export KEY = value # note"),
+            pair("KEY", "value")
+        );
+        assert_eq!(parse_line("  # comment"), Ok(None));
+        assert_eq!(parse_line(""), Ok(None));
+    }
+
+    #[test]
+    fn parses_quoted_values() {
+        assert_eq!(parse_line(r#"A="x\ty\"z""#), pair("A", "x\ty\"z"));
+        assert_eq!(parse_line(r"B='raw \n' # done"), pair("B", r"raw \n"));
+        assert_eq!(parse_line("C=\"a # b\""), pair("C", "a # b"));
+    }
+
+    #[test]
+    fn rejects_malformed_lines() {
+        assert_eq!(parse_line("NOVALUE"), Err("expected KEY=VALUE"));
+        assert_eq!(parse_line("BAD KEY=1"), Err("invalid variable name"));
+        assert_eq!(parse_line("A=\"open"), Err("unterminated quoted value"));
+        assert_eq!(
+            parse_line("A='x' trailing"),
+            Err("unexpected text after quoted value")
+        );
+    }
+}
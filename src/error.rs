@@ -0,0 +1,526 @@
+use std::fmt;
+
+use crate::{GroupRule, ValueRange};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    MissingRequired {
+        name: String,
+    },
+    InvalidValue {
+        arg: String,
+        value: String,
+        message: Option<String>,
+    },
+    OutOfRange {
+        arg: String,
+        value: String,
+        range: ValueRange,
+    },
+    ConversionFailed {
+        arg: String,
+        value: String,
+        message: String,
+    },
+    InvalidChoice {
+        arg: String,
+        value: String,
+        choices: Vec<String>,
+        suggestion: Option<String>,
+    },
+    AmbiguousChoice {
+        arg: String,
+        value: String,
+        candidates: Vec<String>,
+    },
+    UnknownArgument {
+        token: String,
+        suggestion: Option<String>,
+    },
+    MissingValue {
+        arg: String,
+    },
+    WrongValueCount {
+        arg: String,
+        expected: usize,
+        actual: usize,
+    },
+    EmptyValue {
+        arg: String,
+        value: String,
+    },
+    DuplicateArgument {
+        arg: String,
+    },
+    ValueInCluster {
+        arg: String,
+        cluster: String,
+    },
+    DisallowedGlobal {
+        arg: String,
+        subcommand: String,
+    },
+    GroupViolation {
+        group: String,
+        rule: GroupRule,
+        members: Vec<String>,
+        found: Vec<String>,
+    },
+    Conflict {
+        arg: String,
+        other: String,
+    },
+    MissingDependency {
+        arg: String,
+        required: String,
+    },
+    RequiredIf {
+        arg: String,
+        other: String,
+        value: Option<String>,
+    },
+    RequiredUnless {
+        arg: String,
+        others: Vec<String>,
+    },
+    ValidationFailed {
+        message: String,
+    },
+    EnvFile {
+        path: String,
+        line: Option<usize>,
+        message: String,
+    },
+    ConfigFile {
+        path: String,
+        line: Option<usize>,
+        message: String,
+    },
+    NoGlobMatch {
+        arg: String,
+        pattern: String,
+    },
+    Script {
+        message: String,
+    },
+    RemovedArgument {
+        arg: String,
+        version: String,
+        replacement: Option<String>,
+    },
+    AmbiguousToken {
+        token: String,
+        positional: String,
+        subcommand: String,
+    },
+    ExperimentalArgument {
+        arg: String,
+        var: String,
+    },
+    HelpRequested {
+        help: String,
+    },
+    VersionRequested {
+        version: String,
+    },
+    Multiple(Vec<ParseError>),
+}
+
+impl ParseError {
+    pub(crate) fn from_errors(mut errors: Vec<ParseError>) -> Result<(), ParseError> {
+        match errors.len() {
+            0 => Ok(()),
+            1 => Err(errors.remove(0)),
+            _ => Err(ParseError::Multiple(errors)),
+        }
+    }
+
+    pub fn into_errors(self) -> Vec<ParseError> {
+        match self {
+            ParseError::Multiple(errors) => {
+                errors.into_iter().flat_map(|e| e.into_errors()).collect()
+            }
+            other => vec![other],
+        }
+    }
+
+    pub fn errors(&self) -> Vec<&ParseError> {
+        match self {
+            ParseError::Multiple(errors) => errors.iter().flat_map(|e| e.errors()).collect(),
+            other => vec![other],
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::MissingRequired { name } => {
+                write!(f, "Missing required argument: {}", name)
+            }
+            ParseError::InvalidValue {
+                arg,
+                value,
+                message,
+            } => {
+                write!(f, "Invalid value for argument: {} ('{}')", arg, value)?;
+                match message {
+                    Some(message) => write!(f, ": {}", message),
+                    None => Ok(()),
+                }
+            }
+            ParseError::ConversionFailed {
+                arg,
+                value,
+                message,
+            } => write!(
+                f,
+                "Invalid value for argument: {} ('{}'): {}",
+                arg, value, message
+            ),
+            ParseError::OutOfRange { arg, value, range } => write!(
+                f,
+                "Value for argument {} is out of range [{}] ('{}')",
+                arg, range, value
+            ),
+            ParseError::InvalidChoice {
+                arg,
+                value,
+                choices,
+                suggestion,
+            } => {
+                write!(f, "Invalid value for argument: {} ('{}')", arg, value)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "; did you mean '{}'?", suggestion),
+                    None => write!(f, "; expected one of: {}", choices.join(", ")),
+                }
+            }
+            ParseError::AmbiguousChoice {
+                arg,
+                value,
+                candidates,
+            } => write!(
+                f,
+                "Ambiguous value for argument: {} ('{}'); could be: {}",
+                arg,
+                value,
+                candidates.join(", ")
+            ),
+            ParseError::UnknownArgument { token, suggestion } => {
+                write!(f, "Unknown argument: {}", token)?;
+                match suggestion {
+                    Some(suggestion) => write!(f, "; did you mean '{}'?", suggestion),
+                    None => Ok(()),
+                }
+            }
+            ParseError::MissingValue { arg } => write!(f, "Missing value for argument: {}", arg),
+            ParseError::WrongValueCount {
+                arg,
+                expected,
+                actual,
+            } => write!(
+                f,
+                "Argument {} takes {} values but {} were given",
+                arg, expected, actual
+            ),
+            ParseError::EmptyValue { arg, value } => {
+                write!(f, "Empty value for argument: {} ('{}')", arg, value)
+            }
+            ParseError::DuplicateArgument { arg } => {
+                write!(f, "Argument {} was given more than once", arg)
+            }
+            ParseError::ValueInCluster { arg, cluster } => write!(
+                f,
+                "Option {} takes a value and must come last in {}",
+                arg, cluster
+            ),
+            ParseError::DisallowedGlobal { arg, subcommand } => write!(
+                f,
+                "Argument {} is not available for subcommand '{}'",
+                arg, subcommand
+            ),
+            ParseError::GroupViolation {
+                group,
+                rule,
+                members,
+                found,
+            } => {
+                write!(
+                    f,
+                    "Group '{}' requires {} of [{}]",
+                    group,
+                    rule,
+                    members.join(", ")
+                )?;
+                if found.is_empty() {
+                    write!(f, " but none were given")
+                } else {
+                    write!(f, " but got [{}]", found.join(", "))
+                }
+            }
+            ParseError::Conflict { arg, other } => {
+                write!(f, "Argument {} cannot be used with {}", arg, other)
+            }
+            ParseError::MissingDependency { arg, required } => {
+                write!(f, "Argument {} requires {} to also be given", arg, required)
+            }
+            ParseError::RequiredIf { arg, other, value } => match value {
+                Some(value) => write!(
+                    f,
+                    "Argument {} is required when {} is '{}'",
+                    arg, other, value
+                ),
+                None => write!(f, "Argument {} is required when {} is given", arg, other),
+            },
+            ParseError::RequiredUnless { arg, others } => write!(
+                f,
+                "Argument {} is required unless {} is given",
+                arg,
+                others.join(" or ")
+            ),
+            ParseError::ValidationFailed { message } => write!(f, "Invalid arguments: {}", message),
+            ParseError::EnvFile {
+                path,
+                line,
+                message,
+            } => match line {
+                Some(line) => write!(f, "Invalid env file {}:{}: {}", path, line, message),
+                None => write!(f, "Could not read env file {}: {}", path, message),
+            },
+            ParseError::ConfigFile {
+                path,
+                line,
+                message,
+            } => match line {
+                Some(line) => write!(f, "Invalid config file {}:{}: {}", path, line, message),
+                None => write!(f, "Could not read config file {}: {}", path, message),
+            },
+            ParseError::NoGlobMatch { arg, pattern } => {
+                write!(f, "No files match '{}' for {}", pattern, arg)
+            }
+            ParseError::Script { message } => write!(f, "Invalid script line: {}", message),
+            ParseError::RemovedArgument {
+                arg,
+                version,
+                replacement,
+            } => {
+                write!(f, "Argument {} was removed in {}", arg, version)?;
+                match replacement {
+                    Some(replacement) => write!(f, "; use {} instead", replacement),
+                    None => Ok(()),
+                }
+            }
+            ParseError::AmbiguousToken {
+                token,
+                positional,
+                subcommand,
+            } => write!(
+                f,
+                "'{}' could fill <{}> or select subcommand '{}'",
+                token, positional, subcommand
+            ),
+            ParseError::ExperimentalArgument { arg, var } => write!(
+                f,
+                "Argument {} is experimental; set {}=1 to enable it",
+                arg, var
+            ),
+            ParseError::HelpRequested { help } => f.write_str(&crate::strip_ansi(help)),
+            ParseError::VersionRequested { version } => f.write_str(version),
+            ParseError::Multiple(errors) => {
+                write!(f, "Found {} problems:", errors.len())?;
+                for error in errors {
+                    write!(f, "\n  - {}", error)?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecError {
+    GroupCycle {
+        groups: Vec<String>,
+    },
+    UnknownGroupMember {
+        group: String,
+        member: String,
+    },
+    UnknownDefaultSubcommand {
+        name: String,
+    },
+    AmbiguousGrammar {
+        positional: String,
+        subcommand: String,
+    },
+    AmbiguousVariadics {
+        first: String,
+        second: String,
+    },
+    MisplacedTrailingVarArg {
+        arg: String,
+    },
+    InvalidNegatable {
+        arg: String,
+        message: String,
+    },
+    UnknownProfileArg {
+        profile: String,
+        arg: String,
+    },
+    UnknownRelation {
+        arg: String,
+        other: String,
+    },
+    InvalidAbbreviation {
+        token: String,
+        message: String,
+    },
+    DuplicateArg {
+        name: String,
+    },
+    DuplicateFlag {
+        first: String,
+        second: String,
+    },
+    InvalidDocument {
+        path: String,
+        message: String,
+    },
+    ModuleConflict {
+        module: String,
+        conflict: String,
+    },
+}
+
+impl fmt::Display for SpecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecError::GroupCycle { groups } => {
+                write!(f, "Argument groups form a cycle: {}", groups.join(" -> "))
+            }
+            SpecError::UnknownGroupMember { group, member } => write!(
+                f,
+                "Group '{}' refers to unknown argument or group '{}'",
+                group, member
+            ),
+            SpecError::UnknownDefaultSubcommand { name } => {
+                write!(f, "Default subcommand '{}' is not defined", name)
+            }
+            SpecError::AmbiguousGrammar {
+                positional,
+                subcommand,
+            } => write!(
+                f,
+                "Positional '{}' competes with subcommand '{}'; set an ambiguity policy",
+                positional, subcommand
+            ),
+            SpecError::AmbiguousVariadics { first, second } => write!(
+                f,
+                "Variadic positionals '{}' and '{}' are both greedy; make one lazy",
+                first, second
+            ),
+            SpecError::MisplacedTrailingVarArg { arg } => write!(
+                f,
+                "Trailing positional '{}' must be the last and only variadic positional",
+                arg
+            ),
+            SpecError::InvalidNegatable { arg, message } => {
+                write!(f, "Argument '{}' cannot be negatable: {}", arg, message)
+            }
+            SpecError::UnknownProfileArg { profile, arg } => {
+                write!(f, "Profile '{}' sets unknown argument '{}'", profile, arg)
+            }
+            SpecError::UnknownRelation { arg, other } => write!(
+                f,
+                "Argument '{}' refers to unknown argument or group '{}'",
+                arg, other
+            ),
+            SpecError::InvalidAbbreviation { token, message } => {
+                write!(f, "Abbreviation '{}' is invalid: {}", token, message)
+            }
+            SpecError::DuplicateArg { name } => {
+                write!(f, "Argument '{}' is defined more than once", name)
+            }
+            SpecError::DuplicateFlag { first, second } => {
+                write!(f, "Arguments '{}' and '{}' share a flag", first, second)
+            }
+            SpecError::InvalidDocument { path, message } => {
+                write!(f, "Invalid spec document at {}: {}", path, message)
+            }
+            SpecError::ModuleConflict { module, conflict } => {
+                write!(f, "Module '{}' redefines {}", module, conflict)
+            }
+        }
+    }
+}
+
+impl std::error::Error for SpecError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing(name: &str) -> ParseError {
+        ParseError::MissingRequired {
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn from_errors_collapses_single_errors() {
+        assert_eq!(ParseError::from_errors(Vec::new()), Ok(()));
+        assert_eq!(
+            ParseError::from_errors(vec![missing("a")]),
+            Err(missing("a"))
+        );
+        assert_eq!(
+            ParseError::from_errors(vec![missing("a"), missing("b")]),
+            Err(ParseError::Multiple(vec![missing("a"), missing("b")]))
+        );
+    }
+
+    #[test]
+    fn nested_errors_flatten() {
+        let error = ParseError::Multiple(vec![
+            missing("a"),
+            ParseError::Multiple(vec![missing("b"), missing("c")]),
+        ]);
+        assert_eq!(
+            error.errors(),
+            vec![&missing("a"), &missing("b"), &missing("c")]
+        );
+        assert_eq!(
+            error.into_errors(),
+            vec![missing("a"), missing("b"), missing("c")]
+        );
+    }
+
+    #[test]
+    fn display_lists_choices() {
+        let error = ParseError::InvalidChoice {
+            arg: "--color".to_string(),
+            value: "purple".to_string(),
+            choices: vec!["auto".to_string(), "never".to_string()],
+            suggestion: None,
+        };
+        assert_eq!(
+            error.to_string(),
+            "Invalid value for argument: --color ('purple'); expected one of: auto, never"
+        );
+    }
+
+    #[test]
+    fn display_includes_suggestions() {
+        let error = ParseError::UnknownArgument {
+            token: "--verbos".to_string(),
+            suggestion: Some("--verbose".to_string()),
+        };
+        assert_eq!(
+            error.to_string(),
+            "Unknown argument: --verbos; did you mean '--verbose'?"
+        );
+    }
+}
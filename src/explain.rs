@@ -0,0 +1,165 @@
+use std::cell::RefCell;
+use std::fmt;
+use std::rc::Rc;
+
+use crate::{ArgMatches, ArgParser, ParseError, Scope, ValueSource};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExplainStep {
+    Flag {
+        index: usize,
+        token: String,
+        arg: String,
+    },
+    Value {
+        index: usize,
+        token: String,
+        arg: String,
+        value: String,
+    },
+    Positional {
+        index: usize,
+        arg: String,
+        value: String,
+    },
+    Extra {
+        index: usize,
+        token: String,
+    },
+    Subcommand {
+        index: usize,
+        name: String,
+        implicit: bool,
+    },
+    Fallback {
+        command: String,
+        arg: String,
+        value: String,
+        source: ValueSource,
+    },
+}
+
+impl fmt::Display for ExplainStep {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExplainStep::Flag { index, token, arg } => {
+                write!(f, "argv[{}] `{}` sets flag `{}`", index, token, arg)
+            }
+            ExplainStep::Value {
+                index,
+                token,
+                arg,
+                value,
+            } => write!(
+                f,
+                "argv[{}] `{}` sets `{}` to '{}'",
+                index, token, arg, value
+            ),
+            ExplainStep::Positional { index, arg, value } => {
+                write!(f, "argv[{}] '{}' fills positional `{}`", index, value, arg)
+            }
+            ExplainStep::Extra { index, token } => {
+                write!(
+                    f,
+                    "argv[{}] '{}' is kept as an extra positional",
+                    index, token
+                )
+            }
+            ExplainStep::Subcommand {
+                index,
+                name,
+                implicit: false,
+            } => write!(f, "argv[{}] `{}` enters subcommand `{}`", index, name, name),
+            ExplainStep::Subcommand {
+                index,
+                name,
+                implicit: true,
+            } => write!(
+                f,
+                "argv[{}] enters default subcommand `{}` without consuming the token",
+                index, name
+            ),
+            ExplainStep::Fallback {
+                command,
+                arg,
+                value,
+                source,
+            } => {
+                let arg = if command.is_empty() {
+                    arg.clone()
+                } else {
+                    format!("{} {}", command, arg)
+                };
+                write!(
+                    f,
+                    "`{}` is not given; '{}' comes from {}",
+                    arg, value, source
+                )
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Explanation {
+    pub steps: Vec<ExplainStep>,
+    pub error: Option<ParseError>,
+}
+
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for step in &self.steps {
+            writeln!(f, "{}", step)?;
+        }
+        match &self.error {
+            Some(ParseError::HelpRequested { .. }) => writeln!(f, "help would be printed"),
+            Some(ParseError::VersionRequested { .. }) => writeln!(f, "version would be printed"),
+            Some(err) => writeln!(f, "parsing would fail: {}", err),
+            None => Ok(()),
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn explain(&self, args: &[String]) -> Explanation {
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let scope = Scope {
+            program: self.program(args),
+            trace: Some(trace.clone()),
+            ..Scope::default()
+        };
+        let result = self.parse_in_scope(args, &scope);
+        let mut steps = trace.take();
+        match result {
+            Ok(matches) => {
+                push_fallbacks(&matches, String::new(), &mut steps);
+                Explanation { steps, error: None }
+            }
+            Err(err) => Explanation {
+                steps,
+                error: Some(err),
+            },
+        }
+    }
+}
+
+fn push_fallbacks(matches: &ArgMatches, command: String, steps: &mut Vec<ExplainStep>) {
+    for (arg, provenance) in matches.source_map() {
+        if provenance.source != ValueSource::CommandLine {
+            steps.push(ExplainStep::Fallback {
+                command: command.clone(),
+                arg,
+                value: provenance.value,
+                source: provenance.source,
+            });
+        }
+    }
+    if let Some((name, sub)) = matches.subcommand() {
+        let command = if command.is_empty() {
+            name.to_string()
+        } else {
+            format!("{} {}", command, name)
+        };
+        push_fallbacks(sub, command, steps);
+    }
+}
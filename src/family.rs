@@ -0,0 +1,130 @@
+use std::sync::Arc;
+
+use crate::{ArgMatches, ArgParser, FieldError, FromArgMatches, ValueSource};
+
+impl ArgParser {
+    pub fn option_family(mut self, prefix: &str, fields: &[&str]) -> Self {
+        for field in fields {
+            let name = format!("{}-{}", prefix, field);
+            self = self.arg(&name).long(&name, &name).takes_value(&name);
+        }
+        self
+    }
+
+    pub fn validate_family<F>(mut self, prefix: &str, validator: F) -> Self
+    where
+        F: 'static + Fn(&ArgMatches) -> Result<(), String> + Send + Sync,
+    {
+        let prefix = prefix.to_string();
+        self.matches_validators
+            .push(Arc::new(move |matches: &ArgMatches| {
+                match matches.family_view(&prefix) {
+                    Some(view) => {
+                        validator(&view).map_err(|message| format!("--{}-*: {}", prefix, message))
+                    }
+                    None => Ok(()),
+                }
+            }));
+        self
+    }
+}
+
+impl ArgMatches {
+    pub fn family<T: FromArgMatches>(&self, prefix: &str) -> Result<Option<T>, FieldError> {
+        match self.family_view(prefix) {
+            Some(view) => T::from_arg_matches(&view)
+                .map(Some)
+                .map_err(|err| match err {
+                    FieldError::Missing { field, arg } => FieldError::Missing {
+                        field,
+                        arg: format!("{}-{}", prefix, arg),
+                    },
+                    FieldError::Invalid {
+                        field,
+                        arg,
+                        value,
+                        message,
+                    } => FieldError::Invalid {
+                        field,
+                        arg: format!("{}-{}", prefix, arg),
+                        value,
+                        message,
+                    },
+                }),
+            None => Ok(None),
+        }
+    }
+
+    fn family_view(&self, prefix: &str) -> Option<ArgMatches> {
+        let prefix = format!("{}-", prefix);
+        let member = |name: &str| name.strip_prefix(&prefix).map(str::to_string);
+        let supplied = self
+            .sources
+            .iter()
+            .any(|(name, source)| name.starts_with(&prefix) && *source != ValueSource::Default);
+        if !supplied {
+            return None;
+        }
+        Some(ArgMatches {
+            values: self
+                .values
+                .iter()
+                .filter_map(|(name, value)| Some((member(name)?, value.clone())))
+                .collect(),
+            flags: self
+                .flags
+                .iter()
+                .filter_map(|(name, set)| Some((member(name)?, *set)))
+                .collect(),
+            positionals: Vec::new(),
+            sections: Vec::new(),
+            sensitive: self
+                .sensitive
+                .iter()
+                .filter_map(|name| member(name))
+                .collect(),
+            sources: self
+                .sources
+                .iter()
+                .filter_map(|(name, source)| Some((member(name)?, source.clone())))
+                .collect(),
+            argv_indices: self
+                .argv_indices
+                .iter()
+                .filter_map(|(name, index)| Some((member(name)?, *index)))
+                .collect(),
+            source_keys: self
+                .source_keys
+                .iter()
+                .filter_map(|(name, key)| Some((member(name)?, key.clone())))
+                .collect(),
+            deprecations: Vec::new(),
+            key_values: self
+                .key_values
+                .iter()
+                .filter_map(|(name, entries)| Some((member(name)?, entries.clone())))
+                .collect(),
+            multi_values: self
+                .multi_values
+                .iter()
+                .filter_map(|(name, values)| Some((member(name)?, values.clone())))
+                .collect(),
+            occurrences: self
+                .occurrences
+                .iter()
+                .filter_map(|(name, count)| Some((member(name)?, *count)))
+                .collect(),
+            grouped_values: self
+                .grouped_values
+                .iter()
+                .filter_map(|(name, groups)| Some((member(name)?, groups.clone())))
+                .collect(),
+            os_values: self
+                .os_values
+                .iter()
+                .filter_map(|(name, values)| Some((member(name)?, values.clone())))
+                .collect(),
+            subcommand: None,
+        })
+    }
+}
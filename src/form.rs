@@ -0,0 +1,175 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{Arg, ArgMatches, ArgParser};
+
+fn flag_token(arg: &Arg) -> Option<String> {
+    match (&arg.long, arg.short) {
+        (Some(long), _) => Some(format!("--{}", long)),
+        (None, Some(short)) => Some(format!("-{}", short)),
+        (None, None) => None,
+    }
+}
+
+fn read_answer<R: BufRead>(input: &mut R) -> io::Result<String> {
+    let mut line = String::new();
+    if input.read_line(&mut line)? == 0 {
+        return Err(io::Error::new(
+            io::ErrorKind::UnexpectedEof,
+            "form input ended early",
+        ));
+    }
+    Ok(line.trim().to_string())
+}
+
+fn ask_toggle<R, W>(arg: &Arg, input: &mut R, output: &mut W) -> io::Result<bool>
+where
+    R: BufRead,
+    W: Write,
+{
+    let default = arg.effective_default() == Some("true");
+    let hint = if default { "[Y/n]" } else { "[y/N]" };
+    write!(output, "{} {}: ", arg.display(), hint)?;
+    output.flush()?;
+    let answer = read_answer(input)?.to_ascii_lowercase();
+    Ok(match answer.as_str() {
+        "" => default,
+        other => other == "y" || other == "yes",
+    })
+}
+
+fn ask_select<R, W>(
+    label: &str,
+    options: &[String],
+    optional: bool,
+    input: &mut R,
+    output: &mut W,
+) -> io::Result<Option<String>>
+where
+    R: BufRead,
+    W: Write,
+{
+    loop {
+        writeln!(output, "{}:", label)?;
+        for (i, option) in options.iter().enumerate() {
+            writeln!(output, "  {}) {}", i + 1, option)?;
+        }
+        if optional {
+            write!(output, "choice (empty to skip): ")?;
+        } else {
+            write!(output, "choice: ")?;
+        }
+        output.flush()?;
+        let answer = read_answer(input)?;
+        if answer.is_empty() && optional {
+            return Ok(None);
+        }
+        if let Ok(index) = answer.parse::<usize>() {
+            if let Some(option) = options.get(index.wrapping_sub(1)) {
+                return Ok(Some(option.clone()));
+            }
+        }
+        if options.contains(&answer) {
+            return Ok(Some(answer));
+        }
+        writeln!(output, "Please pick one of the listed options.")?;
+    }
+}
+
+fn ask_text<R, W>(arg: &Arg, input: &mut R, output: &mut W) -> io::Result<Option<String>>
+where
+    R: BufRead,
+    W: Write,
+{
+    let flag = flag_token(arg).unwrap_or_else(|| arg.display().to_string());
+    loop {
+        match (arg.effective_default(), arg.sensitive) {
+            (Some(default), false) => write!(output, "{} [{}]: ", arg.display(), default)?,
+            _ if arg.required => write!(output, "{} (required): ", arg.display())?,
+            _ => write!(output, "{}: ", arg.display())?,
+        }
+        output.flush()?;
+        let answer = read_answer(input)?;
+        if answer.is_empty() {
+            if arg.required && arg.effective_default().is_none() {
+                writeln!(output, "A value is required.")?;
+                continue;
+            }
+            return Ok(None);
+        }
+        match arg.accept_values(&flag, &answer) {
+            Ok(_) => return Ok(Some(answer)),
+            Err(err) => writeln!(output, "{}", err)?,
+        }
+    }
+}
+
+impl ArgParser {
+    fn collect_form<R, W>(
+        &self,
+        tokens: &mut Vec<String>,
+        input: &mut R,
+        output: &mut W,
+    ) -> io::Result<()>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut positionals_open = true;
+        for arg in self.args.iter().filter(|a| !a.is_hidden()) {
+            if arg.positional {
+                if !positionals_open {
+                    continue;
+                }
+                match ask_text(arg, input, output)? {
+                    Some(value) => tokens.push(value),
+                    None => positionals_open = false,
+                }
+                continue;
+            }
+            let Some(flag) = flag_token(arg) else {
+                continue;
+            };
+            if !arg.takes_value {
+                if ask_toggle(arg, input, output)? {
+                    tokens.push(flag);
+                }
+                continue;
+            }
+            let value = if arg.choices.is_empty() {
+                ask_text(arg, input, output)?
+            } else {
+                let optional = !arg.required || arg.effective_default().is_some();
+                ask_select(arg.display(), &arg.choices, optional, input, output)?
+            };
+            if let Some(value) = value {
+                tokens.push(flag);
+                tokens.push(value);
+            }
+        }
+        if self.subcommands.is_empty() {
+            return Ok(());
+        }
+        let mut names: Vec<String> = self.subcommands.keys().cloned().collect();
+        names.sort();
+        if let Some(name) = ask_select("subcommand", &names, true, input, output)? {
+            tokens.push(name.clone());
+            self.subcommands[&name].collect_form(tokens, input, output)?;
+        }
+        Ok(())
+    }
+
+    pub fn run_form_with<R, W>(&self, input: &mut R, output: &mut W) -> io::Result<ArgMatches>
+    where
+        R: BufRead,
+        W: Write,
+    {
+        let mut tokens = Vec::new();
+        self.collect_form(&mut tokens, input, output)?;
+        self.parse_args_only(&tokens)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidInput, err))
+    }
+
+    pub fn run_form(&self) -> io::Result<ArgMatches> {
+        self.run_form_with(&mut io::stdin().lock(), &mut io::stderr())
+    }
+}
@@ -0,0 +1,33 @@
+use std::ops::Deref;
+use std::sync::Arc;
+
+use crate::ArgParser;
+
+#[derive(Clone)]
+pub struct FrozenParser(Arc<ArgParser>);
+
+impl ArgParser {
+    pub fn freeze(self) -> FrozenParser {
+        FrozenParser(Arc::new(self))
+    }
+}
+
+impl FrozenParser {
+    pub fn thaw(&self) -> ArgParser {
+        self.0.as_ref().clone()
+    }
+}
+
+impl Deref for FrozenParser {
+    type Target = ArgParser;
+
+    fn deref(&self) -> &ArgParser {
+        &self.0
+    }
+}
+
+impl From<ArgParser> for FrozenParser {
+    fn from(parser: ArgParser) -> Self {
+        parser.freeze()
+    }
+}
@@ -0,0 +1,195 @@
+use std::path::{Path, PathBuf};
+
+pub(crate) fn is_pattern(word: &str) -> bool {
+    word.contains(['*', '?', '['])
+}
+
+pub(crate) fn expand(pattern: &str) -> Option<Vec<String>> {
+    if !cfg!(windows) || !is_pattern(pattern) {
+        return None;
+    }
+    let absolute = pattern.starts_with(['/', '\\']);
+    let mut parts = pattern.split(['/', '\\']).filter(|p| !p.is_empty());
+    let mut bases = match parts.next() {
+        Some(drive) if drive.ends_with(':') => vec![PathBuf::from(format!("{}\\", drive))],
+        first => {
+            let root = PathBuf::from(if absolute { "\\" } else { "" });
+            let mut bases = vec![root];
+            if let Some(first) = first {
+                bases = step(bases, first);
+            }
+            bases
+        }
+    };
+    for part in parts {
+        bases = step(bases, part);
+    }
+    let mut found: Vec<String> = bases
+        .into_iter()
+        .filter(|path| path.exists())
+        .map(|path| path.display().to_string())
+        .collect();
+    found.sort();
+    found.dedup();
+    Some(found)
+}
+
+fn step(bases: Vec<PathBuf>, part: &str) -> Vec<PathBuf> {
+    if part == "**" {
+        let mut all = Vec::new();
+        for base in bases {
+            descend(&base, &mut all);
+        }
+        return all;
+    }
+    if !is_pattern(part) {
+        return bases.into_iter().map(|base| base.join(part)).collect();
+    }
+    let mut next = Vec::new();
+    for base in bases {
+        let dir = if base.as_os_str().is_empty() {
+            Path::new(".")
+        } else {
+            base.as_path()
+        };
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name.starts_with('.') && !part.starts_with('.') {
+                continue;
+            }
+            if matches(part, &name) {
+                next.push(base.join(name));
+            }
+        }
+    }
+    next
+}
+
+fn descend(base: &Path, out: &mut Vec<PathBuf>) {
+    out.push(base.to_path_buf());
+    let dir = if base.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        base
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let hidden = entry.file_name().to_string_lossy().starts_with('.');
+        if !hidden && entry.file_type().is_ok_and(|t| t.is_dir()) {
+            descend(&base.join(entry.file_name()), out);
+        }
+    }
+}
+
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.to_lowercase().chars().collect();
+    let name: Vec<char> = name.to_lowercase().chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+                continue;
+            }
+            Some('?') => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            Some('[') => {
+                if let Some((hit, len)) = class(&pattern[p..], name[n]) {
+                    if hit {
+                        p += len;
+                        n += 1;
+                        continue;
+                    }
+                } else if name[n] == '[' {
+                    p += 1;
+                    n += 1;
+                    continue;
+                }
+            }
+            Some(&c) if c == name[n] => {
+                p += 1;
+                n += 1;
+                continue;
+            }
+            _ => {}
+        }
+        match backtrack {
+            Some((star, at)) => {
+                p = star + 1;
+                n = at + 1;
+                backtrack = Some((star, at + 1));
+            }
+            None => return false,
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+fn class(pattern: &[char], c: char) -> Option<(bool, usize)> {
+    let mut i = 1;
+    let negated = matches!(pattern.get(i), Some('!' | '^'));
+    if negated {
+        i += 1;
+    }
+    let mut hit = false;
+    let mut first = true;
+    while let Some(&start) = pattern.get(i) {
+        if start == ']' && !first {
+            return Some((hit != negated, i + 1));
+        }
+        first = false;
+        match (pattern.get(i + 1), pattern.get(i + 2)) {
+            (Some('-'), Some(&end)) if end != ']' => {
+                hit |= (start..=end).contains(&c);
+                i += 3;
+            }
+            _ => {
+                hit |= start == c;
+                i += 1;
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wildcards() {
+        assert!(matches("*.rs", "main.rs"));
+        assert!(matches("*.RS", "lib.rs"));
+        assert!(!matches("*.rs", "main.rc"));
+        assert!(matches("a?c", "abc"));
+        assert!(!matches("a?c", "ac"));
+        assert!(matches("*a*b", "xxaxxb"));
+    }
+
+    #[test]
+    fn character_classes() {
+        assert!(matches("file[0-9].txt", "file7.txt"));
+        assert!(!matches("file[0-9].txt", "filex.txt"));
+        assert!(matches("[!a]*", "bcd"));
+        assert!(!matches("[^a]*", "abc"));
+        assert!(matches("[]]", "]"));
+        assert!(matches("[a", "[a"));
+    }
+
+    #[test]
+    fn pattern_detection() {
+        assert!(is_pattern("*.rs"));
+        assert!(is_pattern("src/[ab].rs"));
+        assert!(!is_pattern("src/main.rs"));
+    }
+}
@@ -0,0 +1,251 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use crate::help::mention;
+use crate::{Arg, ParseError};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GroupRule {
+    ExactlyOne,
+    AtMostOne,
+    AtLeastOne,
+}
+
+impl GroupRule {
+    fn allows(self, count: usize) -> bool {
+        match self {
+            GroupRule::ExactlyOne => count == 1,
+            GroupRule::AtMostOne => count <= 1,
+            GroupRule::AtLeastOne => count >= 1,
+        }
+    }
+}
+
+impl fmt::Display for GroupRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            GroupRule::ExactlyOne => "exactly one",
+            GroupRule::AtMostOne => "at most one",
+            GroupRule::AtLeastOne => "at least one",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ArgGroup {
+    pub name: String,
+    pub rule: GroupRule,
+    pub members: Vec<String>,
+}
+
+pub(crate) fn is_present(name: &str, groups: &[ArgGroup], present: &HashSet<String>) -> bool {
+    fn visit<'a>(
+        name: &'a str,
+        groups: &'a [ArgGroup],
+        present: &HashSet<String>,
+        visiting: &mut Vec<&'a str>,
+    ) -> bool {
+        if present.contains(name) {
+            return true;
+        }
+        let Some(group) = groups.iter().find(|g| g.name == name) else {
+            return false;
+        };
+        if visiting.contains(&name) {
+            return false;
+        }
+        visiting.push(name);
+        let found = group
+            .members
+            .iter()
+            .any(|member| visit(member, groups, present, visiting));
+        visiting.pop();
+        found
+    }
+    visit(name, groups, present, &mut Vec::new())
+}
+
+pub(crate) fn check_groups(
+    groups: &[ArgGroup],
+    present: &HashSet<String>,
+    display: impl Fn(&str) -> String,
+) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    for group in groups {
+        let found: Vec<String> = group
+            .members
+            .iter()
+            .filter(|member| is_present(member, groups, present))
+            .map(|member| display(member))
+            .collect();
+        let nested = groups.iter().any(|g| g.members.contains(&group.name));
+        if nested && found.is_empty() {
+            continue;
+        }
+        if !group.rule.allows(found.len()) {
+            errors.push(ParseError::GroupViolation {
+                group: group.name.clone(),
+                rule: group.rule,
+                members: group.members.iter().map(|m| display(m)).collect(),
+                found,
+            });
+        }
+    }
+    errors
+}
+
+pub(crate) fn check_relations(
+    args: &[&Arg],
+    groups: &[ArgGroup],
+    present: &HashSet<String>,
+    holds: impl Fn(&str, &str) -> bool,
+) -> Vec<ParseError> {
+    let mut errors = Vec::new();
+    let mut reported: Vec<(&str, &str)> = Vec::new();
+    for arg in args.iter().filter(|a| present.contains(&a.name)) {
+        for other in &arg.conflicts_with {
+            let seen = reported.contains(&(other.as_str(), arg.name.as_str()));
+            if !seen && other != &arg.name && is_present(other, groups, present) {
+                reported.push((&arg.name, other));
+                errors.push(ParseError::Conflict {
+                    arg: mention(&arg.name, args),
+                    other: mention(other, args),
+                });
+            }
+        }
+        for required in &arg.requires {
+            if !is_present(required, groups, present) {
+                errors.push(ParseError::MissingDependency {
+                    arg: mention(&arg.name, args),
+                    required: mention(required, args),
+                });
+            }
+        }
+    }
+    for arg in args
+        .iter()
+        .filter(|a| !is_present(&a.name, groups, present))
+    {
+        let trigger = arg
+            .required_if
+            .iter()
+            .find(|(other, value)| present.contains(other) && holds(other, value));
+        if let Some((other, value)) = trigger {
+            let is_flag = args.iter().any(|a| &a.name == other && !a.takes_value);
+            errors.push(ParseError::RequiredIf {
+                arg: mention(&arg.name, args),
+                other: mention(other, args),
+                value: (!is_flag || value != "true").then(|| value.clone()),
+            });
+        } else if !arg.required_unless.is_empty()
+            && !arg
+                .required_unless
+                .iter()
+                .any(|other| is_present(other, groups, present))
+        {
+            errors.push(ParseError::RequiredUnless {
+                arg: mention(&arg.name, args),
+                others: arg
+                    .required_unless
+                    .iter()
+                    .map(|other| mention(other, args))
+                    .collect(),
+            });
+        }
+    }
+    errors
+}
+
+pub(crate) fn find_cycle(groups: &[ArgGroup]) -> Option<Vec<String>> {
+    fn visit(name: &str, groups: &[ArgGroup], path: &mut Vec<String>) -> Option<Vec<String>> {
+        if let Some(start) = path.iter().position(|p| p == name) {
+            let mut cycle = path[start..].to_vec();
+            cycle.push(name.to_string());
+            return Some(cycle);
+        }
+        let group = groups.iter().find(|g| g.name == name)?;
+        path.push(name.to_string());
+        for member in &group.members {
+            if let Some(cycle) = visit(member, groups, path) {
+                return Some(cycle);
+            }
+        }
+        path.pop();
+        None
+    }
+    groups
+        .iter()
+        .find_map(|group| visit(&group.name, groups, &mut Vec::new()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn group(name: &str, rule: GroupRule, members: &[&str]) -> ArgGroup {
+        ArgGroup {
+            name: name.to_string(),
+            rule,
+            members: members.iter().map(|m| m.to_string()).collect(),
+        }
+    }
+
+    fn present(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|n| n.to_string()).collect()
+    }
+
+    #[test]
+    fn nested_groups_are_present_through_members() {
+        let groups = [
+            group("output", GroupRule::AtMostOne, &["json", "format"]),
+            group("format", GroupRule::AtMostOne, &["csv", "tsv"]),
+        ];
+        assert!(is_present("output", &groups, &present(&["tsv"])));
+        assert!(!is_present("output", &groups, &present(&["verbose"])));
+    }
+
+    #[test]
+    fn rules_are_enforced() {
+        let groups = [group("mode", GroupRule::ExactlyOne, &["fast", "slow"])];
+        assert!(check_groups(&groups, &present(&["fast"]), |n| n.to_string()).is_empty());
+        let errors = check_groups(&groups, &present(&["fast", "slow"]), |n| format!("--{}", n));
+        assert_eq!(
+            errors,
+            vec![ParseError::GroupViolation {
+                group: "mode".to_string(),
+                rule: GroupRule::ExactlyOne,
+                members: vec!["--fast".to_string(), "--slow".to_string()],
+                found: vec!["--fast".to_string(), "--slow".to_string()],
+            }]
+        );
+        assert_eq!(
+            check_groups(&groups, &present(&[]), |n| n.to_string()).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn empty_nested_groups_defer_to_their_parent() {
+        let groups = [
+            group("outer", GroupRule::AtMostOne, &["inner", "x"]),
+            group("inner", GroupRule::ExactlyOne, &["a", "b"]),
+        ];
+        assert!(check_groups(&groups, &present(&["x"]), |n| n.to_string()).is_empty());
+    }
+
+    #[test]
+    fn cycles_are_reported_in_order() {
+        let groups = [
+            group("a", GroupRule::AtLeastOne, &["b"]),
+            group("b", GroupRule::AtLeastOne, &["x", "a"]),
+        ];
+        assert_eq!(
+            find_cycle(&groups),
+            Some(vec!["a".to_string(), "b".to_string(), "a".to_string()])
+        );
+        assert_eq!(find_cycle(&groups[1..]), None);
+        assert!(!is_present("a", &groups, &present(&[])));
+    }
+}
@@ -0,0 +1,455 @@
+use std::path::Path;
+
+use crate::width::break_units;
+use crate::{
+    display_width, pad_to_width, strip_ansi, Arg, ArgGroup, ArgParser, ParseError, Scope, Stream,
+    TermCaps, REDACTED,
+};
+
+const HEADING: &str = "\x1b[1;4m";
+const BOLD: &str = "\x1b[1m";
+const RESET: &str = "\x1b[0m";
+const ERROR: &str = "\x1b[1;31m";
+const EXAMPLES_IN_ERRORS: usize = 2;
+const MIN_WRAP_WIDTH: usize = 20;
+
+pub(crate) fn program_name(argv0: &str) -> String {
+    let name = Path::new(argv0)
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match name.len().checked_sub(4) {
+        Some(at) if name.is_char_boundary(at) && name[at..].eq_ignore_ascii_case(".exe") => {
+            name[..at].to_string()
+        }
+        _ => name,
+    }
+}
+
+fn wrap(text: &str, width: usize) -> Vec<String> {
+    let mut lines = Vec::new();
+    let mut line = String::new();
+    for word in text.split(' ') {
+        for (i, unit) in break_units(word).into_iter().enumerate() {
+            let space = usize::from(i == 0 && !line.is_empty());
+            if !line.is_empty() && display_width(&line) + space + display_width(unit) > width {
+                lines.push(std::mem::take(&mut line));
+            } else if space == 1 {
+                line.push(' ');
+            }
+            line.push_str(unit);
+        }
+    }
+    lines.push(line);
+    lines
+}
+
+pub(crate) fn usage_name(scope: &Scope) -> String {
+    let mut program = scope.program.clone();
+    if program.is_empty() {
+        program = std::env::args_os()
+            .next()
+            .map(|argv0| program_name(&argv0.to_string_lossy()))
+            .unwrap_or_default();
+    }
+    let mut words = vec![program];
+    words.extend(scope.command_path.iter().cloned());
+    words.join(" ")
+}
+
+pub(crate) fn mention(name: &str, known: &[&Arg]) -> String {
+    match known.iter().find(|a| a.name == name) {
+        Some(arg) if arg.positional => format!("<{}>", arg.display()),
+        Some(arg) => match (&arg.long, arg.short) {
+            (Some(long), _) => format!("--{}", long),
+            (None, Some(short)) => format!("-{}", short),
+            (None, None) => arg.display().to_string(),
+        },
+        None => name.to_string(),
+    }
+}
+
+pub(crate) fn relation_tags(arg: &Arg, known: &[&Arg], groups: &[ArgGroup]) -> Vec<String> {
+    let list = |names: &[String]| {
+        names
+            .iter()
+            .map(|name| mention(name, known))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let mut tags = Vec::new();
+    for group in groups.iter().filter(|g| g.members.contains(&arg.name)) {
+        tags.push(format!("[{} of: {}]", group.rule, list(&group.members)));
+    }
+    if !arg.conflicts_with.is_empty() {
+        tags.push(format!("[conflicts with: {}]", list(&arg.conflicts_with)));
+    }
+    if !arg.requires.is_empty() {
+        tags.push(format!("[requires: {}]", list(&arg.requires)));
+    }
+    for (other, value) in &arg.required_if {
+        let flag = known.iter().any(|a| &a.name == other && !a.takes_value);
+        if flag && value == "true" {
+            tags.push(format!("[required if: {}]", mention(other, known)));
+        } else {
+            tags.push(format!(
+                "[required if: {}={}]",
+                mention(other, known),
+                value
+            ));
+        }
+    }
+    if !arg.required_unless.is_empty() {
+        tags.push(format!("[required unless: {}]", list(&arg.required_unless)));
+    }
+    tags
+}
+
+pub(crate) fn tags(
+    arg: &Arg,
+    version: Option<&str>,
+    env: Option<String>,
+    relations: Vec<String>,
+) -> String {
+    let mut tags: Vec<String> = arg.help.iter().cloned().collect();
+    if let Some(default) = arg.effective_default() {
+        let default = if arg.sensitive { REDACTED } else { default };
+        tags.push(format!("[default: {}]", default));
+    }
+    if let Some(var) = env {
+        tags.push(format!("[env: {}]", var));
+    }
+    if !arg.choices.is_empty() {
+        tags.push(format!("[possible values: {}]", arg.choices.join(", ")));
+    }
+    tags.extend(arg.level_tag());
+    if let Some(range) = arg.range {
+        tags.push(format!("[{}]", range));
+    }
+    let aliases: Vec<String> = arg
+        .aliases
+        .iter()
+        .filter(|a| a.visible)
+        .map(|a| format!("--{}", a.name))
+        .collect();
+    if !aliases.is_empty() {
+        tags.push(format!("[aliases: {}]", aliases.join(", ")));
+    }
+    if arg.is_required(version) {
+        tags.push("[required]".to_string());
+    }
+    tags.extend(relations);
+    if let Some(var) = &arg.experimental {
+        tags.push(format!("[experimental: {}=1]", var));
+    }
+    tags.join(" ")
+}
+
+pub(crate) fn option_left(arg: &Arg) -> String {
+    let negation = if arg.negatable { "[no-]" } else { "" };
+    let mut left = match (arg.short, &arg.long) {
+        (Some(short), Some(long)) => format!("-{}, --{}{}", short, negation, long),
+        (Some(short), None) => format!("-{}", short),
+        (None, Some(long)) => format!("    --{}{}", negation, long),
+        (None, None) => arg.display().to_string(),
+    };
+    if arg.key_value {
+        left.push_str(" <key[=value]>");
+    } else if arg.takes_value {
+        for _ in 0..arg.num_values {
+            left.push_str(&format!(" <{}>", arg.display()));
+        }
+        if let Some(delimiter) = arg.value_delimiter {
+            left.push_str(&format!("[{}...]", delimiter));
+        }
+    }
+    if arg.multiple {
+        left.push_str("...");
+    }
+    left
+}
+
+impl ArgParser {
+    pub fn usage_example(mut self, example: &str) -> Self {
+        self.usage_examples.push(example.to_string());
+        self
+    }
+
+    pub fn with_name(mut self, name: &str) -> Self {
+        self.name = Some(name.to_string());
+        self
+    }
+
+    pub fn with_version(self, version: &str) -> Self {
+        self.version(version)
+    }
+
+    pub fn with_about(mut self, about: &str) -> Self {
+        self.about = Some(about.to_string());
+        self
+    }
+
+    pub fn with_author(mut self, author: &str) -> Self {
+        self.author = Some(author.to_string());
+        self
+    }
+
+    pub(crate) fn program(&self, args: &[String]) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            None => args.first().map(|a| program_name(a)).unwrap_or_default(),
+        }
+    }
+
+    pub fn render_error(&self, args: &[String], err: &ParseError) -> String {
+        if self.quiet_requested(args) {
+            return format!("{}error{}: {}\n", ERROR, RESET, err.brief());
+        }
+        let mut out = format!("{}error{}: {}\n", ERROR, RESET, err);
+        let mut scope = Scope {
+            program: self.program(args),
+            ..Scope::default()
+        };
+        let mut parser = self;
+        for token in args.iter().skip(1).filter(|t| !t.starts_with('-')) {
+            if let Some((name, sub)) = parser.find_subcommand(token) {
+                scope.command_path.push(name.to_string());
+                parser = sub;
+            }
+        }
+        if !parser.usage_examples.is_empty() {
+            out.push_str(&format!("\n{}Examples:{}\n", HEADING, RESET));
+            let usage = usage_name(&scope);
+            for example in parser.usage_examples.iter().take(EXAMPLES_IN_ERRORS) {
+                out.push_str(&format!("  {} {}\n", usage, example));
+            }
+        }
+        out
+    }
+
+    pub fn render_help(&self) -> String {
+        let args: Vec<&Arg> = self.args.iter().collect();
+        let scope = Scope {
+            program: self.program(&[]),
+            ..Scope::default()
+        };
+        let help = self.help_text(&scope, &args, self.version.as_deref());
+        strip_ansi(&help).into_owned()
+    }
+
+    pub(crate) fn wants_help(&self, token: &str, known: &[&Arg]) -> bool {
+        match token {
+            "--help" => !known.iter().any(|a| a.matches_long("help")),
+            "-h" => !known.iter().any(|a| a.short == Some('h')),
+            _ => false,
+        }
+    }
+
+    pub(crate) fn wants_version(&self, token: &str, known: &[&Arg], version: Option<&str>) -> bool {
+        version.is_some()
+            && match token {
+                "--version" => !known.iter().any(|a| a.matches_long("version")),
+                "-V" => !known.iter().any(|a| a.short == Some('V')),
+                _ => false,
+            }
+    }
+
+    pub(crate) fn help_text(&self, scope: &Scope, known: &[&Arg], version: Option<&str>) -> String {
+        let namespace = self.namespace.as_deref().or(scope.namespace.as_deref());
+        let visible: Vec<&Arg> = known
+            .iter()
+            .copied()
+            .filter(|a| !a.is_hidden() && !a.is_removed(version))
+            .collect();
+        let mut usage = format!("{}Usage:{} {} [OPTIONS]", HEADING, RESET, usage_name(scope));
+        let mut arguments = Vec::new();
+        let mut options = Vec::new();
+        let program = usage_name(scope);
+        let with_examples = |arg: &Arg, text: String| {
+            let mut lines: Vec<String> = Some(text).filter(|t| !t.is_empty()).into_iter().collect();
+            for example in &arg.examples {
+                lines.push(format!("Example: {} {}", program, example));
+            }
+            lines.join("\n")
+        };
+        for arg in &visible {
+            let env = arg.env_var(namespace);
+            let relations = relation_tags(arg, known, &self.groups);
+            if arg.positional {
+                let repeat = if arg.multiple { "..." } else { "" };
+                if arg.is_required(version) {
+                    usage.push_str(&format!(" <{}>{}", arg.display(), repeat));
+                } else {
+                    usage.push_str(&format!(" [{}]{}", arg.display(), repeat));
+                }
+                arguments.push((
+                    format!("<{}>{}", arg.display(), repeat),
+                    with_examples(arg, tags(arg, version, env, relations)),
+                ));
+            } else {
+                options.push((
+                    option_left(arg),
+                    with_examples(arg, tags(arg, version, env, relations)),
+                ));
+            }
+        }
+        let help_short = self.wants_help("-h", known);
+        let help_long = self.wants_help("--help", known);
+        if help_short || help_long {
+            let left = match (help_short, help_long) {
+                (true, true) => "-h, --help",
+                (true, false) => "-h",
+                _ => "    --help",
+            };
+            options.push((left.to_string(), "Print help".to_string()));
+        }
+        let version_short = self.wants_version("-V", known, version);
+        let version_long = self.wants_version("--version", known, version);
+        if version_short || version_long {
+            let left = match (version_short, version_long) {
+                (true, true) => "-V, --version",
+                (true, false) => "-V",
+                _ => "    --version",
+            };
+            options.push((left.to_string(), "Print version".to_string()));
+        }
+        let mut names: Vec<&String> = self.subcommands.keys().collect();
+        names.sort();
+        let commands: Vec<(String, String)> = names
+            .into_iter()
+            .map(|name| {
+                let mut tags: Vec<String> = self.subcommands[name].about.iter().cloned().collect();
+                let aliases: Vec<&str> = self.subcommands[name]
+                    .aliases
+                    .iter()
+                    .filter(|a| a.visible)
+                    .map(|a| a.name.as_str())
+                    .collect();
+                if !aliases.is_empty() {
+                    tags.push(format!("[aliases: {}]", aliases.join(", ")));
+                }
+                if self.default_subcommand.as_ref() == Some(name) {
+                    tags.push("[default]".to_string());
+                }
+                (name.clone(), tags.join(" "))
+            })
+            .collect();
+        if !commands.is_empty() {
+            usage.push_str(" [COMMAND]");
+        }
+        if self.dash_sections {
+            usage.push_str(" [-- <args>...]...");
+        }
+        let shorthands: Vec<(String, String)> = self
+            .abbreviations
+            .iter()
+            .map(|a| (a.token.clone(), a.expansion.join(" ")))
+            .collect();
+        let width = arguments
+            .iter()
+            .chain(&options)
+            .chain(&commands)
+            .chain(&shorthands)
+            .map(|(left, _)| display_width(left))
+            .max()
+            .unwrap_or(0);
+        let wrap_width = TermCaps::get(Stream::Stdout)
+            .width
+            .map(|columns| columns.saturating_sub(width + 4))
+            .filter(|&columns| columns >= MIN_WRAP_WIDTH);
+        let mut out = String::new();
+        if scope.command_path.is_empty() && (self.name.is_some() || version.is_some()) {
+            let title = format!("{} {}", usage_name(scope), version.unwrap_or_default());
+            out.push_str(&format!("{}{}{}\n", BOLD, title.trim(), RESET));
+        }
+        if let Some(author) = &self.author {
+            out.push_str(&format!("{}\n", author));
+        }
+        if let Some(about) = &self.about {
+            out.push_str(&format!("{}\n", about));
+        }
+        if !out.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(&usage);
+        out.push('\n');
+        for (heading, rows) in [
+            ("Arguments", &arguments),
+            ("Options", &options),
+            ("Commands", &commands),
+            ("Shorthands", &shorthands),
+        ] {
+            if rows.is_empty() {
+                continue;
+            }
+            out.push_str(&format!("\n{}{}:{}\n", HEADING, heading, RESET));
+            for (left, right) in rows {
+                let line = if right.is_empty() {
+                    format!("  {}{}{}", BOLD, left, RESET)
+                } else {
+                    let right: Vec<String> = match wrap_width {
+                        Some(columns) => right.lines().flat_map(|l| wrap(l, columns)).collect(),
+                        None => right.lines().map(str::to_string).collect(),
+                    };
+                    let right = right.join(&format!("\n{}", " ".repeat(width + 4)));
+                    format!(
+                        "  {}{}{}  {}",
+                        BOLD,
+                        pad_to_width(left, width),
+                        RESET,
+                        right
+                    )
+                };
+                out.push_str(&line);
+                out.push('\n');
+            }
+        }
+        if !self.usage_examples.is_empty() {
+            out.push_str(&format!("\n{}Examples:{}\n", HEADING, RESET));
+            for example in &self.usage_examples {
+                out.push_str(&format!("  {} {}\n", program, example));
+            }
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrap_breaks_on_spaces() {
+        assert_eq!(
+            wrap("print more details while running", 12),
+            vec!["print more", "details", "while", "running"]
+        );
+    }
+
+    #[test]
+    fn wrap_breaks_cjk_runs_between_characters() {
+        let lines = wrap("設定ファイルのパスを指定します", 10);
+        assert_eq!(lines, vec!["設定ファイ", "ルのパスを", "指定します"]);
+        assert!(lines.iter().all(|line| display_width(line) <= 10));
+    }
+
+    #[test]
+    fn wrap_mixes_latin_words_and_cjk() {
+        let lines = wrap("use the 設定ファイル option", 12);
+        assert_eq!(lines, vec!["use the 設定", "ファイル", "option"]);
+    }
+
+    #[test]
+    fn program_name_strips_directories_and_extension() {
+        assert_eq!(program_name("/usr/bin/tool"), "tool");
+        assert_eq!(program_name("C:/bin/tool.exe"), "tool");
+        assert_eq!(program_name("TOOL.EXE"), "TOOL");
+    }
+
+    #[test]
+    fn program_name_keeps_dotted_names() {
+        assert_eq!(program_name("/usr/bin/python3.11"), "python3.11");
+        assert_eq!(program_name("git-lfs.sh"), "git-lfs.sh");
+        assert_eq!(program_name("tool.exe.bak"), "tool.exe.bak");
+    }
+}
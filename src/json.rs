@@ -0,0 +1,594 @@
+use serde_json::Value;
+
+use crate::spec::CommandSpec;
+use crate::{ArgParser, SpecError};
+
+pub const JSON_SPEC_SCHEMA: &str = r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "https://github.com/linuxfanboy4/rust-arguments/spec.schema.json",
+  "title": "rust-arguments command spec",
+  "$ref": "#/$defs/command",
+  "$defs": {
+    "command": {
+      "type": "object",
+      "required": ["name"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string" },
+        "aliases": { "type": "array", "items": { "$ref": "#/$defs/alias" } },
+        "args": { "type": "array", "items": { "$ref": "#/$defs/arg" } },
+        "groups": { "type": "array", "items": { "$ref": "#/$defs/group" } },
+        "subcommands": { "type": "array", "items": { "$ref": "#/$defs/command" } },
+        "disallowed_globals": { "type": "array", "items": { "type": "string" } },
+        "shortcuts": { "type": "array", "items": { "$ref": "#/$defs/shortcut" } },
+        "abbreviations": { "type": "array", "items": { "$ref": "#/$defs/abbreviation" } },
+        "profiles": { "type": "array", "items": { "$ref": "#/$defs/profile" } },
+        "collect_errors": { "type": "boolean" },
+        "hide_internal_aliases": { "type": "boolean" },
+        "version": { "type": ["string", "null"] },
+        "accept_removed_args": { "type": "boolean" },
+        "exec_style": { "type": "boolean" },
+        "dash_sections": { "type": "boolean" },
+        "default_subcommand": { "type": ["string", "null"] },
+        "ambiguity_policy": { "enum": ["PreferSubcommand", "PreferPositional", "Error", null] },
+        "usage_examples": { "type": "array", "items": { "type": "string" } },
+        "namespace": { "type": ["string", "null"] },
+        "about": { "type": ["string", "null"] },
+        "author": { "type": ["string", "null"] },
+        "config_files": { "type": "array", "items": { "type": "string" } },
+        "env_files": { "type": "array", "items": { "type": "string" } },
+        "shell_init": {
+          "anyOf": [
+            { "type": "null" },
+            {
+              "type": "array",
+              "prefixItems": [{ "type": "string" }, { "type": "string" }],
+              "minItems": 2,
+              "maxItems": 2
+            }
+          ]
+        },
+        "quiet_arg": { "type": ["string", "null"] }
+      }
+    },
+    "arg": {
+      "type": "object",
+      "required": ["name"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string", "minLength": 1 },
+        "short": { "type": ["string", "null"], "minLength": 1, "maxLength": 1 },
+        "long": { "type": ["string", "null"], "minLength": 1 },
+        "takes_value": { "type": "boolean" },
+        "required": { "type": "boolean" },
+        "default": { "type": ["string", "null"] },
+        "conditional_defaults": {
+          "type": "array",
+          "items": {
+            "type": "array",
+            "prefixItems": [{ "$ref": "#/$defs/condition" }, { "type": "string" }],
+            "minItems": 2,
+            "maxItems": 2
+          }
+        },
+        "is_path": { "type": "boolean" },
+        "sensitive": { "type": "boolean" },
+        "global": { "type": "boolean" },
+        "choices": { "type": "array", "items": { "type": "string" } },
+        "on_duplicate": { "enum": ["LastWins", "FirstWins", "Error"] },
+        "multiple": { "type": "boolean" },
+        "hidden": { "anyOf": [{ "type": "null" }, { "$ref": "#/$defs/condition" }] },
+        "examples": { "type": "array", "items": { "type": "string" } },
+        "aliases": { "type": "array", "items": { "$ref": "#/$defs/alias" } },
+        "env": { "type": ["string", "null"] },
+        "positional": { "type": "boolean" },
+        "introduced_in": { "type": ["string", "null"] },
+        "removed_in": { "type": ["string", "null"] },
+        "replaced_by": { "type": ["string", "null"] },
+        "display_name": { "type": ["string", "null"] },
+        "soft_required": { "type": ["string", "null"] },
+        "experimental": { "type": ["string", "null"] },
+        "key_value": { "type": "boolean" },
+        "value_delimiter": { "type": ["string", "null"], "minLength": 1, "maxLength": 1 },
+        "forbid_empty_values": { "type": "boolean" },
+        "levels": { "type": "array", "items": { "type": "string" } },
+        "conflicts_with": { "type": "array", "items": { "type": "string" } },
+        "requires": { "type": "array", "items": { "type": "string" } },
+        "capture": { "enum": ["Greedy", "Lazy"] },
+        "ignore_case": { "type": "boolean" },
+        "trailing_var_arg": { "type": "boolean" },
+        "required_when": { "type": "array", "items": { "$ref": "#/$defs/condition" } },
+        "glob_expand": { "type": "boolean" },
+        "negatable": { "type": "boolean" },
+        "num_values": { "type": "integer", "minimum": 1 },
+        "help": { "type": ["string", "null"] },
+        "prefix_choices": { "type": "boolean" },
+        "required_if": { "type": "array", "items": { "type": "array", "prefixItems": [{ "type": "string" }, { "type": "string" }], "minItems": 2, "maxItems": 2 } },
+        "required_unless": { "type": "array", "items": { "type": "string" } },
+        "range": {
+          "anyOf": [
+            { "type": "null" },
+            {
+              "type": "object",
+              "properties": {
+                "min": { "type": ["integer", "null"] },
+                "max": { "type": ["integer", "null"] }
+              },
+              "additionalProperties": false
+            }
+          ]
+        }
+      }
+    },
+    "alias": {
+      "type": "object",
+      "required": ["name"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string", "minLength": 1 },
+        "visible": { "type": "boolean" }
+      }
+    },
+    "condition": {
+      "anyOf": [
+        { "enum": ["Always", "Ci", "Tty"] },
+        {
+          "type": "object",
+          "required": ["Os"],
+          "additionalProperties": false,
+          "properties": { "Os": { "type": "string" } }
+        },
+        {
+          "type": "object",
+          "required": ["Not"],
+          "additionalProperties": false,
+          "properties": { "Not": { "$ref": "#/$defs/condition" } }
+        }
+      ]
+    },
+    "group": {
+      "type": "object",
+      "required": ["name", "rule", "members"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string" },
+        "rule": { "enum": ["ExactlyOne", "AtMostOne", "AtLeastOne"] },
+        "members": { "type": "array", "items": { "type": "string" } }
+      }
+    },
+    "profile": {
+      "type": "object",
+      "required": ["name", "values"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string" },
+        "values": {
+          "type": "array",
+          "items": {
+            "type": "array",
+            "prefixItems": [{ "type": "string" }, { "type": "string" }],
+            "minItems": 2,
+            "maxItems": 2
+          }
+        }
+      }
+    },
+    "shortcut": {
+      "type": "object",
+      "required": ["name", "args"],
+      "additionalProperties": false,
+      "properties": {
+        "name": { "type": "string" },
+        "args": { "type": "array", "items": { "type": "string" } }
+      }
+    },
+    "abbreviation": {
+      "type": "object",
+      "required": ["token", "expansion"],
+      "additionalProperties": false,
+      "properties": {
+        "token": { "type": "string" },
+        "expansion": { "type": "array", "items": { "type": "string" } }
+      }
+    }
+  }
+}"##;
+
+fn invalid(path: &str, message: &str) -> SpecError {
+    SpecError::InvalidDocument {
+        path: if path.is_empty() {
+            "/".to_string()
+        } else {
+            path.to_string()
+        },
+        message: message.to_string(),
+    }
+}
+
+fn object<'a>(
+    value: &'a Value,
+    path: &str,
+    required: &[&str],
+    allowed: &[&str],
+) -> Result<&'a serde_json::Map<String, Value>, SpecError> {
+    let map = value
+        .as_object()
+        .ok_or_else(|| invalid(path, "expected an object"))?;
+    for key in map.keys() {
+        if !allowed.contains(&key.as_str()) {
+            return Err(invalid(&format!("{}/{}", path, key), "unknown property"));
+        }
+    }
+    for key in required {
+        if !map.contains_key(*key) {
+            return Err(invalid(
+                path,
+                &format!("missing required property '{}'", key),
+            ));
+        }
+    }
+    Ok(map)
+}
+
+fn field<'a>(
+    map: &'a serde_json::Map<String, Value>,
+    path: &str,
+    key: &str,
+) -> Option<(&'a Value, String)> {
+    map.get(key).map(|v| (v, format!("{}/{}", path, key)))
+}
+
+fn string(value: &Value, path: &str, nullable: bool) -> Result<(), SpecError> {
+    match value {
+        Value::String(_) => Ok(()),
+        Value::Null if nullable => Ok(()),
+        _ => Err(invalid(path, "expected a string")),
+    }
+}
+
+fn boolean(value: &Value, path: &str) -> Result<(), SpecError> {
+    value
+        .as_bool()
+        .map(|_| ())
+        .ok_or_else(|| invalid(path, "expected a boolean"))
+}
+
+fn array<F>(value: &Value, path: &str, mut item: F) -> Result<(), SpecError>
+where
+    F: FnMut(&Value, &str) -> Result<(), SpecError>,
+{
+    let items = value
+        .as_array()
+        .ok_or_else(|| invalid(path, "expected an array"))?;
+    for (i, value) in items.iter().enumerate() {
+        item(value, &format!("{}/{}", path, i))?;
+    }
+    Ok(())
+}
+
+fn one_of(value: &Value, path: &str, options: &[&str]) -> Result<(), SpecError> {
+    match value.as_str() {
+        Some(s) if options.contains(&s) => Ok(()),
+        _ => Err(invalid(
+            path,
+            &format!("expected one of {}", options.join(", ")),
+        )),
+    }
+}
+
+fn check_condition(value: &Value, path: &str) -> Result<(), SpecError> {
+    if value.is_string() {
+        return one_of(value, path, &["Always", "Ci", "Tty"]);
+    }
+    let map = value
+        .as_object()
+        .ok_or_else(|| invalid(path, "expected a condition"))?;
+    match map.iter().next() {
+        Some((key, inner)) if map.len() == 1 && key == "Os" => {
+            string(inner, &format!("{}/Os", path), false)
+        }
+        Some((key, inner)) if map.len() == 1 && key == "Not" => {
+            check_condition(inner, &format!("{}/Not", path))
+        }
+        _ => Err(invalid(path, "expected a condition")),
+    }
+}
+
+fn check_arg(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(
+        value,
+        path,
+        &["name"],
+        &[
+            "name",
+            "short",
+            "long",
+            "takes_value",
+            "required",
+            "default",
+            "conditional_defaults",
+            "is_path",
+            "sensitive",
+            "global",
+            "choices",
+            "on_duplicate",
+            "multiple",
+            "hidden",
+            "examples",
+            "aliases",
+            "env",
+            "positional",
+            "introduced_in",
+            "removed_in",
+            "replaced_by",
+            "display_name",
+            "range",
+            "soft_required",
+            "experimental",
+            "key_value",
+            "value_delimiter",
+            "forbid_empty_values",
+            "levels",
+            "conflicts_with",
+            "requires",
+            "capture",
+            "ignore_case",
+            "trailing_var_arg",
+            "required_when",
+            "glob_expand",
+            "negatable",
+            "num_values",
+            "help",
+            "prefix_choices",
+            "required_if",
+            "required_unless",
+        ],
+    )?;
+    for (key, value) in map {
+        let path = format!("{}/{}", path, key);
+        match key.as_str() {
+            "name" => match value.as_str() {
+                Some(name) if !name.is_empty() => {}
+                _ => return Err(invalid(&path, "expected a non-empty string")),
+            },
+            "short" | "value_delimiter" => {
+                string(value, &path, true)?;
+                if let Some(short) = value.as_str() {
+                    if short.chars().count() != 1 {
+                        return Err(invalid(&path, "expected a single character"));
+                    }
+                }
+            }
+            "long" => {
+                string(value, &path, true)?;
+                if value.as_str() == Some("") {
+                    return Err(invalid(&path, "expected a non-empty string"));
+                }
+            }
+            "default" | "env" | "introduced_in" | "removed_in" | "replaced_by" | "display_name"
+            | "soft_required" | "experimental" | "help" => string(value, &path, true)?,
+            "takes_value"
+            | "required"
+            | "is_path"
+            | "sensitive"
+            | "global"
+            | "positional"
+            | "key_value"
+            | "multiple"
+            | "forbid_empty_values"
+            | "ignore_case"
+            | "trailing_var_arg"
+            | "glob_expand"
+            | "negatable"
+            | "prefix_choices" => boolean(value, &path)?,
+            "choices" | "examples" | "levels" | "conflicts_with" | "requires"
+            | "required_unless" => array(value, &path, |v, p| string(v, p, false))?,
+            "on_duplicate" => one_of(value, &path, &["LastWins", "FirstWins", "Error"])?,
+            "capture" => one_of(value, &path, &["Greedy", "Lazy"])?,
+            "aliases" => array(value, &path, check_alias)?,
+            "hidden" if value.is_null() => {}
+            "hidden" => check_condition(value, &path)?,
+            "required_when" => array(value, &path, check_condition)?,
+            "range" if value.is_null() => {}
+            "range" => {
+                let map = object(value, &path, &[], &["min", "max"])?;
+                for (bound, value) in map {
+                    if !value.is_null() && value.as_i64().is_none() {
+                        return Err(invalid(
+                            &format!("{}/{}", path, bound),
+                            "expected an integer",
+                        ));
+                    }
+                }
+            }
+            "conditional_defaults" => array(value, &path, |v, p| match v.as_array() {
+                Some(pair) if pair.len() == 2 => {
+                    check_condition(&pair[0], &format!("{}/0", p))?;
+                    string(&pair[1], &format!("{}/1", p), false)
+                }
+                _ => Err(invalid(p, "expected a [condition, value] pair")),
+            })?,
+            "required_if" => array(value, &path, |v, p| match v.as_array() {
+                Some(pair) if pair.len() == 2 => {
+                    string(&pair[0], &format!("{}/0", p), false)?;
+                    string(&pair[1], &format!("{}/1", p), false)
+                }
+                _ => Err(invalid(p, "expected an [argument, value] pair")),
+            })?,
+            "num_values" => {
+                if value.as_u64().filter(|&n| n >= 1).is_none() {
+                    return Err(invalid(&path, "expected a positive integer"));
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+fn check_alias(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(value, path, &["name"], &["name", "visible"])?;
+    if let Some((name, path)) = field(map, path, "name") {
+        string(name, &path, false)?;
+    }
+    if let Some((visible, path)) = field(map, path, "visible") {
+        boolean(visible, &path)?;
+    }
+    Ok(())
+}
+
+fn check_group(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(
+        value,
+        path,
+        &["name", "rule", "members"],
+        &["name", "rule", "members"],
+    )?;
+    if let Some((name, path)) = field(map, path, "name") {
+        string(name, &path, false)?;
+    }
+    if let Some((rule, path)) = field(map, path, "rule") {
+        one_of(rule, &path, &["ExactlyOne", "AtMostOne", "AtLeastOne"])?;
+    }
+    if let Some((members, path)) = field(map, path, "members") {
+        array(members, &path, |v, p| string(v, p, false))?;
+    }
+    Ok(())
+}
+
+fn check_shortcut(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(value, path, &["name", "args"], &["name", "args"])?;
+    if let Some((name, path)) = field(map, path, "name") {
+        string(name, &path, false)?;
+    }
+    if let Some((args, path)) = field(map, path, "args") {
+        array(args, &path, |v, p| string(v, p, false))?;
+    }
+    Ok(())
+}
+
+fn check_abbreviation(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(
+        value,
+        path,
+        &["token", "expansion"],
+        &["token", "expansion"],
+    )?;
+    if let Some((token, path)) = field(map, path, "token") {
+        string(token, &path, false)?;
+    }
+    if let Some((expansion, path)) = field(map, path, "expansion") {
+        array(expansion, &path, |v, p| string(v, p, false))?;
+    }
+    Ok(())
+}
+
+fn check_profile(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(value, path, &["name", "values"], &["name", "values"])?;
+    if let Some((name, path)) = field(map, path, "name") {
+        string(name, &path, false)?;
+    }
+    if let Some((values, path)) = field(map, path, "values") {
+        array(values, &path, |v, p| match v.as_array() {
+            Some(pair) if pair.len() == 2 => {
+                string(&pair[0], &format!("{}/0", p), false)?;
+                string(&pair[1], &format!("{}/1", p), false)
+            }
+            _ => Err(invalid(p, "expected an [argument, value] pair")),
+        })?;
+    }
+    Ok(())
+}
+
+fn check_command(value: &Value, path: &str) -> Result<(), SpecError> {
+    let map = object(
+        value,
+        path,
+        &["name"],
+        &[
+            "name",
+            "aliases",
+            "args",
+            "groups",
+            "subcommands",
+            "disallowed_globals",
+            "shortcuts",
+            "abbreviations",
+            "profiles",
+            "collect_errors",
+            "hide_internal_aliases",
+            "version",
+            "accept_removed_args",
+            "exec_style",
+            "dash_sections",
+            "default_subcommand",
+            "ambiguity_policy",
+            "usage_examples",
+            "namespace",
+            "about",
+            "author",
+            "config_files",
+            "env_files",
+            "shell_init",
+            "quiet_arg",
+        ],
+    )?;
+    for (key, value) in map {
+        let path = format!("{}/{}", path, key);
+        match key.as_str() {
+            "name" => string(value, &path, false)?,
+            "aliases" => array(value, &path, check_alias)?,
+            "args" => array(value, &path, check_arg)?,
+            "groups" => array(value, &path, check_group)?,
+            "subcommands" => array(value, &path, check_command)?,
+            "disallowed_globals" | "usage_examples" | "config_files" | "env_files" => {
+                array(value, &path, |v, p| string(v, p, false))?
+            }
+            "shortcuts" => array(value, &path, check_shortcut)?,
+            "abbreviations" => array(value, &path, check_abbreviation)?,
+            "profiles" => array(value, &path, check_profile)?,
+            "version" | "default_subcommand" | "namespace" | "about" | "author" | "quiet_arg" => {
+                string(value, &path, true)?
+            }
+            "shell_init" if value.is_null() => {}
+            "shell_init" => match value.as_array() {
+                Some(pair) if pair.len() == 2 => {
+                    string(&pair[0], &format!("{}/0", path), false)?;
+                    string(&pair[1], &format!("{}/1", path), false)?
+                }
+                _ => return Err(invalid(&path, "expected a [function, template] pair")),
+            },
+            "ambiguity_policy" if value.is_null() => {}
+            "ambiguity_policy" => one_of(
+                value,
+                &path,
+                &["PreferSubcommand", "PreferPositional", "Error"],
+            )?,
+            "collect_errors"
+            | "hide_internal_aliases"
+            | "accept_removed_args"
+            | "exec_style"
+            | "dash_sections" => boolean(value, &path)?,
+            _ => unreachable!(),
+        }
+    }
+    Ok(())
+}
+
+pub fn validate_json_spec(json: &str) -> Result<CommandSpec, SpecError> {
+    let value: Value = serde_json::from_str(json).map_err(|err| invalid("", &err.to_string()))?;
+    check_command(&value, "")?;
+    serde_json::from_value(value).map_err(|err| invalid("", &err.to_string()))
+}
+
+impl ArgParser {
+    pub fn to_json_spec(&self, name: &str) -> String {
+        serde_json::to_string_pretty(&self.to_spec(name)).expect("command specs always serialize")
+    }
+
+    pub fn from_json_spec(json: &str) -> Result<ArgParser, SpecError> {
+        let parser = ArgParser::from_spec(validate_json_spec(json)?);
+        parser.check_spec()?;
+        Ok(parser)
+    }
+}
@@ -0,0 +1,63 @@
+use std::sync::Arc;
+
+use crate::{Arg, ArgMatches, ArgParser, ParseError, REDACTED};
+
+pub type KeyValidator = Arc<dyn Fn(Option<&str>) -> bool + Send + Sync>;
+
+impl Arg {
+    pub(crate) fn accept_key_value(
+        &self,
+        flag: &str,
+        raw: &str,
+    ) -> Result<(String, Option<String>), ParseError> {
+        let (key, value) = match raw.split_once('=') {
+            Some((key, value)) => (key, Some(value)),
+            None => (raw, None),
+        };
+        let valid = !key.is_empty()
+            && self
+                .key_validators
+                .iter()
+                .filter(|(k, _)| k == key)
+                .all(|(_, validator)| validator(value));
+        if !valid {
+            return Err(ParseError::InvalidValue {
+                arg: flag.to_string(),
+                value: if self.sensitive {
+                    REDACTED.to_string()
+                } else {
+                    raw.to_string()
+                },
+                message: key.is_empty().then(|| "expected a key".to_string()),
+            });
+        }
+        Ok((key.to_string(), value.map(str::to_string)))
+    }
+}
+
+impl ArgParser {
+    pub fn key_value(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.key_value = true;
+            arg.takes_value = true;
+        }
+        self
+    }
+
+    pub fn key_validator<F>(mut self, name: &str, key: &str, validator: F) -> Self
+    where
+        F: 'static + Fn(Option<&str>) -> bool + Send + Sync,
+    {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.key_validators
+                .push((key.to_string(), Arc::new(validator)));
+        }
+        self
+    }
+}
+
+impl ArgMatches {
+    pub fn key_values(&self, name: &str) -> &[(String, Option<String>)] {
+        self.key_values.get(name).map_or(&[], Vec::as_slice)
+    }
+}
@@ -0,0 +1,50 @@
+use crate::{Arg, ArgMatches, ArgParser};
+
+impl Arg {
+    pub(crate) fn level_for(&self, count: usize) -> Option<&str> {
+        let last = self.levels.len().checked_sub(1)?;
+        Some(&self.levels[count.min(last)])
+    }
+
+    pub(crate) fn level_tag(&self) -> Option<String> {
+        let last = self.levels.len().checked_sub(1)?;
+        let entries: Vec<String> = self
+            .levels
+            .iter()
+            .enumerate()
+            .map(|(count, level)| {
+                let plus = if count == last && count > 0 { "+" } else { "" };
+                format!("{}{}={}", count, plus, level)
+            })
+            .collect();
+        Some(format!("[levels: {}]", entries.join(", ")))
+    }
+}
+
+impl ArgParser {
+    pub fn counted(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.takes_value = false;
+            arg.multiple = true;
+        }
+        self
+    }
+
+    pub fn count_levels(mut self, name: &str, levels: &[&str]) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.multiple = true;
+            arg.levels = levels.iter().map(|level| level.to_string()).collect();
+        }
+        self
+    }
+}
+
+impl ArgMatches {
+    pub fn count(&self, name: &str) -> usize {
+        self.occurrences_of(name)
+    }
+
+    pub fn level(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+}
@@ -1,5 +1,124 @@
-use std::collections::HashMap;                             use std::sync::Arc;
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::iter::Peekable;
+#[cfg(feature = "dotenv")]
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::sync::Arc;
+
+mod abbreviation;
+mod alias;
+#[cfg(feature = "json")]
+mod bug_report;
+mod capture;
+mod completion;
+mod config_file;
+mod context;
+mod convert;
+mod derive;
+mod dump;
+#[cfg(feature = "dotenv")]
+mod env_file;
+mod error;
+mod explain;
+mod family;
+#[cfg(feature = "tui")]
+mod form;
+mod frozen;
+#[cfg(feature = "glob")]
+mod glob;
+mod group;
+mod help;
+#[cfg(feature = "json")]
+mod json;
+mod key_value;
+mod level;
+mod lint;
+mod man;
+mod matches;
+mod module;
+mod os_args;
+mod path;
+mod preprocess;
+mod probe;
+mod profile;
+mod prompt;
+mod quiet;
+mod quote;
+mod range;
+mod script;
+mod shell;
+mod shell_init;
+mod shortcut;
+mod small_map;
+mod source;
+pub mod spec;
+mod store;
+mod suggest;
+mod template;
+mod term;
+mod timings;
+mod typed;
+mod unparse;
+mod validation;
+mod versioning;
+mod warning;
+mod width;
+
+pub use abbreviation::Abbreviation;
+pub use alias::Alias;
+#[cfg(feature = "json")]
+pub use bug_report::BUG_REPORT;
+pub use capture::Capture;
+pub use completion::{
+    CandidateSource, CompletionCandidate, LIST_OPTIONS, LIST_SUBCOMMANDS,
+    PRINT_COMPLETION_CANDIDATES,
+};
+pub use context::{Context, ContextValidator};
+pub use convert::{FieldError, FromArgMatches, FromArgValue};
+pub use derive::Arguments;
+pub use dump::{ConfigFormat, DUMP_CONFIG};
+pub use error::{ParseError, SpecError};
+pub use explain::{ExplainStep, Explanation};
+pub use frozen::FrozenParser;
+pub use group::{ArgGroup, GroupRule};
+#[cfg(feature = "json")]
+pub use json::{validate_json_spec, JSON_SPEC_SCHEMA};
+pub use key_value::KeyValidator;
+pub use lint::{LintFinding, LintRule};
+pub use matches::{ArgMatches, REDACTED};
+pub use module::CliModule;
+pub use path::{normalize_path, PathStyle};
+pub use preprocess::Preprocessor;
+pub use probe::{when_ci, when_os, when_tty, Condition};
+pub use profile::{Profile, PROFILE};
+pub use prompt::{confirm, confirm_with, ASSUME_YES, DRY_RUN};
+pub use quiet::QUIET;
+pub use quote::{join, quote, QuoteStyle};
+pub use range::ValueRange;
+#[cfg(feature = "derive")]
+pub use rust_arguments_derive::Arguments;
+pub use script::ScriptLines;
+pub use shell::Shell;
+pub use shell_init::SHELL_INIT;
+pub use shortcut::Shortcut;
+pub use source::{Provenance, ValueSource};
+pub use spec::{ArgSpec, CommandSpec};
+pub use store::ValueStore;
+pub use term::{
+    enable_virtual_terminal, set_term_caps, strip_ansi, ColorChoice, ColorDepth, Stream, TermCaps,
+};
+#[cfg(feature = "timings")]
+pub use timings::{reset_timings, timing_report, timings, DEBUG_TIMINGS};
+pub use typed::{Flag, Opt, Positional};
+pub use validation::Validation;
+pub use warning::{set_quiet_warnings, set_warning_handler, warn, WarningHandler, QUIET_WARNINGS};
+pub use width::{display_width, pad_to_width};
+
+pub type Validator = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+pub type ValueParser = Arc<dyn Fn(&str) -> Result<(), String> + Send + Sync>;
+pub type MatchesValidator = Arc<dyn Fn(&ArgMatches) -> Result<(), String> + Send + Sync>;
 
 #[derive(Clone)]
 pub struct Arg {
@@ -9,7 +128,64 @@ pub struct Arg {
     pub takes_value: bool,
     pub required: bool,
     pub default: Option<String>,
-    pub validator: Option<Arc<dyn Fn(&str) -> bool + Send + Sync>>,
+    pub validator: Option<Validator>,
+    pub context_validator: Option<ContextValidator>,
+    pub is_path: bool,
+    pub sensitive: bool,
+    pub global: bool,
+    pub choices: Vec<String>,
+    pub on_duplicate: DuplicatePolicy,
+    pub multiple: bool,
+    pub conditional_defaults: Vec<(Condition, String)>,
+    pub hidden: Option<Condition>,
+    pub examples: Vec<String>,
+    pub aliases: Vec<Alias>,
+    pub env: Option<String>,
+    pub positional: bool,
+    pub value_parser: Option<ValueParser>,
+    pub introduced_in: Option<String>,
+    pub removed_in: Option<String>,
+    pub replaced_by: Option<String>,
+    pub display_name: Option<String>,
+    pub range: Option<ValueRange>,
+    pub soft_required: Option<String>,
+    pub experimental: Option<String>,
+    pub key_value: bool,
+    pub value_delimiter: Option<char>,
+    pub forbid_empty_values: bool,
+    pub levels: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub requires: Vec<String>,
+    pub capture: Capture,
+    pub ignore_case: bool,
+    pub trailing_var_arg: bool,
+    pub required_when: Vec<Condition>,
+    pub glob_expand: bool,
+    pub negatable: bool,
+    pub num_values: usize,
+    pub help: Option<String>,
+    pub prefix_choices: bool,
+    pub required_if: Vec<(String, String)>,
+    pub required_unless: Vec<String>,
+    pub key_validators: Vec<(String, KeyValidator)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DuplicatePolicy {
+    #[default]
+    LastWins,
+    FirstWins,
+    Error,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum AmbiguityPolicy {
+    #[default]
+    PreferSubcommand,
+    PreferPositional,
+    Error,
 }
 
 impl fmt::Debug for Arg {
@@ -20,21 +196,320 @@ impl fmt::Debug for Arg {
             .field("long", &self.long)
             .field("takes_value", &self.takes_value)
             .field("required", &self.required)
-            .field("default", &self.default)
+            .field(
+                "default",
+                &self.default.as_ref().map(|default| {
+                    if self.sensitive {
+                        REDACTED
+                    } else {
+                        default.as_str()
+                    }
+                }),
+            )
+            .field("is_path", &self.is_path)
+            .field("sensitive", &self.sensitive)
+            .field("global", &self.global)
+            .field("choices", &self.choices)
+            .field("on_duplicate", &self.on_duplicate)
+            .field("multiple", &self.multiple)
+            .field("hidden", &self.hidden)
+            .field("examples", &self.examples)
+            .field("aliases", &self.aliases)
+            .field("env", &self.env)
+            .field("positional", &self.positional)
+            .field("introduced_in", &self.introduced_in)
+            .field("removed_in", &self.removed_in)
+            .field("replaced_by", &self.replaced_by)
+            .field("display_name", &self.display_name)
+            .field("range", &self.range)
+            .field("soft_required", &self.soft_required)
+            .field("experimental", &self.experimental)
+            .field("key_value", &self.key_value)
+            .field("value_delimiter", &self.value_delimiter)
+            .field("forbid_empty_values", &self.forbid_empty_values)
+            .field("levels", &self.levels)
+            .field("conflicts_with", &self.conflicts_with)
+            .field("requires", &self.requires)
+            .field("capture", &self.capture)
+            .field("ignore_case", &self.ignore_case)
+            .field("trailing_var_arg", &self.trailing_var_arg)
+            .field("required_when", &self.required_when)
+            .field("glob_expand", &self.glob_expand)
+            .field("negatable", &self.negatable)
+            .field("num_values", &self.num_values)
+            .field("help", &self.help)
+            .field("prefix_choices", &self.prefix_choices)
+            .field("required_if", &self.required_if)
+            .field("required_unless", &self.required_unless)
             .finish()
     }
 }
 
-#[derive(Debug)]
-pub struct ArgMatches {
-    pub values: HashMap<String, String>,
-    pub flags: HashMap<String, bool>,
-    pub positionals: Vec<String>,
+impl Arg {
+    pub fn new(name: &str) -> Self {
+        Arg {
+            name: name.to_string(),
+            short: None,
+            long: None,
+            takes_value: false,
+            required: false,
+            default: None,
+            validator: None,
+            context_validator: None,
+            is_path: false,
+            sensitive: false,
+            global: false,
+            choices: Vec::new(),
+            on_duplicate: DuplicatePolicy::LastWins,
+            multiple: false,
+            conditional_defaults: Vec::new(),
+            hidden: None,
+            examples: Vec::new(),
+            aliases: Vec::new(),
+            env: None,
+            positional: false,
+            value_parser: None,
+            introduced_in: None,
+            removed_in: None,
+            replaced_by: None,
+            display_name: None,
+            range: None,
+            soft_required: None,
+            experimental: None,
+            key_value: false,
+            value_delimiter: None,
+            forbid_empty_values: false,
+            levels: Vec::new(),
+            conflicts_with: Vec::new(),
+            requires: Vec::new(),
+            capture: Capture::Greedy,
+            ignore_case: false,
+            trailing_var_arg: false,
+            required_when: Vec::new(),
+            glob_expand: false,
+            negatable: false,
+            num_values: 1,
+            help: None,
+            prefix_choices: false,
+            required_if: Vec::new(),
+            required_unless: Vec::new(),
+            key_validators: Vec::new(),
+        }
+    }
+
+    pub(crate) fn env_var(&self, namespace: Option<&str>) -> Option<String> {
+        match (&self.env, namespace) {
+            (Some(var), _) => Some(var.clone()),
+            (None, Some(namespace)) if !self.positional => {
+                Some(format!("{}_{}", namespace, env_segment(&self.name)))
+            }
+            _ => None,
+        }
+    }
+
+    pub fn display(&self) -> &str {
+        self.display_name.as_deref().unwrap_or(&self.name)
+    }
+
+    pub fn effective_default(&self) -> Option<&str> {
+        self.conditional_defaults
+            .iter()
+            .find(|(condition, _)| condition.holds())
+            .map(|(_, value)| value.as_str())
+            .or(self.default.as_deref())
+    }
+
+    pub fn is_hidden(&self) -> bool {
+        self.hidden.as_ref().is_some_and(Condition::holds)
+    }
+
+    fn accept_values(&self, flag: &str, raw: &str) -> Result<Vec<String>, ParseError> {
+        let elements: Vec<&str> = match self.value_delimiter {
+            Some(delimiter) => raw.split(delimiter).collect(),
+            None => vec![raw],
+        };
+        if self.forbid_empty_values && elements.iter().any(|e| e.is_empty()) {
+            return Err(ParseError::EmptyValue {
+                arg: flag.to_string(),
+                value: if self.sensitive { REDACTED } else { raw }.to_string(),
+            });
+        }
+        elements
+            .into_iter()
+            .map(|element| self.accept_value(flag, element))
+            .collect()
+    }
+
+    fn accept_value(&self, flag: &str, raw: &str) -> Result<String, ParseError> {
+        let value = if self.is_path {
+            normalize_path(raw)
+        } else {
+            raw.to_string()
+        };
+        let value = match self
+            .choices
+            .iter()
+            .find(|choice| self.ignore_case && choice.to_lowercase() == value.to_lowercase())
+        {
+            Some(choice) => choice.clone(),
+            None => value,
+        };
+        let shown = if self.sensitive {
+            REDACTED.to_string()
+        } else {
+            raw.to_string()
+        };
+        let value = if self.prefix_choices && !value.is_empty() && !self.choices.contains(&value) {
+            let fold = |s: &str| {
+                if self.ignore_case {
+                    s.to_lowercase()
+                } else {
+                    s.to_string()
+                }
+            };
+            let prefix = fold(&value);
+            let candidates: Vec<String> = self
+                .choices
+                .iter()
+                .filter(|choice| fold(choice).starts_with(&prefix))
+                .cloned()
+                .collect();
+            match candidates.len() {
+                0 => value,
+                1 => candidates.into_iter().next().unwrap_or_default(),
+                _ => {
+                    return Err(ParseError::AmbiguousChoice {
+                        arg: flag.to_string(),
+                        value: shown,
+                        candidates,
+                    })
+                }
+            }
+        } else {
+            value
+        };
+        if let Some(value_parser) = &self.value_parser {
+            if let Err(message) = value_parser(&value) {
+                return Err(ParseError::ConversionFailed {
+                    arg: flag.to_string(),
+                    value: shown,
+                    message,
+                });
+            }
+        }
+        if let Some(range) = self.range {
+            match value.parse::<i64>() {
+                Ok(number) if range.contains(number) => {}
+                Ok(_) => {
+                    return Err(ParseError::OutOfRange {
+                        arg: flag.to_string(),
+                        value: shown,
+                        range,
+                    })
+                }
+                Err(_) => {
+                    return Err(ParseError::InvalidValue {
+                        arg: flag.to_string(),
+                        value: shown,
+                        message: Some("expected an integer".to_string()),
+                    })
+                }
+            }
+        }
+        if !self.choices.is_empty() && !self.choices.contains(&value) {
+            let suggestion = suggest::closest(&value, self.choices.iter().map(String::as_str));
+            return Err(ParseError::InvalidChoice {
+                arg: flag.to_string(),
+                value: shown,
+                choices: self.choices.clone(),
+                suggestion: suggestion.map(str::to_string),
+            });
+        }
+        if let Some(validator) = &self.validator {
+            if let Err(message) = validator(&value) {
+                return Err(ParseError::InvalidValue {
+                    arg: flag.to_string(),
+                    value: shown,
+                    message: Some(message).filter(|m| !m.is_empty()),
+                });
+            }
+        }
+        Ok(value)
+    }
 }
 
+#[derive(Clone)]
 pub struct ArgParser {
     args: Vec<Arg>,
     subcommands: HashMap<String, ArgParser>,
+    collect_errors: bool,
+    disallowed_globals: Vec<String>,
+    groups: Vec<ArgGroup>,
+    shortcuts: Vec<Shortcut>,
+    abbreviations: Vec<Abbreviation>,
+    profiles: Vec<Profile>,
+    matches_validators: Vec<MatchesValidator>,
+    aliases: Vec<Alias>,
+    hide_internal_aliases: bool,
+    name: Option<String>,
+    about: Option<String>,
+    author: Option<String>,
+    namespace: Option<String>,
+    version: Option<String>,
+    accept_removed_args: bool,
+    exec_style: bool,
+    dash_sections: bool,
+    default_subcommand: Option<String>,
+    ambiguity_policy: Option<AmbiguityPolicy>,
+    preprocessors: Vec<Preprocessor>,
+    usage_examples: Vec<String>,
+    shell_init: Option<shell_init::ShellInit>,
+    quiet_arg: Option<String>,
+    #[cfg(feature = "dotenv")]
+    env_files: Vec<PathBuf>,
+    config_files: Vec<std::path::PathBuf>,
+    #[cfg(feature = "timings")]
+    created: std::time::Instant,
+    #[cfg(feature = "timings")]
+    construction_recorded: std::sync::OnceLock<()>,
+}
+
+impl Default for ArgParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FromIterator<Arg> for ArgParser {
+    fn from_iter<I: IntoIterator<Item = Arg>>(iter: I) -> Self {
+        let mut parser = ArgParser::new();
+        parser.extend(iter);
+        parser
+    }
+}
+
+impl Extend<Arg> for ArgParser {
+    fn extend<I: IntoIterator<Item = Arg>>(&mut self, iter: I) {
+        self.args.extend(iter);
+    }
+}
+
+impl IntoIterator for ArgParser {
+    type Item = Arg;
+    type IntoIter = std::vec::IntoIter<Arg>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.args.into_iter()
+    }
+}
+
+impl<'a> IntoIterator for &'a ArgParser {
+    type Item = &'a Arg;
+    type IntoIter = std::slice::Iter<'a, Arg>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.args.iter()
+    }
 }
 
 impl ArgParser {
@@ -42,22 +517,48 @@ impl ArgParser {
         Self {
             args: Vec::new(),
             subcommands: HashMap::new(),
+            collect_errors: false,
+            disallowed_globals: Vec::new(),
+            groups: Vec::new(),
+            shortcuts: Vec::new(),
+            abbreviations: Vec::new(),
+            profiles: Vec::new(),
+            matches_validators: Vec::new(),
+            aliases: Vec::new(),
+            hide_internal_aliases: false,
+            name: None,
+            about: None,
+            author: None,
+            namespace: None,
+            version: None,
+            accept_removed_args: false,
+            exec_style: false,
+            dash_sections: false,
+            default_subcommand: None,
+            ambiguity_policy: None,
+            preprocessors: Vec::new(),
+            usage_examples: Vec::new(),
+            shell_init: None,
+            quiet_arg: None,
+            #[cfg(feature = "dotenv")]
+            env_files: Vec::new(),
+            config_files: Vec::new(),
+            #[cfg(feature = "timings")]
+            created: std::time::Instant::now(),
+            #[cfg(feature = "timings")]
+            construction_recorded: std::sync::OnceLock::new(),
         }
     }
 
     pub fn arg(mut self, name: &str) -> Self {
-        self.args.push(Arg {
-            name: name.to_string(),
-            short: None,
-            long: None,
-            takes_value: false,
-            required: false,
-            default: None,
-            validator: None,
-        });
+        self.args.push(Arg::new(name));
         self
     }
 
+    pub fn args(&self) -> &[Arg] {
+        &self.args
+    }
+
     pub fn short(mut self, name: &str, short: char) -> Self {
         if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
             arg.short = Some(short);
@@ -79,6 +580,47 @@ impl ArgParser {
         self
     }
 
+    pub fn positional(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.positional = true;
+            arg.takes_value = true;
+        }
+        self
+    }
+
+    pub fn trailing_var_arg(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.positional = true;
+            arg.takes_value = true;
+            arg.multiple = true;
+            arg.trailing_var_arg = true;
+        }
+        self
+    }
+
+    pub fn positional_multiple(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.positional = true;
+            arg.takes_value = true;
+            arg.multiple = true;
+        }
+        self
+    }
+
+    pub fn help(mut self, name: &str, help: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.help = Some(help.to_string());
+        }
+        self
+    }
+
+    pub fn display_name(mut self, name: &str, display: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.display_name = Some(display.to_string());
+        }
+        self
+    }
+
     pub fn required(mut self, name: &str) -> Self {
         if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
             arg.required = true;
@@ -93,84 +635,1421 @@ impl ArgParser {
         self
     }
 
-    pub fn validator<F>(mut self, name: &str, validator: F) -> Self
+    pub fn env(mut self, name: &str, var: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.env = Some(var.to_string());
+        }
+        self
+    }
+
+    pub fn namespace(mut self, prefix: &str) -> Self {
+        self.namespace = Some(prefix.to_string());
+        self
+    }
+
+    pub fn required_when(mut self, name: &str, condition: Condition) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.required_when.push(condition);
+        }
+        self
+    }
+
+    pub fn required_in_ci(self, name: &str) -> Self {
+        self.required_when(name, Condition::Ci)
+    }
+
+    pub fn default_when(mut self, name: &str, condition: Condition, default: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.conditional_defaults
+                .push((condition, default.to_string()));
+        }
+        self
+    }
+
+    pub fn hidden(self, name: &str) -> Self {
+        self.hide_when(name, Condition::Always)
+    }
+
+    pub fn hide_when(mut self, name: &str, condition: Condition) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.hidden = Some(condition);
+        }
+        self
+    }
+
+    pub fn arg_example(mut self, name: &str, example: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.examples.push(example.to_string());
+        }
+        self
+    }
+
+    pub fn validator<F, R>(mut self, name: &str, validator: F) -> Self
     where
-        F: 'static + Fn(&str) -> bool + Send + Sync,
+        F: 'static + Fn(&str) -> R + Send + Sync,
+        R: Validation,
     {
         if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
-            arg.validator = Some(Arc::new(validator));
+            arg.validator = Some(validation::validator(validator));
+        }
+        self
+    }
+
+    pub fn value_parser<T: FromArgValue>(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.value_parser = Some(convert::value_parser::<T>(name));
+        }
+        self
+    }
+
+    pub fn choices(mut self, name: &str, choices: &[&str]) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.choices = choices.iter().map(|c| c.to_string()).collect();
+        }
+        self
+    }
+
+    pub fn glob_expand(mut self, name: &str, expand: bool) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.glob_expand = expand;
+        }
+        self
+    }
+
+    pub fn num_values(mut self, name: &str, count: usize) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.num_values = count.max(1);
+        }
+        self
+    }
+
+    pub fn negatable(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.negatable = true;
+        }
+        self
+    }
+
+    pub fn prefix_choices(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.prefix_choices = true;
+        }
+        self
+    }
+
+    pub fn ignore_case(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.ignore_case = true;
+        }
+        self
+    }
+
+    pub fn on_duplicate(mut self, name: &str, policy: DuplicatePolicy) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.on_duplicate = policy;
+        }
+        self
+    }
+
+    pub fn multiple(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.multiple = true;
+        }
+        self
+    }
+
+    pub fn value_delimiter(mut self, name: &str, delimiter: char) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.value_delimiter = Some(delimiter);
+        }
+        self
+    }
+
+    pub fn forbid_empty_values(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.forbid_empty_values = true;
+        }
+        self
+    }
+
+    pub fn path(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.is_path = true;
+        }
+        self
+    }
+
+    pub fn sensitive(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.sensitive = true;
+        }
+        self
+    }
+
+    pub fn global(mut self, name: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.global = true;
+        }
+        self
+    }
+
+    pub fn disallow_global(mut self, name: &str) -> Self {
+        self.disallowed_globals.push(name.to_string());
+        self
+    }
+
+    pub fn conflicts_with(mut self, name: &str, other: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.conflicts_with.push(other.to_string());
+        }
+        self
+    }
+
+    pub fn requires(mut self, name: &str, other: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.requires.push(other.to_string());
+        }
+        self
+    }
+
+    pub fn required_if(mut self, name: &str, other: &str, value: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.required_if.push((other.to_string(), value.to_string()));
+        }
+        self
+    }
+
+    pub fn required_unless(mut self, name: &str, other: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.required_unless.push(other.to_string());
         }
         self
     }
 
+    pub fn group(mut self, name: &str, rule: GroupRule, members: &[&str]) -> Self {
+        self.groups.push(ArgGroup {
+            name: name.to_string(),
+            rule,
+            members: members.iter().map(|m| m.to_string()).collect(),
+        });
+        self
+    }
+
+    pub fn validate_matches<F>(mut self, validator: F) -> Self
+    where
+        F: 'static + Fn(&ArgMatches) -> Result<(), String> + Send + Sync,
+    {
+        self.matches_validators.push(Arc::new(validator));
+        self
+    }
+
     pub fn subcommand(mut self, name: &str, parser: ArgParser) -> Self {
         self.subcommands.insert(name.to_string(), parser);
         self
     }
 
-    pub fn parse(mut self, args: &[String]) -> ArgMatches {
-        let mut values = HashMap::new();
-        let mut flags = HashMap::new();
-        let mut positionals = Vec::new();
-        let mut iter = args.iter().skip(1).peekable();
+    pub fn exec_style(mut self, exec_style: bool) -> Self {
+        self.exec_style = exec_style;
+        self
+    }
 
-        while let Some(arg) = iter.next() {
-            if arg.starts_with("--") {
-                let name = &arg[2..];
-                if let Some(a) = self.args.iter().find(|a| a.long.as_deref() == Some(name)) {
+    pub fn dash_sections(mut self, dash_sections: bool) -> Self {
+        self.dash_sections = dash_sections;
+        self
+    }
+
+    pub fn default_subcommand(mut self, name: &str) -> Self {
+        self.default_subcommand = Some(name.to_string());
+        self
+    }
+
+    pub fn ambiguity_policy(mut self, policy: AmbiguityPolicy) -> Self {
+        self.ambiguity_policy = Some(policy);
+        self
+    }
+
+    pub fn collect_errors(mut self) -> Self {
+        self.collect_errors = true;
+        self
+    }
+
+    fn scope_for(&self, name: &str, sub: &ArgParser, scope: &Scope) -> Scope {
+        let mut command_path = scope.command_path.clone();
+        command_path.push(name.to_string());
+        let mut inner = Scope {
+            collect_errors: self.collect_errors || scope.collect_errors,
+            command_path,
+            globals: Vec::new(),
+            rejected_globals: Vec::new(),
+            env_file_vars: scope.env_file_vars.clone(),
+            config_files: scope.config_files.clone(),
+            version: self.version.clone().or_else(|| scope.version.clone()),
+            namespace: self
+                .namespace
+                .as_ref()
+                .or(scope.namespace.as_ref())
+                .map(|namespace| format!("{}_{}", namespace, env_segment(name))),
+            accept_removed_args: self.accept_removed_args || scope.accept_removed_args,
+            argv_offset: scope.argv_offset,
+            program: scope.program.clone(),
+            trace: scope.trace.clone(),
+        };
+        let candidates = self.args.iter().chain(&scope.globals);
+        for global in candidates.filter(|a| a.global) {
+            if sub.args.iter().any(|a| a.name == global.name) {
+                continue;
+            }
+            if sub.disallowed_globals.contains(&global.name) {
+                inner.rejected_globals.push(global.clone());
+            } else {
+                inner.globals.push(global.clone());
+            }
+        }
+        for rejected in &scope.rejected_globals {
+            if !sub.args.iter().any(|a| a.name == rejected.name) {
+                inner.rejected_globals.push(rejected.clone());
+            }
+        }
+        inner
+    }
+
+    fn unknown_argument<F>(
+        &self,
+        token: &str,
+        scope: &Scope,
+        known: &[&Arg],
+        matches: F,
+    ) -> ParseError
+    where
+        F: Fn(&Arg) -> bool,
+    {
+        if scope.rejected_globals.iter().any(matches) {
+            return ParseError::DisallowedGlobal {
+                arg: token.to_string(),
+                subcommand: scope.command_path.join(" "),
+            };
+        }
+        let suggestion = token.strip_prefix("--").and_then(|name| {
+            let longs = known.iter().filter(|a| !a.is_hidden()).flat_map(|a| {
+                a.long
+                    .iter()
+                    .chain(a.aliases.iter().map(|alias| &alias.name))
+            });
+            suggest::closest(name, longs.map(String::as_str)).map(|long| format!("--{}", long))
+        });
+        ParseError::UnknownArgument {
+            token: token.to_string(),
+            suggestion,
+        }
+    }
+
+    fn unknown_subcommand(&self, token: &str) -> Option<ParseError> {
+        let names = self.subcommands.iter().flat_map(|(name, sub)| {
+            std::iter::once(name).chain(sub.aliases.iter().map(|a| &a.name))
+        });
+        let suggestion = suggest::closest(token, names.map(String::as_str))?;
+        Some(ParseError::UnknownArgument {
+            token: token.to_string(),
+            suggestion: Some(suggestion.to_string()),
+        })
+    }
+
+    pub fn shortcut(mut self, name: &str, args: &[&str]) -> Self {
+        self.shortcuts.push(Shortcut {
+            name: name.to_string(),
+            args: args.iter().map(|a| a.to_string()).collect(),
+        });
+        self
+    }
+
+    pub fn shortcuts(&self) -> &[Shortcut] {
+        &self.shortcuts
+    }
+
+    pub fn render_shortcuts(&self, program: &str, shell: Shell) -> String {
+        self.shortcuts
+            .iter()
+            .map(|shortcut| format!("{}\n", shortcut.render(program, shell)))
+            .collect()
+    }
+
+    pub fn check_spec(&self) -> Result<(), SpecError> {
+        if let Some(groups) = group::find_cycle(&self.groups) {
+            return Err(SpecError::GroupCycle { groups });
+        }
+        for (i, arg) in self.args.iter().enumerate() {
+            for other in &self.args[..i] {
+                if other.name == arg.name {
+                    return Err(SpecError::DuplicateArg {
+                        name: arg.name.clone(),
+                    });
+                }
+                let same_long = arg.long.is_some() && other.long == arg.long;
+                let same_short = arg.short.is_some() && other.short == arg.short;
+                if same_long || same_short {
+                    return Err(SpecError::DuplicateFlag {
+                        first: other.name.clone(),
+                        second: arg.name.clone(),
+                    });
+                }
+            }
+        }
+        for profile in &self.profiles {
+            for (arg, _) in &profile.values {
+                if !self.args.iter().any(|a| &a.name == arg) {
+                    return Err(SpecError::UnknownProfileArg {
+                        profile: profile.name.clone(),
+                        arg: arg.clone(),
+                    });
+                }
+            }
+        }
+        for arg in &self.args {
+            let conditions = arg.required_if.iter().map(|(other, _)| other);
+            for other in arg
+                .conflicts_with
+                .iter()
+                .chain(&arg.requires)
+                .chain(&arg.required_unless)
+                .chain(conditions)
+            {
+                let known = self.args.iter().any(|a| &a.name == other)
+                    || self.groups.iter().any(|g| &g.name == other);
+                if !known {
+                    return Err(SpecError::UnknownRelation {
+                        arg: arg.name.clone(),
+                        other: other.clone(),
+                    });
+                }
+            }
+        }
+        for group in &self.groups {
+            for member in &group.members {
+                let known = self.args.iter().any(|a| &a.name == member)
+                    || self.groups.iter().any(|g| &g.name == member);
+                if !known {
+                    return Err(SpecError::UnknownGroupMember {
+                        group: group.name.clone(),
+                        member: member.clone(),
+                    });
+                }
+            }
+        }
+        self.check_abbreviations()?;
+        if let Some(name) = &self.default_subcommand {
+            if !self.subcommands.contains_key(name) {
+                return Err(SpecError::UnknownDefaultSubcommand { name: name.clone() });
+            }
+        }
+        if self.ambiguity_policy.is_none() {
+            let positional = self.args.iter().find(|a| a.positional);
+            let subcommand = self.subcommands.keys().min();
+            if let (Some(positional), Some(subcommand)) = (positional, subcommand) {
+                return Err(SpecError::AmbiguousGrammar {
+                    positional: positional.name.clone(),
+                    subcommand: subcommand.clone(),
+                });
+            }
+        }
+        for arg in self.args.iter().filter(|a| a.negatable) {
+            let message = match &arg.long {
+                _ if arg.takes_value || arg.positional => "only flags can be negated".to_string(),
+                None => "a negatable flag needs a long form".to_string(),
+                Some(long) => match self
+                    .args
+                    .iter()
+                    .find(|a| a.matches_long(&format!("no-{}", long)))
+                {
+                    Some(other) => format!("--no-{} is already used by '{}'", long, other.name),
+                    None => continue,
+                },
+            };
+            return Err(SpecError::InvalidNegatable {
+                arg: arg.name.clone(),
+                message,
+            });
+        }
+        let positionals: Vec<&Arg> = self.args.iter().filter(|a| a.positional).collect();
+        if let Some(trailing) = positionals.iter().find(|a| a.trailing_var_arg) {
+            let variadics = positionals.iter().filter(|a| a.multiple).count();
+            if positionals.last().map(|a| &a.name) != Some(&trailing.name) || variadics > 1 {
+                return Err(SpecError::MisplacedTrailingVarArg {
+                    arg: trailing.name.clone(),
+                });
+            }
+        }
+        let mut greedy = self
+            .args
+            .iter()
+            .filter(|a| a.positional && a.multiple && a.capture == Capture::Greedy);
+        if let (Some(first), Some(second)) = (greedy.next(), greedy.next()) {
+            return Err(SpecError::AmbiguousVariadics {
+                first: first.name.clone(),
+                second: second.name.clone(),
+            });
+        }
+        for sub in self.subcommands.values() {
+            sub.check_spec()?;
+        }
+        Ok(())
+    }
+
+    pub fn parse(&self, args: &[String]) -> ArgMatches {
+        if args.get(1).map(String::as_str) == Some(PRINT_COMPLETION_CANDIDATES) {
+            self.print_completion_candidates(&args[2..]);
+            std::process::exit(0);
+        }
+        if let Some(init) = self.shell_init_request(args) {
+            print!("{}", init);
+            std::process::exit(0);
+        }
+        #[cfg(feature = "json")]
+        if self.bug_report_requested(args) {
+            print!("{}", self.render_bug_report(args));
+            std::process::exit(0);
+        }
+        if let Some(list) = self.render_list(args.get(1..).unwrap_or_default()) {
+            print!("{}", list);
+            std::process::exit(0);
+        }
+        match self.try_parse(args) {
+            Ok(matches) => {
+                let leaf = matches.innermost();
+                if leaf.quiet_warnings() || self.quiet_in(&matches) {
+                    set_quiet_warnings(true);
+                }
+                let mut level = Some(&matches);
+                while let Some(current) = level {
+                    for deprecation in current.deprecations() {
+                        warn(deprecation);
+                    }
+                    level = current.subcommand().map(|(_, sub)| sub);
+                }
+                if let Some(format) = leaf.dump_config_format() {
+                    print!("{}", leaf.render_config(format));
+                    std::process::exit(0);
+                }
+                #[cfg(feature = "timings")]
+                if leaf.debug_timings() {
+                    eprint!("{}", timing_report());
+                }
+                matches
+            }
+            Err(ParseError::HelpRequested { help }) => {
+                print!("{}", ColorChoice::Auto.prepare(&help, Stream::Stdout));
+                std::process::exit(0);
+            }
+            Err(ParseError::VersionRequested { version }) => {
+                match version
+                    .rsplit_once(' ')
+                    .filter(|_| self.quiet_requested(args))
+                {
+                    Some((_, number)) => println!("{}", number),
+                    None => println!("{}", version),
+                }
+                std::process::exit(0);
+            }
+            Err(err) => {
+                let message = self.render_error(args, &err);
+                eprint!("{}", ColorChoice::Auto.prepare(&message, Stream::Stderr));
+                std::process::exit(2);
+            }
+        }
+    }
+
+    pub fn try_parse(&self, args: &[String]) -> Result<ArgMatches, ParseError> {
+        #[cfg(feature = "timings")]
+        self.record_construction();
+        let scope = Scope {
+            program: self.program(args),
+            ..Scope::default()
+        };
+        timings::time("parse", || self.parse_in_scope(args, &scope))
+    }
+
+    pub fn parse_with_program_name(
+        &self,
+        program: &str,
+        args: &[String],
+    ) -> Result<ArgMatches, ParseError> {
+        let mut argv = Vec::with_capacity(args.len() + 1);
+        argv.push(program.to_string());
+        argv.extend_from_slice(args);
+        self.try_parse(&argv)
+    }
+
+    pub fn parse_args_only(&self, args: &[String]) -> Result<ArgMatches, ParseError> {
+        self.parse_with_program_name("", args)
+    }
+
+    pub fn try_parse_from<I>(&self, args: I) -> Result<ArgMatches, ParseError>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let args: Vec<String> = args.into_iter().map(Into::into).collect();
+        self.parse_args_only(&args)
+    }
+
+    fn parse_in_scope(&self, args: &[String], scope: &Scope) -> Result<ArgMatches, ParseError> {
+        let processed = self.preprocess(args);
+        let args = processed.as_deref().unwrap_or(args);
+        #[cfg(feature = "dotenv")]
+        let scope = &self.load_env_files(scope)?;
+        let scope = &self.load_config_files(scope)?;
+        let collect_errors = self.collect_errors || scope.collect_errors;
+        let version = self.version.as_deref().or(scope.version.as_deref());
+        let namespace = self.namespace.as_deref().or(scope.namespace.as_deref());
+        let accept_removed_args = self.accept_removed_args || scope.accept_removed_args;
+        let known: Vec<&Arg> = self
+            .args
+            .iter()
+            .chain(&scope.globals)
+            .filter(|a| accept_removed_args || !a.is_removed(version))
+            .collect();
+        let mut state = ParseState {
+            tracing: scope.trace.is_some(),
+            ..ParseState::default()
+        };
+        let mut slots = self.args.iter().filter(|a| a.positional).peekable();
+        let deferred = self
+            .args
+            .iter()
+            .filter(|a| a.positional)
+            .skip_while(|a| !a.multiple)
+            .nth(1)
+            .is_some();
+        let mut pending = Vec::new();
+        let mut raw = false;
+        let mut iter = args.iter().enumerate().skip(1).peekable();
+        let mut default_subcommand = self
+            .default_subcommand
+            .as_ref()
+            .and_then(|name| self.subcommands.get_key_value(name));
+        let mut subcommand = None;
+
+        'tokens: while let Some((index, arg)) = iter.next() {
+            if !collect_errors && !state.errors.is_empty() {
+                break;
+            }
+            state.index = scope.argv_offset + index;
+            if !raw && self.wants_help(arg, &known) {
+                return Err(ParseError::HelpRequested {
+                    help: self.help_text(scope, &known, version),
+                });
+            }
+            if !raw && self.wants_version(arg, &known, version) {
+                let version = version.unwrap_or_default();
+                return Err(ParseError::VersionRequested {
+                    version: format!("{} {}", scope.program, version)
+                        .trim_start()
+                        .to_string(),
+                });
+            }
+            if self.dash_sections && !raw && arg == "--" {
+                let rest: Vec<&String> = iter.by_ref().map(|(_, token)| token).collect();
+                state.sections = rest
+                    .split(|token| *token == "--")
+                    .map(|section| section.iter().map(|token| token.to_string()).collect())
+                    .collect();
+                break;
+            }
+            if self.exec_style && !raw {
+                let capture_from = if arg == "--" {
+                    Some(index + 1)
+                } else if let Some(name) = arg.strip_prefix("--") {
+                    let name = name.split_once('=').map_or(name, |(name, _)| name);
+                    (!known.iter().any(|a| a.matches_long(name))).then_some(index)
+                } else if arg.starts_with('-') && arg != "-" {
+                    let c = arg.chars().nth(1);
+                    (!known.iter().any(|a| a.short == c)).then_some(index)
+                } else if self.find_subcommand(arg).is_none()
+                    && slots.peek().is_none()
+                    && default_subcommand.is_none()
+                {
+                    Some(index)
+                } else {
+                    None
+                };
+                if let Some(start) = capture_from {
+                    for (offset, token) in args[start..].iter().enumerate() {
+                        state.index = scope.argv_offset + start + offset;
+                        state.push_extra(token);
+                    }
+                    break;
+                }
+            }
+            if !raw && arg == "--" {
+                raw = true;
+                continue;
+            }
+            if let Some(long) = arg.strip_prefix("--").filter(|_| !raw) {
+                let (name, inline) = match long.split_once('=') {
+                    Some((name, value)) => (name, Some(value)),
+                    None => (long, None),
+                };
+                let flag = format!("--{}", name);
+                if let Some(a) = known.iter().find(|a| a.matches_long(name)) {
                     if a.takes_value {
-                        if let Some(value) = iter.next() {
-                            if let Some(validator) = &a.validator {
-                                if !validator(value) {
-                                    panic!("Invalid value for argument: {}", name);
-                                }
-                            }
-                            values.insert(a.name.clone(), value.clone());
+                        if let Some(value) = inline.or_else(|| iter.next().map(|(_, v)| v.as_str()))
+                        {
+                            state.set_value(scope, a, &flag, value);
+                            state.take_more_values(scope, a, &flag, &mut iter);
+                        } else {
+                            state.errors.push(ParseError::MissingValue { arg: flag });
                         }
+                    } else if let Some(value) = inline {
+                        state.errors.push(ParseError::InvalidValue {
+                            arg: flag,
+                            value: value.to_string(),
+                            message: Some("the flag takes no value".to_string()),
+                        });
                     } else {
-                        flags.insert(a.name.clone(), true);
+                        state.set_flag(a, &flag, true);
+                    }
+                } else if let Some(a) = name.strip_prefix("no-").and_then(|positive| {
+                    known
+                        .iter()
+                        .find(|a| a.negatable && a.matches_long(positive))
+                }) {
+                    match inline {
+                        Some(value) => state.errors.push(ParseError::InvalidValue {
+                            arg: flag,
+                            value: value.to_string(),
+                            message: Some("the flag takes no value".to_string()),
+                        }),
+                        None => state.set_flag(a, &flag, false),
                     }
+                } else {
+                    state.errors.push(
+                        self.unknown_argument(&flag, scope, &known, |a| a.matches_long(name)),
+                    );
                 }
-            } else if arg.starts_with('-') {
+            } else if !raw && arg.starts_with('-') && arg != "-" {
                 let chars: Vec<char> = arg.chars().skip(1).collect();
-                for &c in &chars {
-                    if let Some(a) = self.args.iter().find(|a| a.short == Some(c)) {
-                        if a.takes_value {
-                            if let Some(value) = iter.next() {
-                                if let Some(validator) = &a.validator {
-                                    if !validator(value) {
-                                        panic!("Invalid value for argument: -{}", c);
-                                    }
-                                }
-                                values.insert(a.name.clone(), value.clone());
-                            }
+                for (i, &c) in chars.iter().enumerate() {
+                    if !collect_errors && !state.errors.is_empty() {
+                        break 'tokens;
+                    }
+                    let flag = format!("-{}", c);
+                    let Some(a) = known.iter().find(|a| a.short == Some(c)) else {
+                        state.errors.push(
+                            self.unknown_argument(&flag, scope, &known, |a| a.short == Some(c)),
+                        );
+                        continue;
+                    };
+                    if !a.takes_value {
+                        state.set_flag(a, &flag, true);
+                        continue;
+                    }
+                    let rest = &chars[i + 1..];
+                    if rest.is_empty() {
+                        if let Some((_, value)) = iter.next() {
+                            state.set_value(scope, a, &flag, value);
+                            state.take_more_values(scope, a, &flag, &mut iter);
                         } else {
-                            flags.insert(a.name.clone(), true);
+                            state.errors.push(ParseError::MissingValue { arg: flag });
                         }
+                    } else if !a.key_value
+                        && rest
+                            .iter()
+                            .all(|r| known.iter().any(|k| k.short == Some(*r)))
+                    {
+                        state.errors.push(ParseError::ValueInCluster {
+                            arg: flag,
+                            cluster: arg.clone(),
+                        });
+                    } else {
+                        let rest = rest.strip_prefix(&['=']).unwrap_or(rest);
+                        let value: String = rest.iter().collect();
+                        state.set_value(scope, a, &flag, &value);
+                        state.take_more_values(scope, a, &flag, &mut iter);
                     }
+                    break;
                 }
-            } else if self.subcommands.contains_key(arg) {
-                let sub = self.subcommands.remove(arg).unwrap();
-                return sub.parse(&args[1..]);
             } else {
-                positionals.push(arg.clone());
+                let implicit = default_subcommand.take().filter(|_| !raw);
+                let target = match self.find_subcommand(arg).filter(|_| !raw) {
+                    Some(found) => Some((found, false)),
+                    None => implicit.map(|(name, sub)| ((name.as_str(), sub), true)),
+                };
+                let take_subcommand = match (target, slots.peek()) {
+                    (None, _) => false,
+                    (Some(_), None) => true,
+                    (Some(((name, _), _)), Some(slot)) => {
+                        match self.ambiguity_policy.unwrap_or_default() {
+                            AmbiguityPolicy::PreferSubcommand => true,
+                            AmbiguityPolicy::PreferPositional => false,
+                            AmbiguityPolicy::Error => {
+                                state.errors.push(ParseError::AmbiguousToken {
+                                    token: arg.clone(),
+                                    positional: slot.display().to_string(),
+                                    subcommand: name.to_string(),
+                                });
+                                slots.next();
+                                continue;
+                            }
+                        }
+                    }
+                };
+                match target.filter(|_| take_subcommand) {
+                    Some(((name, sub), false)) => {
+                        let mut inner = self.scope_for(name, sub, scope);
+                        inner.argv_offset = scope.argv_offset + index;
+                        state.record(ExplainStep::Subcommand {
+                            index: state.index,
+                            name: name.to_string(),
+                            implicit: false,
+                        });
+                        subcommand = Some((name, sub, args[index..].to_vec(), inner));
+                        break;
+                    }
+                    Some(((name, sub), true)) => {
+                        let mut inner = self.scope_for(name, sub, scope);
+                        inner.argv_offset = scope.argv_offset + index - 1;
+                        let mut sub_args = vec![name.to_string()];
+                        sub_args.extend_from_slice(&args[index..]);
+                        state.record(ExplainStep::Subcommand {
+                            index: state.index,
+                            name: name.to_string(),
+                            implicit: true,
+                        });
+                        subcommand = Some((name, sub, sub_args, inner));
+                        break;
+                    }
+                    None if deferred && slots.peek().is_some_and(|slot| slot.multiple) => {
+                        pending.push((state.index, arg.as_str()));
+                    }
+                    None => match slots
+                        .next_if(|slot| !slot.multiple)
+                        .or_else(|| slots.peek().copied())
+                    {
+                        Some(slot) if slot.trailing_var_arg => {
+                            let label = format!("<{}>", slot.display());
+                            state.set_value(scope, slot, &label, arg);
+                            for (index, token) in iter.by_ref() {
+                                state.index = scope.argv_offset + index;
+                                state.set_value(scope, slot, &label, token);
+                            }
+                        }
+                        Some(slot) => {
+                            state.set_value(scope, slot, &format!("<{}>", slot.display()), arg)
+                        }
+                        None if state.positionals.is_empty() && !raw => {
+                            match self.unknown_subcommand(arg) {
+                                Some(err) => state.errors.push(err),
+                                None => state.push_extra(arg),
+                            }
+                        }
+                        None => state.push_extra(arg),
+                    },
+                }
             }
         }
 
-        for arg in &self.args {
-            if arg.required && !values.contains_key(&arg.name) {
-                if let Some(default) = &arg.default {
-                    values.insert(arg.name.clone(), default.clone());
+        if !pending.is_empty() {
+            let remaining: Vec<&Arg> = slots.collect();
+            let counts = capture::distribute(&remaining, pending.len(), version);
+            let mut words = pending.into_iter();
+            for (slot, count) in remaining.into_iter().zip(counts) {
+                for (index, word) in words.by_ref().take(count) {
+                    state.index = index;
+                    state.set_value(scope, slot, &format!("<{}>", slot.display()), word);
+                }
+            }
+        }
+        if let Some(trace) = &scope.trace {
+            trace.borrow_mut().append(&mut state.steps);
+        }
+        let ParseState {
+            mut values,
+            mut flags,
+            positionals,
+            sections,
+            mut present,
+            mut errors,
+            mut argv_indices,
+            mut key_values,
+            mut multi_values,
+            mut occurrences,
+            mut grouped_values,
+            ..
+        } = state;
+        let sub_result = match subcommand {
+            Some((name, sub, sub_args, inner)) if collect_errors || errors.is_empty() => {
+                match sub.parse_in_scope(&sub_args, &inner) {
+                    Err(err @ ParseError::HelpRequested { .. })
+                    | Err(err @ ParseError::VersionRequested { .. }) => return Err(err),
+                    result => Some((name, result)),
+                }
+            }
+            _ => None,
+        };
+        if let Some((_, Ok(sub_matches))) = &sub_result {
+            for arg in known.iter().filter(|a| a.global) {
+                let name = &arg.name;
+                if present.contains(name)
+                    || sub_matches.value_source(name) != Some(&ValueSource::CommandLine)
+                {
+                    continue;
+                }
+                if let Some(value) = sub_matches.values.get(name) {
+                    values.insert(name.clone(), value.clone());
+                }
+                if let Some(&set) = sub_matches.flags.get(name) {
+                    flags.insert(name.clone(), set);
+                }
+                if let Some(list) = sub_matches.multi_values.get(name) {
+                    multi_values.insert(name.clone(), list.clone());
+                }
+                if let Some(entries) = sub_matches.key_values.get(name) {
+                    key_values.insert(name.clone(), entries.clone());
+                }
+                if let Some(&index) = sub_matches.argv_indices.get(name) {
+                    argv_indices.insert(name.clone(), index);
+                }
+                if let Some(&count) = sub_matches.occurrences.get(name) {
+                    occurrences.insert(name.clone(), count);
+                }
+                if let Some(groups) = sub_matches.grouped_values.get(name) {
+                    grouped_values.insert(name.clone(), groups.clone());
+                }
+                present.insert(name.clone());
+            }
+        }
+        let mut deprecations = Vec::new();
+        let mut source_keys = HashMap::new();
+        for arg in known.iter().filter(|a| present.contains(&a.name)) {
+            if let Some(var) = arg.experimental.as_ref().filter(|_| !arg.is_enabled()) {
+                errors.push(ParseError::ExperimentalArgument {
+                    arg: arg.label(),
+                    var: var.clone(),
+                });
+            }
+            let Some(removed_in) = &arg.removed_in else {
+                continue;
+            };
+            let replacement = versioning::replacement_label(arg, known.iter().copied());
+            if arg.is_removed(version) {
+                errors.push(ParseError::RemovedArgument {
+                    arg: arg.label(),
+                    version: removed_in.clone(),
+                    replacement,
+                });
+            } else {
+                let mut message = format!(
+                    "{} is deprecated and will be removed in {}",
+                    arg.label(),
+                    removed_in
+                );
+                if let Some(replacement) = replacement {
+                    message.push_str(&format!("; use {} instead", replacement));
+                }
+                deprecations.push(message);
+            }
+        }
+        let mut sources: HashMap<String, ValueSource> = present
+            .iter()
+            .map(|name| (name.clone(), ValueSource::CommandLine))
+            .collect();
+        if collect_errors || errors.is_empty() {
+            let profile = values
+                .get(PROFILE)
+                .and_then(|name| self.profiles.iter().find(|p| &p.name == name))
+                .cloned();
+            let mut templated = Vec::new();
+            for arg in &known {
+                if present.contains(&arg.name) || arg.is_removed(version) {
+                    continue;
+                }
+                let var = arg.env_var(namespace);
+                if let Some((value, label, source)) = env_value(var.as_deref(), scope) {
+                    if arg.takes_value {
+                        let context = Context::new(
+                            &values,
+                            &multi_values,
+                            &flags,
+                            &scope.command_path,
+                            &scope.env_file_vars,
+                        );
+                        let accepted = arg.accept_values(&label, &value).and_then(|accepted| {
+                            arg.check_context(&label, &accepted, &context)
+                                .map(|_| accepted)
+                        });
+                        match accepted {
+                            Ok(accepted) => {
+                                store_values(&mut values, &mut multi_values, arg, accepted)
+                            }
+                            Err(err) => errors.push(err),
+                        }
+                    } else if let Some(set) = source::parse_bool(&value) {
+                        flags.insert(arg.name.clone(), set);
+                    } else {
+                        errors.push(ParseError::InvalidValue {
+                            arg: label,
+                            value: if arg.sensitive { REDACTED } else { &value }.to_string(),
+                            message: Some("expected a boolean".to_string()),
+                        });
+                    }
+                    sources.insert(arg.name.clone(), source);
+                    source_keys.insert(arg.name.clone(), var.unwrap_or_default());
+                    present.insert(arg.name.clone());
+                    continue;
+                }
+                if let Some((profile, value)) = profile
+                    .as_ref()
+                    .and_then(|p| p.value(&arg.name).map(|value| (p, value)))
+                {
+                    if arg.takes_value {
+                        let flag = format!("--{} {}", PROFILE, profile.name);
+                        let context = Context::new(
+                            &values,
+                            &multi_values,
+                            &flags,
+                            &scope.command_path,
+                            &scope.env_file_vars,
+                        );
+                        let accepted = arg.accept_values(&flag, value).and_then(|accepted| {
+                            arg.check_context(&flag, &accepted, &context)
+                                .map(|_| accepted)
+                        });
+                        match accepted {
+                            Ok(accepted) => {
+                                store_values(&mut values, &mut multi_values, arg, accepted)
+                            }
+                            Err(err) => errors.push(err),
+                        }
+                    } else {
+                        flags.insert(arg.name.clone(), value == "true");
+                    }
+                    sources.insert(arg.name.clone(), ValueSource::Profile(profile.name.clone()));
+                    present.insert(arg.name.clone());
+                    continue;
+                }
+                if let Some((config, key, path)) = config_file::config_value(arg, scope) {
+                    let label = format!("{} ({})", key, path.display());
+                    let listed = arg.multiple || arg.value_delimiter.is_some();
+                    let shown = if arg.sensitive {
+                        REDACTED.to_string()
+                    } else {
+                        config.join(",")
+                    };
+                    if config.len() > 1 && !(arg.takes_value && listed) {
+                        errors.push(ParseError::InvalidValue {
+                            arg: label,
+                            value: shown,
+                            message: Some("expected a single value".to_string()),
+                        });
+                    } else if arg.takes_value {
+                        let context = Context::new(
+                            &values,
+                            &multi_values,
+                            &flags,
+                            &scope.command_path,
+                            &scope.env_file_vars,
+                        );
+                        let mut accepted = Vec::new();
+                        for value in &config {
+                            match arg.accept_values(&label, value).and_then(|values| {
+                                arg.check_context(&label, &values, &context).map(|_| values)
+                            }) {
+                                Ok(values) => accepted.extend(values),
+                                Err(err) => errors.push(err),
+                            }
+                        }
+                        if arg.multiple && arg.value_delimiter.is_none() {
+                            multi_values.insert(arg.name.clone(), accepted.clone());
+                        }
+                        store_values(&mut values, &mut multi_values, arg, accepted);
+                    } else {
+                        match config.last().and_then(|value| source::parse_bool(value)) {
+                            Some(set) => {
+                                flags.insert(arg.name.clone(), set);
+                            }
+                            None => errors.push(ParseError::InvalidValue {
+                                arg: label,
+                                value: shown,
+                                message: Some("expected a boolean".to_string()),
+                            }),
+                        }
+                    }
+                    sources.insert(arg.name.clone(), ValueSource::ConfigFile(path));
+                    source_keys.insert(arg.name.clone(), key);
+                    present.insert(arg.name.clone());
+                    continue;
+                }
+                match arg.effective_default() {
+                    Some(default) if arg.takes_value && template::is_template(default, &known) => {
+                        templated.push((*arg, default));
+                    }
+                    Some(default) if arg.takes_value => {
+                        let split = match arg.value_delimiter {
+                            Some(delimiter) => {
+                                default.split(delimiter).map(str::to_string).collect()
+                            }
+                            None => vec![default.to_string()],
+                        };
+                        store_values(&mut values, &mut multi_values, arg, split);
+                        sources.insert(arg.name.clone(), ValueSource::Default);
+                    }
+                    Some(default) => {
+                        flags.insert(arg.name.clone(), default == "true");
+                        sources.insert(arg.name.clone(), ValueSource::Default);
+                    }
+                    None if arg.is_required(version) => errors.push(ParseError::MissingRequired {
+                        name: arg.display().to_string(),
+                    }),
+                    None => deprecations.extend(arg.soft_required_warning()),
+                }
+            }
+            for arg in known.iter().filter(|a| !a.takes_value) {
+                let count = occurrences.get(&arg.name).copied().unwrap_or(0);
+                if let Some(level) = arg.level_for(count) {
+                    values.insert(arg.name.clone(), level.to_string());
+                    sources
+                        .entry(arg.name.clone())
+                        .or_insert(ValueSource::Default);
+                }
+            }
+            loop {
+                let pending = templated.len();
+                let mut i = 0;
+                while i < templated.len() {
+                    let (arg, template) = templated[i];
+                    match template::expand(template, &known, &values, &flags) {
+                        Some(value) => {
+                            values.insert(arg.name.clone(), value);
+                            sources.insert(arg.name.clone(), ValueSource::Default);
+                            templated.remove(i);
+                        }
+                        None => i += 1,
+                    }
+                }
+                if templated.len() == pending {
+                    break;
+                }
+            }
+            for (arg, _) in &templated {
+                if arg.is_required(version) {
+                    errors.push(ParseError::MissingRequired {
+                        name: arg.display().to_string(),
+                    });
                 } else {
-                    panic!("Missing required argument: {}", arg.name);
+                    deprecations.extend(arg.soft_required_warning());
                 }
             }
+            let display = |member: &str| {
+                known
+                    .iter()
+                    .find(|a| a.name == member)
+                    .map_or(member, |a| a.display())
+                    .to_string()
+            };
+            let holds = |other: &str, value: &str| {
+                values.get(other).is_some_and(|v| v == value)
+                    || multi_values
+                        .get(other)
+                        .is_some_and(|all| all.iter().any(|v| v == value))
+                    || flags.get(other).is_some_and(|f| f.to_string() == value)
+            };
+            errors.extend(group::check_relations(
+                &known,
+                &self.groups,
+                &present,
+                holds,
+            ));
+            errors.extend(group::check_groups(&self.groups, &present, display));
         }
+        let sub_matches = match sub_result {
+            Some((name, Ok(sub_matches))) => Some((name.to_string(), sub_matches)),
+            Some((_, Err(err))) => {
+                errors.extend(err.into_errors());
+                None
+            }
+            None => None,
+        };
+        if !collect_errors {
+            errors.truncate(1);
+        }
+        ParseError::from_errors(errors)?;
 
-        ArgMatches {
+        let mut matches = ArgMatches {
             values,
             flags,
             positionals,
+            sections,
+            sensitive: known
+                .iter()
+                .filter(|a| a.sensitive)
+                .map(|a| a.name.clone())
+                .collect(),
+            sources: sources.into_iter().collect(),
+            argv_indices: argv_indices.into_iter().collect(),
+            source_keys: source_keys.into_iter().collect(),
+            deprecations,
+            key_values: key_values.into_iter().collect(),
+            multi_values: multi_values.into_iter().collect(),
+            occurrences: occurrences.into_iter().collect(),
+            grouped_values: grouped_values.into_iter().collect(),
+            os_values: Default::default(),
+            subcommand: None,
+        };
+        if let Some((name, mut sub_matches)) = sub_matches {
+            for arg in known.iter().filter(|a| a.global) {
+                sub_matches.inherit(&matches, &arg.name);
+            }
+            matches.subcommand = Some((name, Box::new(sub_matches)));
+        }
+        let mut errors = Vec::new();
+        for validator in &self.matches_validators {
+            if let Err(message) = validator(&matches) {
+                errors.push(ParseError::ValidationFailed { message });
+                if !collect_errors {
+                    break;
+                }
+            }
+        }
+        ParseError::from_errors(errors)?;
+        Ok(matches)
+    }
+}
+
+#[derive(Default, Clone)]
+struct Scope {
+    collect_errors: bool,
+    command_path: Vec<String>,
+    globals: Vec<Arg>,
+    rejected_globals: Vec<Arg>,
+    env_file_vars: HashMap<String, (String, std::path::PathBuf)>,
+    config_files: Vec<Rc<config_file::ConfigFile>>,
+    version: Option<String>,
+    namespace: Option<String>,
+    accept_removed_args: bool,
+    argv_offset: usize,
+    program: String,
+    trace: Option<Rc<RefCell<Vec<ExplainStep>>>>,
+}
+
+fn store_values(
+    values: &mut HashMap<String, String>,
+    multi_values: &mut HashMap<String, Vec<String>>,
+    arg: &Arg,
+    accepted: Vec<String>,
+) {
+    if let Some(last) = accepted.last() {
+        values.insert(arg.name.clone(), last.clone());
+    }
+    if arg.value_delimiter.is_some() {
+        multi_values.insert(arg.name.clone(), accepted);
+    }
+}
+
+fn env_segment(name: &str) -> String {
+    name.to_uppercase().replace('-', "_")
+}
+
+fn env_value(var: Option<&str>, scope: &Scope) -> Option<(String, String, ValueSource)> {
+    let var = var?;
+    if let Ok(value) = std::env::var(var) {
+        return Some((
+            value,
+            format!("${}", var),
+            ValueSource::Env(var.to_string()),
+        ));
+    }
+    let (value, path) = scope.env_file_vars.get(var)?;
+    Some((
+        value.clone(),
+        format!("${} ({})", var, path.display()),
+        ValueSource::EnvFile(path.clone()),
+    ))
+}
+
+#[derive(Default)]
+struct ParseState {
+    values: HashMap<String, String>,
+    flags: HashMap<String, bool>,
+    positionals: Vec<String>,
+    sections: Vec<Vec<String>>,
+    present: HashSet<String>,
+    errors: Vec<ParseError>,
+    index: usize,
+    argv_indices: HashMap<String, usize>,
+    key_values: HashMap<String, Vec<(String, Option<String>)>>,
+    multi_values: HashMap<String, Vec<String>>,
+    occurrences: HashMap<String, usize>,
+    grouped_values: HashMap<String, Vec<Vec<String>>>,
+    extending: bool,
+    tracing: bool,
+    steps: Vec<ExplainStep>,
+}
+
+impl ParseState {
+    fn record(&mut self, step: ExplainStep) {
+        if self.tracing {
+            self.steps.push(step);
+        }
+    }
+
+    fn push_extra(&mut self, token: &str) {
+        self.record(ExplainStep::Extra {
+            index: self.index,
+            token: token.to_string(),
+        });
+        self.positionals.push(token.to_string());
+    }
+
+    fn check_duplicate(&mut self, arg: &Arg, flag: &str) -> bool {
+        *self.occurrences.entry(arg.name.clone()).or_default() += 1;
+        if arg.multiple || !self.present.contains(&arg.name) {
+            return true;
+        }
+        match arg.on_duplicate {
+            DuplicatePolicy::LastWins => true,
+            DuplicatePolicy::FirstWins => false,
+            DuplicatePolicy::Error => {
+                self.errors.push(ParseError::DuplicateArgument {
+                    arg: flag.to_string(),
+                });
+                false
+            }
+        }
+    }
+
+    fn set_value(&mut self, scope: &Scope, arg: &Arg, flag: &str, raw: &str) {
+        #[cfg(feature = "glob")]
+        if let Some(paths) = glob::expand(raw).filter(|_| arg.positional && arg.glob_expand) {
+            if paths.is_empty() {
+                self.errors.push(ParseError::NoGlobMatch {
+                    arg: flag.to_string(),
+                    pattern: raw.to_string(),
+                });
+            }
+            for path in paths {
+                self.set_literal_value(scope, arg, flag, &path);
+            }
+            return;
+        }
+        self.set_literal_value(scope, arg, flag, raw);
+    }
+
+    fn set_literal_value(&mut self, scope: &Scope, arg: &Arg, flag: &str, raw: &str) {
+        self.extending = false;
+        self.store_literal(scope, arg, flag, raw, false);
+    }
+
+    fn extend_occurrence(&mut self, scope: &Scope, arg: &Arg, flag: &str, raw: &str) {
+        self.store_literal(scope, arg, flag, raw, true);
+    }
+
+    fn store_literal(&mut self, scope: &Scope, arg: &Arg, flag: &str, raw: &str, extend: bool) {
+        let shown = if arg.sensitive { REDACTED } else { raw }.to_string();
+        self.record(if arg.positional {
+            ExplainStep::Positional {
+                index: self.index,
+                arg: arg.name.clone(),
+                value: shown,
+            }
+        } else {
+            ExplainStep::Value {
+                index: self.index,
+                token: flag.to_string(),
+                arg: arg.name.clone(),
+                value: shown,
+            }
+        });
+        if arg.key_value {
+            match arg.accept_key_value(flag, raw) {
+                Ok(entry) => {
+                    self.key_values
+                        .entry(arg.name.clone())
+                        .or_default()
+                        .push(entry);
+                    self.argv_indices.insert(arg.name.clone(), self.index);
+                    self.present.insert(arg.name.clone());
+                }
+                Err(err) => self.errors.push(err),
+            }
+            return;
+        }
+        let context = Context::new(
+            &self.values,
+            &self.multi_values,
+            &self.flags,
+            &scope.command_path,
+            &scope.env_file_vars,
+        );
+        let accepted = match arg.accept_values(flag, raw).and_then(|accepted| {
+            arg.check_context(flag, &accepted, &context)
+                .map(|_| accepted)
+        }) {
+            Ok(accepted) => accepted,
+            Err(err) => {
+                self.errors.push(err);
+                return;
+            }
+        };
+        if extend && !self.extending {
+            return;
+        }
+        if extend || self.check_duplicate(arg, flag) {
+            self.extending = true;
+            let last = accepted.last().cloned().unwrap_or_default();
+            if arg.multiple || arg.value_delimiter.is_some() || arg.num_values > 1 {
+                let groups = self.grouped_values.entry(arg.name.clone()).or_default();
+                match groups.last_mut() {
+                    Some(group) if extend => group.extend(accepted.iter().cloned()),
+                    _ if arg.multiple => groups.push(accepted.clone()),
+                    _ => *groups = vec![accepted.clone()],
+                }
+                let values = self.multi_values.entry(arg.name.clone()).or_default();
+                if !arg.multiple && !extend {
+                    values.clear();
+                }
+                values.extend(accepted);
+            }
+            self.values.insert(arg.name.clone(), last);
+            self.argv_indices.insert(arg.name.clone(), self.index);
+        }
+        self.present.insert(arg.name.clone());
+    }
+
+    fn take_more_values<'a, I>(
+        &mut self,
+        scope: &Scope,
+        arg: &Arg,
+        flag: &str,
+        iter: &mut Peekable<I>,
+    ) where
+        I: Iterator<Item = (usize, &'a String)>,
+    {
+        for taken in 1..arg.num_values {
+            let next = iter.next_if(|(_, token)| {
+                !token.starts_with('-') || *token == "-" || token.parse::<f64>().is_ok()
+            });
+            let Some((index, value)) = next else {
+                self.errors.push(ParseError::WrongValueCount {
+                    arg: flag.to_string(),
+                    expected: arg.num_values,
+                    actual: taken,
+                });
+                return;
+            };
+            self.index = scope.argv_offset + index;
+            self.extend_occurrence(scope, arg, flag, value);
+        }
+    }
+
+    fn set_flag(&mut self, arg: &Arg, flag: &str, set: bool) {
+        self.record(ExplainStep::Flag {
+            index: self.index,
+            token: flag.to_string(),
+            arg: arg.name.clone(),
+        });
+        if self.check_duplicate(arg, flag) {
+            self.flags.insert(arg.name.clone(), set);
+            self.argv_indices.insert(arg.name.clone(), self.index);
         }
+        self.present.insert(arg.name.clone());
     }
 }
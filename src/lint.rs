@@ -0,0 +1,173 @@
+use std::fmt;
+
+use crate::{Arg, ArgParser};
+
+const VALUE_WORDS: &[&str] = &[
+    "address",
+    "config",
+    "count",
+    "dir",
+    "directory",
+    "email",
+    "file",
+    "format",
+    "host",
+    "input",
+    "level",
+    "limit",
+    "name",
+    "output",
+    "path",
+    "port",
+    "size",
+    "target",
+    "timeout",
+    "url",
+    "user",
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LintRule {
+    MissingHelp,
+    CaseCollidingShorts,
+    MissingAbout,
+    FlagLooksLikeOption,
+}
+
+impl LintRule {
+    pub const ALL: [LintRule; 4] = [
+        LintRule::MissingHelp,
+        LintRule::CaseCollidingShorts,
+        LintRule::MissingAbout,
+        LintRule::FlagLooksLikeOption,
+    ];
+}
+
+impl fmt::Display for LintRule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            LintRule::MissingHelp => "missing-help",
+            LintRule::CaseCollidingShorts => "case-colliding-shorts",
+            LintRule::MissingAbout => "missing-about",
+            LintRule::FlagLooksLikeOption => "flag-looks-like-option",
+        })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LintFinding {
+    pub rule: LintRule,
+    pub command: Vec<String>,
+    pub message: String,
+}
+
+impl fmt::Display for LintFinding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.command.is_empty() {
+            write!(f, "{}: ", self.command.join(" "))?;
+        }
+        write!(f, "{} [{}]", self.message, self.rule)
+    }
+}
+
+impl ArgParser {
+    pub fn lint(&self) -> Vec<LintFinding> {
+        self.lint_with(&LintRule::ALL)
+    }
+
+    pub fn lint_with(&self, rules: &[LintRule]) -> Vec<LintFinding> {
+        let mut findings = Vec::new();
+        self.lint_level(rules, &mut Vec::new(), &[], None, &mut findings);
+        findings
+    }
+
+    fn lint_level<'a>(
+        &'a self,
+        rules: &[LintRule],
+        command: &mut Vec<String>,
+        globals: &[&'a Arg],
+        version: Option<&str>,
+        findings: &mut Vec<LintFinding>,
+    ) {
+        let version = self.version.as_deref().or(version);
+        let mut report = |rule: LintRule, message: String| {
+            if rules.contains(&rule) {
+                findings.push(LintFinding {
+                    rule,
+                    command: command.clone(),
+                    message,
+                });
+            }
+        };
+        for arg in self.args.iter().filter(|a| !a.is_hidden()) {
+            if arg.help.is_none() {
+                report(
+                    LintRule::MissingHelp,
+                    format!("{} has no help text", arg.label()),
+                );
+            }
+            if !arg.takes_value && looks_like_option(arg) {
+                report(
+                    LintRule::FlagLooksLikeOption,
+                    format!(
+                        "{} is a flag but looks like it should take a value",
+                        arg.label()
+                    ),
+                );
+            }
+        }
+        let known: Vec<&Arg> = self.args.iter().chain(globals.iter().copied()).collect();
+        let own = self.args.iter().map(|a| (a, true));
+        let mut shorts: Vec<(char, String, bool)> = own
+            .chain(globals.iter().map(|a| (*a, false)))
+            .filter_map(|(a, own)| Some((a.short?, a.label(), own)))
+            .collect();
+        if self.wants_help("-h", &known) {
+            shorts.push(('h', "-h (help)".to_string(), command.is_empty()));
+        }
+        if self.wants_version("-V", &known, version) {
+            shorts.push(('V', "-V (version)".to_string(), command.is_empty()));
+        }
+        shorts.sort();
+        for (i, (short, label, own)) in shorts.iter().enumerate() {
+            for (other, other_label, other_own) in &shorts[i + 1..] {
+                if (*own || *other_own)
+                    && short != other
+                    && short.to_lowercase().eq(other.to_lowercase())
+                {
+                    report(
+                        LintRule::CaseCollidingShorts,
+                        format!("{} and {} differ only in case", label, other_label),
+                    );
+                }
+            }
+        }
+        if !command.is_empty() && self.about.is_none() {
+            report(
+                LintRule::MissingAbout,
+                "subcommand has no about text".to_string(),
+            );
+        }
+        let inherited: Vec<&Arg> = known.into_iter().filter(|a| a.global).collect();
+        let mut names: Vec<&String> = self.subcommands.keys().collect();
+        names.sort();
+        for name in names {
+            command.push(name.clone());
+            self.subcommands[name].lint_level(rules, command, &inherited, version, findings);
+            command.pop();
+        }
+    }
+}
+
+fn looks_like_option(arg: &Arg) -> bool {
+    let name = arg.long.as_deref().unwrap_or(&arg.name);
+    let last_word = name.rsplit(['-', '_']).next().unwrap_or(name);
+    !arg.choices.is_empty()
+        || arg.range.is_some()
+        || arg.value_delimiter.is_some()
+        || arg
+            .default
+            .as_deref()
+            .is_some_and(|d| d != "true" && d != "false")
+        || VALUE_WORDS.contains(&last_word.to_lowercase().as_str())
+}
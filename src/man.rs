@@ -0,0 +1,233 @@
+use std::io::{self, Write};
+
+use crate::help::{option_left, relation_tags, tags, usage_name};
+use crate::{Arg, ArgParser, Scope};
+
+impl ArgParser {
+    pub fn render_man<W: Write>(&self, out: &mut W) -> io::Result<()> {
+        let scope = Scope {
+            program: self.program(&[]),
+            ..Scope::default()
+        };
+        let name = usage_name(&scope);
+        let version = self.version.as_deref();
+        let title = format!("{} {}", name, version.unwrap_or_default());
+        writeln!(
+            out,
+            ".TH \"{}\" 1 \"\" \"{}\" \"User Commands\"",
+            roff(&name.to_uppercase()),
+            roff(title.trim())
+        )?;
+        writeln!(out, ".SH NAME")?;
+        match &self.about {
+            Some(about) => writeln!(out, "{} \\- {}", roff(&name), roff(about))?,
+            None => writeln!(out, "{}", roff(&name))?,
+        }
+        writeln!(out, ".SH SYNOPSIS")?;
+        writeln!(out, "{}", self.man_synopsis(&name))?;
+        self.man_args(out, &[], version, self.namespace.as_deref())?;
+        if !self.subcommands.is_empty() {
+            writeln!(out, ".SH COMMANDS")?;
+            self.man_commands(out, &name, version, self.namespace.as_deref())?;
+        }
+        let mut examples: Vec<String> = self
+            .usage_examples
+            .iter()
+            .map(|example| format!("{} {}", name, example))
+            .collect();
+        self.man_arg_examples(&name, version, &mut examples);
+        if !examples.is_empty() {
+            writeln!(out, ".SH EXAMPLES")?;
+            for example in examples {
+                writeln!(out, ".PP\n\\fB{}\\fR", roff(&example))?;
+            }
+        }
+        if let Some(version) = version {
+            writeln!(out, ".SH VERSION\nv{}", roff(version))?;
+        }
+        if let Some(author) = &self.author {
+            writeln!(out, ".SH AUTHOR\n{}", roff(author))?;
+        }
+        Ok(())
+    }
+
+    fn man_arg_examples(&self, path: &str, version: Option<&str>, examples: &mut Vec<String>) {
+        let visible = self
+            .args
+            .iter()
+            .filter(|a| !a.is_hidden() && !a.is_removed(version));
+        for arg in visible {
+            examples.extend(arg.examples.iter().map(|e| format!("{} {}", path, e)));
+        }
+        let mut names: Vec<&String> = self.subcommands.keys().collect();
+        names.sort();
+        for name in names {
+            let sub = &self.subcommands[name];
+            let version = sub.version.as_deref().or(version);
+            sub.man_arg_examples(&format!("{} {}", path, name), version, examples);
+        }
+    }
+
+    fn man_synopsis(&self, name: &str) -> String {
+        let mut synopsis = format!("\\fB{}\\fR [\\fIOPTIONS\\fR]", roff(name));
+        for arg in self.args.iter().filter(|a| a.positional && !a.is_hidden()) {
+            let repeat = if arg.multiple { "..." } else { "" };
+            let (open, close) = if arg.is_required(self.version.as_deref()) {
+                ("<", ">")
+            } else {
+                ("[", "]")
+            };
+            synopsis.push_str(&format!(
+                " {}\\fI{}\\fR{}{}",
+                open,
+                roff(arg.display()),
+                close,
+                repeat
+            ));
+        }
+        if !self.subcommands.is_empty() {
+            synopsis.push_str(" [\\fICOMMAND\\fR]");
+        }
+        if self.dash_sections {
+            synopsis.push_str(" [\\-\\- \\fIargs\\fR...]...");
+        }
+        synopsis
+    }
+
+    fn man_args<W: Write>(
+        &self,
+        out: &mut W,
+        heading: &[&str],
+        version: Option<&str>,
+        namespace: Option<&str>,
+    ) -> io::Result<()> {
+        let known: Vec<&Arg> = self.args.iter().collect();
+        let visible: Vec<&Arg> = self
+            .args
+            .iter()
+            .filter(|a| !a.is_hidden() && !a.is_removed(version))
+            .collect();
+        let (positionals, options): (Vec<&Arg>, Vec<&Arg>) =
+            visible.into_iter().partition(|a| a.positional);
+        let section = |title: &str| match heading {
+            [] => format!(".SH {}", title),
+            _ => format!(".SS \"{} {}\"", heading.join(" "), title.to_lowercase()),
+        };
+        let entry = |arg: &Arg| {
+            let relations = relation_tags(arg, &known, &self.groups);
+            let text = tags(arg, version, arg.env_var(namespace), relations);
+            if text.is_empty() {
+                String::new()
+            } else {
+                format!("\n{}", roff(&text))
+            }
+        };
+        if !positionals.is_empty() {
+            writeln!(out, "{}", section("ARGUMENTS"))?;
+            for arg in positionals {
+                let repeat = if arg.multiple { "..." } else { "" };
+                writeln!(
+                    out,
+                    ".TP\n\\fI<{}>\\fR{}{}",
+                    roff(arg.display()),
+                    repeat,
+                    entry(arg)
+                )?;
+            }
+        }
+        writeln!(out, "{}", section("OPTIONS"))?;
+        for arg in options {
+            writeln!(out, ".TP\n{}{}", man_flags(&option_left(arg)), entry(arg))?;
+        }
+        if self.wants_help("--help", &known) {
+            let left = if self.wants_help("-h", &known) {
+                "-h, --help"
+            } else {
+                "--help"
+            };
+            writeln!(out, ".TP\n{}\nPrint help", man_flags(left))?;
+        }
+        if self.wants_version("--version", &known, version) {
+            let left = if self.wants_version("-V", &known, version) {
+                "-V, --version"
+            } else {
+                "--version"
+            };
+            writeln!(out, ".TP\n{}\nPrint version", man_flags(left))?;
+        }
+        Ok(())
+    }
+
+    fn man_commands<W: Write>(
+        &self,
+        out: &mut W,
+        path: &str,
+        version: Option<&str>,
+        namespace: Option<&str>,
+    ) -> io::Result<()> {
+        let mut names: Vec<&String> = self.subcommands.keys().collect();
+        names.sort();
+        for name in names {
+            let sub = &self.subcommands[name];
+            let version = sub.version.as_deref().or(version);
+            let namespace = sub.namespace.as_deref().or(namespace);
+            let full = format!("{} {}", path, name);
+            writeln!(out, ".SS \"{}\"", roff(&full))?;
+            if let Some(about) = &sub.about {
+                writeln!(out, "{}", roff(about))?;
+            }
+            writeln!(out, ".PP\n{}", sub.man_synopsis(&full))?;
+            sub.man_args(out, &[&full], version, namespace)?;
+            sub.man_commands(out, &full, version, namespace)?;
+        }
+        Ok(())
+    }
+}
+
+fn man_flags(left: &str) -> String {
+    left.split_whitespace()
+        .map(|word| {
+            let (word, comma) = match word.strip_suffix(',') {
+                Some(word) => (word, ","),
+                None => (word, ""),
+            };
+            let font = if word.starts_with('<') { 'I' } else { 'B' };
+            format!("\\f{}{}\\fR{}", font, roff(word), comma)
+        })
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn roff(text: &str) -> String {
+    text.lines()
+        .map(|line| {
+            let line = line.replace('\\', "\\e").replace('-', "\\-");
+            if line.starts_with(['.', '\'']) {
+                format!("\\&{}", line)
+            } else {
+                line
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn roff_escapes_dashes_backslashes_and_control_lines() {
+        assert_eq!(roff("--verbose"), "\\-\\-verbose");
+        assert_eq!(roff("C:\\dir"), "C:\\edir");
+        assert_eq!(roff(".hidden\n'quoted"), "\\&.hidden\n\\&'quoted");
+    }
+
+    #[test]
+    fn flags_are_bold_and_placeholders_italic() {
+        assert_eq!(
+            man_flags("-o, --output <FILE>"),
+            "\\fB\\-o\\fR, \\fB\\-\\-output\\fR \\fI<FILE>\\fR"
+        );
+    }
+}
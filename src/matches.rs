@@ -0,0 +1,201 @@
+use std::collections::{HashMap, HashSet};
+use std::ffi::OsString;
+use std::fmt;
+use std::sync::OnceLock;
+
+use crate::small_map::SmallMap;
+use crate::ValueSource;
+
+pub const REDACTED: &str = "<redacted>";
+
+static CURRENT: OnceLock<ArgMatches> = OnceLock::new();
+
+pub struct ArgMatches {
+    pub values: HashMap<String, String>,
+    pub flags: HashMap<String, bool>,
+    pub positionals: Vec<String>,
+    pub(crate) sections: Vec<Vec<String>>,
+    pub(crate) sensitive: HashSet<String>,
+    pub(crate) sources: SmallMap<ValueSource>,
+    pub(crate) argv_indices: SmallMap<usize>,
+    pub(crate) source_keys: SmallMap<String>,
+    pub(crate) deprecations: Vec<String>,
+    pub(crate) key_values: SmallMap<Vec<(String, Option<String>)>>,
+    pub(crate) multi_values: SmallMap<Vec<String>>,
+    pub(crate) occurrences: SmallMap<usize>,
+    pub(crate) grouped_values: SmallMap<Vec<Vec<String>>>,
+    pub(crate) os_values: SmallMap<Vec<OsString>>,
+    pub(crate) subcommand: Option<(String, Box<ArgMatches>)>,
+}
+
+impl ArgMatches {
+    pub fn install_global(self) -> Result<&'static ArgMatches, Box<ArgMatches>> {
+        CURRENT.set(self).map_err(Box::new)?;
+        Ok(CURRENT.get().unwrap())
+    }
+
+    pub fn current() -> Option<&'static ArgMatches> {
+        CURRENT.get()
+    }
+
+    pub fn is_sensitive(&self, name: &str) -> bool {
+        self.sensitive.contains(name)
+    }
+
+    pub fn deprecations(&self) -> &[String] {
+        &self.deprecations
+    }
+
+    pub fn get_many(&self, name: &str) -> Vec<String> {
+        match self.multi_values.get(name) {
+            Some(values) => values.clone(),
+            None => self.values.get(name).cloned().into_iter().collect(),
+        }
+    }
+
+    pub fn get_occurrences(&self, name: &str) -> Vec<Vec<String>> {
+        if let Some(groups) = self.grouped_values.get(name) {
+            return groups.clone();
+        }
+        let values = self.get_many(name);
+        if values.is_empty() {
+            Vec::new()
+        } else {
+            vec![values]
+        }
+    }
+
+    pub fn occurrences_of(&self, name: &str) -> usize {
+        self.occurrences.get(name).copied().unwrap_or(0)
+    }
+
+    pub fn get_count(&self, name: &str) -> usize {
+        self.occurrences_of(name)
+    }
+
+    pub fn sections(&self) -> &[Vec<String>] {
+        &self.sections
+    }
+
+    pub fn subcommand(&self) -> Option<(&str, &ArgMatches)> {
+        self.subcommand
+            .as_ref()
+            .map(|(name, matches)| (name.as_str(), matches.as_ref()))
+    }
+
+    pub fn subcommand_name(&self) -> Option<&str> {
+        self.subcommand().map(|(name, _)| name)
+    }
+
+    pub(crate) fn innermost(&self) -> &ArgMatches {
+        match self.subcommand() {
+            Some((_, sub)) => sub.innermost(),
+            None => self,
+        }
+    }
+
+    pub(crate) fn inherit(&mut self, parent: &ArgMatches, name: &str) {
+        if parent.sources.get(name) != Some(&ValueSource::CommandLine)
+            || self.sources.get(name) == Some(&ValueSource::CommandLine)
+        {
+            return;
+        }
+        inherit_entry(name, &parent.values, &mut self.values);
+        inherit_entry(name, &parent.flags, &mut self.flags);
+        self.sources.copy_entry(name, &parent.sources);
+        self.argv_indices.copy_entry(name, &parent.argv_indices);
+        self.source_keys.copy_entry(name, &parent.source_keys);
+        self.key_values.copy_entry(name, &parent.key_values);
+        self.multi_values.copy_entry(name, &parent.multi_values);
+        self.occurrences.copy_entry(name, &parent.occurrences);
+        self.grouped_values.copy_entry(name, &parent.grouped_values);
+        self.os_values.copy_entry(name, &parent.os_values);
+        if let Some((sub_name, mut sub)) = self.subcommand.take() {
+            sub.inherit(self, name);
+            self.subcommand = Some((sub_name, sub));
+        }
+    }
+
+    pub fn shrink_to_fit(&mut self) {
+        self.values.shrink_to_fit();
+        self.flags.shrink_to_fit();
+        self.positionals.shrink_to_fit();
+        self.sections.shrink_to_fit();
+        self.sensitive.shrink_to_fit();
+        self.sources.shrink_to_fit();
+        self.argv_indices.shrink_to_fit();
+        self.source_keys.shrink_to_fit();
+        self.deprecations.shrink_to_fit();
+        self.key_values.shrink_to_fit();
+        self.multi_values.shrink_to_fit();
+        self.occurrences.shrink_to_fit();
+        self.grouped_values.shrink_to_fit();
+        self.os_values.shrink_to_fit();
+        if let Some((_, sub)) = &mut self.subcommand {
+            sub.shrink_to_fit();
+        }
+    }
+
+    pub fn redacted_value(&self, name: &str) -> Option<&str> {
+        let value = self.values.get(name)?;
+        if self.is_sensitive(name) {
+            Some(REDACTED)
+        } else {
+            Some(value)
+        }
+    }
+
+    fn redacted_values(&self) -> Vec<(&str, &str)> {
+        let mut values: Vec<(&str, &str)> = self
+            .values
+            .keys()
+            .map(|name| (name.as_str(), self.redacted_value(name).unwrap()))
+            .collect();
+        values.sort();
+        values
+    }
+}
+
+fn inherit_entry<T: Clone>(name: &str, from: &HashMap<String, T>, to: &mut HashMap<String, T>) {
+    match from.get(name) {
+        Some(value) => to.insert(name.to_string(), value.clone()),
+        None => to.remove(name),
+    };
+}
+
+impl fmt::Debug for ArgMatches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let values: HashMap<&str, &str> = self.redacted_values().into_iter().collect();
+        f.debug_struct("ArgMatches")
+            .field("values", &values)
+            .field("flags", &self.flags)
+            .field("positionals", &self.positionals)
+            .field("subcommand", &self.subcommand)
+            .finish()
+    }
+}
+
+impl fmt::Display for ArgMatches {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut lines = Vec::new();
+        for (name, value) in self.redacted_values() {
+            lines.push(format!("{}={}", name, value));
+        }
+        let mut flags: Vec<&String> = self
+            .flags
+            .iter()
+            .filter(|(_, set)| **set)
+            .map(|(name, _)| name)
+            .collect();
+        flags.sort();
+        lines.extend(flags.into_iter().cloned());
+        for positional in &self.positionals {
+            lines.push(positional.clone());
+        }
+        if let Some((name, sub)) = &self.subcommand {
+            lines.push(name.clone());
+            lines.extend(Some(sub.to_string()).filter(|s| !s.is_empty()));
+        }
+        f.write_str(&lines.join(" "))
+    }
+}
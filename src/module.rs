@@ -0,0 +1,93 @@
+use crate::{Arg, ArgParser, SpecError, PROFILE};
+
+pub trait CliModule {
+    fn name(&self) -> &str;
+
+    fn register(&self, parser: &mut ArgParser);
+}
+
+impl ArgParser {
+    pub fn update<F>(&mut self, f: F)
+    where
+        F: FnOnce(ArgParser) -> ArgParser,
+    {
+        *self = f(std::mem::take(self));
+    }
+
+    pub fn register_module<M: CliModule>(mut self, module: &M) -> Result<Self, SpecError> {
+        let mut part = ArgParser::new();
+        module.register(&mut part);
+        let conflict = |conflict: String| SpecError::ModuleConflict {
+            module: module.name().to_string(),
+            conflict,
+        };
+        for arg in part.args {
+            if arg.name == PROFILE {
+                if let Some(existing) = self.args.iter_mut().find(|a| a.name == PROFILE) {
+                    existing.choices.extend(arg.choices);
+                    continue;
+                }
+            }
+            if let Some(message) = self.args.iter().find_map(|a| arg_conflict(a, &arg)) {
+                return Err(conflict(message));
+            }
+            self.args.push(arg);
+        }
+        for (name, sub) in part.subcommands {
+            let names = std::iter::once(&name).chain(sub.aliases.iter().map(|a| &a.name));
+            for word in names {
+                if self.find_subcommand(word).is_some() {
+                    return Err(conflict(format!("subcommand '{}'", word)));
+                }
+            }
+            self.subcommands.insert(name, sub);
+        }
+        for group in part.groups {
+            if self.groups.iter().any(|g| g.name == group.name) {
+                return Err(conflict(format!("group '{}'", group.name)));
+            }
+            self.groups.push(group);
+        }
+        for shortcut in part.shortcuts {
+            if self.shortcuts.iter().any(|s| s.name == shortcut.name) {
+                return Err(conflict(format!("shortcut '{}'", shortcut.name)));
+            }
+            self.shortcuts.push(shortcut);
+        }
+        for abbreviation in part.abbreviations {
+            if self
+                .abbreviations
+                .iter()
+                .any(|a| a.token == abbreviation.token)
+            {
+                return Err(conflict(format!("abbreviation '{}'", abbreviation.token)));
+            }
+            self.abbreviations.push(abbreviation);
+        }
+        for profile in part.profiles {
+            if self.profiles.iter().any(|p| p.name == profile.name) {
+                return Err(conflict(format!("profile '{}'", profile.name)));
+            }
+            self.profiles.push(profile);
+        }
+        self.disallowed_globals.extend(part.disallowed_globals);
+        self.matches_validators.extend(part.matches_validators);
+        self.preprocessors.extend(part.preprocessors);
+        self.usage_examples.extend(part.usage_examples);
+        Ok(self)
+    }
+}
+
+fn arg_conflict(existing: &Arg, arg: &Arg) -> Option<String> {
+    if existing.name == arg.name {
+        return Some(format!("argument '{}'", arg.name));
+    }
+    if let Some(short) = arg.short.filter(|short| existing.short == Some(*short)) {
+        return Some(format!("flag -{}", short));
+    }
+    let longs = arg.long.iter().chain(arg.aliases.iter().map(|a| &a.name));
+    longs
+        .into_iter()
+        .find(|long| existing.matches_long(long))
+        .map(|long| format!("flag --{}", long))
+}
@@ -0,0 +1,224 @@
+use std::ffi::{OsStr, OsString};
+use std::path::PathBuf;
+
+use crate::{ArgMatches, ArgParser, ParseError};
+
+const ESCAPE_BASE: u32 = 0x10F800;
+
+impl ArgParser {
+    pub fn parse_env(&self) -> ArgMatches {
+        self.parse_os(&std::env::args_os().collect::<Vec<_>>())
+    }
+
+    pub fn try_parse_env(&self) -> Result<ArgMatches, ParseError> {
+        self.try_parse_os(&std::env::args_os().collect::<Vec<_>>())
+    }
+
+    pub fn parse_os(&self, args: &[OsString]) -> ArgMatches {
+        let mut matches = self.parse(&escape_all(args));
+        restore(&mut matches);
+        matches
+    }
+
+    pub fn try_parse_os(&self, args: &[OsString]) -> Result<ArgMatches, ParseError> {
+        let mut matches = self.try_parse(&escape_all(args))?;
+        restore(&mut matches);
+        Ok(matches)
+    }
+}
+
+impl ArgMatches {
+    pub fn get_os(&self, name: &str) -> Option<OsString> {
+        match self.os_values.get(name) {
+            Some(values) => values.last().cloned(),
+            None => self.values.get(name).map(OsString::from),
+        }
+    }
+
+    pub fn get_many_os(&self, name: &str) -> Vec<OsString> {
+        match self.os_values.get(name) {
+            Some(values) => values.clone(),
+            None => self
+                .get_many(name)
+                .into_iter()
+                .map(OsString::from)
+                .collect(),
+        }
+    }
+
+    pub fn get_path(&self, name: &str) -> Option<PathBuf> {
+        self.get_os(name).map(PathBuf::from)
+    }
+}
+
+fn escape_all(args: &[OsString]) -> Vec<String> {
+    args.iter().map(|arg| escape(arg)).collect()
+}
+
+#[cfg(unix)]
+fn escape(arg: &OsStr) -> String {
+    use std::os::unix::ffi::OsStrExt;
+
+    let mut out = String::new();
+    for chunk in arg.as_bytes().utf8_chunks() {
+        out.push_str(chunk.valid());
+        out.extend(chunk.invalid().iter().map(|&b| escaped(b as u32)));
+    }
+    out
+}
+
+#[cfg(windows)]
+fn escape(arg: &OsStr) -> String {
+    use std::os::windows::ffi::OsStrExt;
+
+    char::decode_utf16(arg.encode_wide())
+        .map(|c| c.unwrap_or_else(|err| escaped(err.unpaired_surrogate() as u32 - 0xD800)))
+        .collect()
+}
+
+#[cfg(not(any(unix, windows)))]
+fn escape(arg: &OsStr) -> String {
+    arg.to_string_lossy().into_owned()
+}
+
+fn escaped(unit: u32) -> char {
+    char::from_u32(ESCAPE_BASE + unit).unwrap_or(char::REPLACEMENT_CHARACTER)
+}
+
+fn is_escaped(c: char) -> bool {
+    c as u32 >= ESCAPE_BASE
+}
+
+fn lossy(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if is_escaped(c) {
+                char::REPLACEMENT_CHARACTER
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+#[cfg(unix)]
+fn unescape(value: &str) -> OsString {
+    use std::os::unix::ffi::OsStringExt;
+
+    let mut bytes = Vec::with_capacity(value.len());
+    for c in value.chars() {
+        if is_escaped(c) {
+            bytes.push((c as u32 - ESCAPE_BASE) as u8);
+        } else {
+            bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes());
+        }
+    }
+    OsString::from_vec(bytes)
+}
+
+#[cfg(windows)]
+fn unescape(value: &str) -> OsString {
+    use std::os::windows::ffi::OsStringExt;
+
+    let mut wide = Vec::with_capacity(value.len());
+    for c in value.chars() {
+        if is_escaped(c) {
+            wide.push((c as u32 - ESCAPE_BASE + 0xD800) as u16);
+        } else {
+            wide.extend_from_slice(c.encode_utf16(&mut [0; 2]));
+        }
+    }
+    OsString::from_wide(&wide)
+}
+
+#[cfg(not(any(unix, windows)))]
+fn unescape(value: &str) -> OsString {
+    OsString::from(value)
+}
+
+fn restore(matches: &mut ArgMatches) {
+    let escaped: Vec<String> = matches
+        .values
+        .iter()
+        .filter(|(name, value)| {
+            value.chars().any(is_escaped)
+                || matches
+                    .multi_values
+                    .get(name)
+                    .is_some_and(|values| values.iter().any(|v| v.chars().any(is_escaped)))
+        })
+        .map(|(name, _)| name.clone())
+        .collect();
+    for name in escaped {
+        let values = matches.get_many(&name);
+        matches
+            .os_values
+            .insert(&name, values.iter().map(|v| unescape(v)).collect());
+        matches
+            .values
+            .insert(name.clone(), lossy(&matches.values[&name]));
+        if matches.multi_values.get(&name).is_some() {
+            matches
+                .multi_values
+                .insert(&name, values.iter().map(|v| lossy(v)).collect());
+        }
+    }
+    matches.key_values = matches
+        .key_values
+        .iter()
+        .map(|(name, entries)| {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| (lossy(key), value.as_deref().map(lossy)))
+                .collect();
+            (name.to_string(), entries)
+        })
+        .collect();
+    matches.grouped_values = matches
+        .grouped_values
+        .iter()
+        .map(|(name, groups)| {
+            let groups = groups
+                .iter()
+                .map(|group| group.iter().map(|v| lossy(v)).collect())
+                .collect();
+            (name.to_string(), groups)
+        })
+        .collect();
+    for positional in &mut matches.positionals {
+        *positional = lossy(positional);
+    }
+    for token in matches.sections.iter_mut().flatten() {
+        *token = lossy(token);
+    }
+    if let Some((_, sub)) = &mut matches.subcommand {
+        restore(sub);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[cfg(unix)]
+    #[test]
+    fn invalid_bytes_round_trip_through_escapes() {
+        use std::os::unix::ffi::OsStrExt;
+
+        let raw = OsStr::from_bytes(b"caf\xe9-\xff.txt");
+        let escaped = escape(raw);
+        assert!(escaped.starts_with("caf"));
+        assert_eq!(escaped.chars().filter(|&c| is_escaped(c)).count(), 2);
+        assert_eq!(unescape(&escaped), raw);
+        assert_eq!(lossy(&escaped), "caf\u{fffd}-\u{fffd}.txt");
+    }
+
+    #[test]
+    fn valid_text_is_unchanged() {
+        let text = OsString::from("grüße/データ");
+        assert_eq!(escape(&text), "grüße/データ");
+        assert_eq!(unescape("grüße/データ"), text);
+        assert_eq!(lossy("grüße"), "grüße");
+    }
+}
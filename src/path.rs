@@ -0,0 +1,35 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PathStyle {
+    Unix,
+    Windows,
+}
+
+impl PathStyle {
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            PathStyle::Windows
+        } else {
+            PathStyle::Unix
+        }
+    }
+
+    pub fn separator(self) -> char {
+        match self {
+            PathStyle::Unix => '/',
+            PathStyle::Windows => '\\',
+        }
+    }
+
+    // `\` is an ordinary file name character on Unix, so only Windows
+    // accepts both separators.
+    pub fn normalize(self, value: &str) -> String {
+        match self {
+            PathStyle::Unix => value.to_string(),
+            PathStyle::Windows => value.replace('/', "\\"),
+        }
+    }
+}
+
+pub fn normalize_path(value: &str) -> String {
+    PathStyle::native().normalize(value)
+}
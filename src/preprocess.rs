@@ -0,0 +1,30 @@
+use std::sync::Arc;
+
+use crate::ArgParser;
+
+pub type Preprocessor = Arc<dyn Fn(Vec<String>) -> Vec<String> + Send + Sync>;
+
+impl ArgParser {
+    pub fn preprocessor<F>(mut self, preprocessor: F) -> Self
+    where
+        F: 'static + Fn(Vec<String>) -> Vec<String> + Send + Sync,
+    {
+        self.preprocessors.push(Arc::new(preprocessor));
+        self
+    }
+
+    pub(crate) fn preprocess(&self, args: &[String]) -> Option<Vec<String>> {
+        let (first, rest) = args.split_first()?;
+        if self.preprocessors.is_empty() && self.abbreviations.is_empty() {
+            return None;
+        }
+        let tokens = self.preprocessors.iter().fold(
+            self.expand_abbreviations(rest.to_vec()),
+            |tokens, preprocessor| preprocessor(tokens),
+        );
+        let mut processed = Vec::with_capacity(tokens.len() + 1);
+        processed.push(first.clone());
+        processed.extend(tokens);
+        Some(processed)
+    }
+}
@@ -0,0 +1,69 @@
+use std::env;
+use std::ops::Not;
+
+use crate::{Stream, TermCaps};
+
+const CI_VARS: &[&str] = &[
+    "GITHUB_ACTIONS",
+    "GITLAB_CI",
+    "BUILDKITE",
+    "CIRCLECI",
+    "TRAVIS",
+    "TF_BUILD",
+    "JENKINS_URL",
+    "TEAMCITY_VERSION",
+];
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Condition {
+    Always,
+    Ci,
+    Tty,
+    Os(String),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    pub fn holds(&self) -> bool {
+        match self {
+            Condition::Always => true,
+            Condition::Ci => is_ci(),
+            Condition::Tty => TermCaps::get(Stream::Stdout).interactive,
+            Condition::Os(os) => env::consts::OS.eq_ignore_ascii_case(os),
+            Condition::Not(inner) => !inner.holds(),
+        }
+    }
+}
+
+impl Not for Condition {
+    type Output = Condition;
+
+    fn not(self) -> Condition {
+        match self {
+            Condition::Not(inner) => *inner,
+            other => Condition::Not(Box::new(other)),
+        }
+    }
+}
+
+pub fn when_ci() -> Condition {
+    Condition::Ci
+}
+
+pub fn when_tty() -> Condition {
+    Condition::Tty
+}
+
+pub fn when_os(os: &str) -> Condition {
+    Condition::Os(os.to_string())
+}
+
+fn is_ci() -> bool {
+    let truthy = |name: &str| {
+        env::var(name)
+            .map(|v| !v.is_empty() && v != "0" && !v.eq_ignore_ascii_case("false"))
+            .unwrap_or(false)
+    };
+    truthy("CI") || CI_VARS.iter().any(|name| env::var_os(name).is_some())
+}
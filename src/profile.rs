@@ -0,0 +1,45 @@
+use crate::ArgParser;
+
+pub const PROFILE: &str = "profile";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Profile {
+    pub name: String,
+    pub values: Vec<(String, String)>,
+}
+
+impl Profile {
+    pub fn value(&self, arg: &str) -> Option<&str> {
+        self.values
+            .iter()
+            .find(|(name, _)| name == arg)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+impl ArgParser {
+    pub fn profile(mut self, name: &str, values: &[(&str, &str)]) -> Self {
+        self.profiles.push(Profile {
+            name: name.to_string(),
+            values: values
+                .iter()
+                .map(|(arg, value)| (arg.to_string(), value.to_string()))
+                .collect(),
+        });
+        if !self.args.iter().any(|a| a.name == PROFILE) {
+            self = self
+                .arg(PROFILE)
+                .long(PROFILE, PROFILE)
+                .takes_value(PROFILE);
+        }
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == PROFILE) {
+            arg.choices.push(name.to_string());
+        }
+        self
+    }
+
+    pub fn profiles(&self) -> &[Profile] {
+        &self.profiles
+    }
+}
@@ -0,0 +1,65 @@
+use std::io::{self, BufRead, Write};
+
+use crate::{ArgMatches, ArgParser, Stream, TermCaps};
+
+pub const DRY_RUN: &str = "dry-run";
+pub const ASSUME_YES: &str = "yes";
+
+pub fn confirm_with<R, W>(prompt: &str, input: &mut R, output: &mut W) -> io::Result<bool>
+where
+    R: BufRead,
+    W: Write,
+{
+    write!(output, "{} [y/N] ", prompt)?;
+    output.flush()?;
+    let mut answer = String::new();
+    input.read_line(&mut answer)?;
+    Ok(matches!(
+        answer.trim().to_ascii_lowercase().as_str(),
+        "y" | "yes"
+    ))
+}
+
+pub fn confirm(prompt: &str) -> io::Result<bool> {
+    if !TermCaps::get(Stream::Stderr).interactive {
+        return Ok(false);
+    }
+    confirm_with(prompt, &mut io::stdin().lock(), &mut io::stderr())
+}
+
+impl ArgParser {
+    pub fn dry_run_flag(self) -> Self {
+        self.safety_flag(DRY_RUN, 'n')
+    }
+
+    pub fn assume_yes_flag(self) -> Self {
+        self.safety_flag(ASSUME_YES, 'y')
+    }
+
+    fn safety_flag(self, name: &str, short: char) -> Self {
+        let short_taken = self.args.iter().any(|a| a.short == Some(short));
+        let parser = self.arg(name).long(name, name);
+        if short_taken {
+            parser
+        } else {
+            parser.short(name, short)
+        }
+    }
+}
+
+impl ArgMatches {
+    pub fn is_dry_run(&self) -> bool {
+        self.flags.get(DRY_RUN).copied().unwrap_or(false)
+    }
+
+    pub fn assume_yes(&self) -> bool {
+        self.flags.get(ASSUME_YES).copied().unwrap_or(false)
+    }
+
+    pub fn confirm(&self, prompt: &str) -> io::Result<bool> {
+        if self.assume_yes() || self.is_dry_run() {
+            return Ok(true);
+        }
+        confirm(prompt)
+    }
+}
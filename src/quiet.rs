@@ -0,0 +1,95 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use crate::{Arg, ArgMatches, ArgParser, ExplainStep, ParseError, Scope};
+
+pub const QUIET: &str = "quiet";
+
+impl ArgParser {
+    pub fn quiet_flag(self) -> Self {
+        self.arg(QUIET)
+            .short(QUIET, 'q')
+            .long(QUIET, QUIET)
+            .global(QUIET)
+            .quiet_arg(QUIET)
+    }
+
+    pub fn quiet_arg(mut self, name: &str) -> Self {
+        self.quiet_arg = Some(name.to_string());
+        self
+    }
+
+    pub fn quiet_requested(&self, args: &[String]) -> bool {
+        let Some(arg) = self.quiet_definition() else {
+            return false;
+        };
+        let trace = Rc::new(RefCell::new(Vec::new()));
+        let scope = Scope {
+            program: self.program(args),
+            collect_errors: true,
+            trace: Some(trace.clone()),
+            ..Scope::default()
+        };
+        let _ = self.parse_in_scope(args, &scope);
+        let mut quiet = false;
+        for step in trace.take() {
+            if let ExplainStep::Flag {
+                token, arg: name, ..
+            } = step
+            {
+                if name == arg.name {
+                    quiet = token.strip_prefix("--").is_none_or(|l| arg.matches_long(l));
+                }
+            }
+        }
+        quiet
+    }
+
+    pub(crate) fn quiet_in(&self, matches: &ArgMatches) -> bool {
+        let Some(arg) = self.quiet_definition() else {
+            return false;
+        };
+        let mut level = Some(matches);
+        while let Some(current) = level {
+            if current.flags.get(&arg.name) == Some(&true) {
+                return true;
+            }
+            level = current.subcommand().map(|(_, sub)| sub);
+        }
+        false
+    }
+
+    fn quiet_definition(&self) -> Option<&Arg> {
+        let name = self.quiet_arg.as_ref()?;
+        self.args.iter().find(|a| &a.name == name)
+    }
+}
+
+impl ParseError {
+    pub fn brief(&self) -> String {
+        let brief = match self {
+            ParseError::Multiple(errors) => match errors.first() {
+                Some(first) => return first.brief(),
+                None => return String::new(),
+            },
+            ParseError::UnknownArgument { token, .. } => ParseError::UnknownArgument {
+                token: token.clone(),
+                suggestion: None,
+            },
+            ParseError::InvalidChoice {
+                arg,
+                value,
+                choices,
+                ..
+            } => ParseError::InvalidChoice {
+                arg: arg.clone(),
+                value: value.clone(),
+                choices: choices.clone(),
+                suggestion: None,
+            },
+            other => other.clone(),
+        };
+        let text = brief.to_string();
+        text.lines().next().unwrap_or_default().to_string()
+    }
+}
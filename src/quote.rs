@@ -0,0 +1,108 @@
+pub(crate) fn is_plain(word: &str) -> bool {
+    !word.is_empty()
+        && !word.starts_with(['=', '@'])
+        && word
+            .chars()
+            .all(|c| c.is_alphanumeric() || "/._-+:@%,=".contains(c))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    Posix,
+    Windows,
+}
+
+impl QuoteStyle {
+    pub fn native() -> Self {
+        if cfg!(windows) {
+            QuoteStyle::Windows
+        } else {
+            QuoteStyle::Posix
+        }
+    }
+
+    pub fn quote(self, word: &str) -> String {
+        match self {
+            QuoteStyle::Posix if is_plain(word) => word.to_string(),
+            QuoteStyle::Posix => format!("'{}'", word.replace('\'', "'\\''")),
+            // The rules of CommandLineToArgvW: backslashes are literal unless
+            // they precede a quote, so only those runs need doubling.
+            QuoteStyle::Windows => {
+                if !word.is_empty() && !word.contains([' ', '\t', '\n', '"']) {
+                    return word.to_string();
+                }
+                let mut out = String::from("\"");
+                let mut backslashes = 0;
+                for c in word.chars() {
+                    match c {
+                        '\\' => backslashes += 1,
+                        '"' => {
+                            out.push_str(&"\\".repeat(backslashes * 2 + 1));
+                            backslashes = 0;
+                        }
+                        _ => {
+                            out.push_str(&"\\".repeat(backslashes));
+                            backslashes = 0;
+                        }
+                    }
+                    if c != '\\' {
+                        out.push(c);
+                    }
+                }
+                out.push_str(&"\\".repeat(backslashes * 2));
+                out.push('"');
+                out
+            }
+        }
+    }
+
+    pub fn join<S: AsRef<str>>(self, words: &[S]) -> String {
+        words
+            .iter()
+            .map(|word| self.quote(word.as_ref()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+pub fn quote(word: &str) -> String {
+    QuoteStyle::native().quote(word)
+}
+
+pub fn join<S: AsRef<str>>(words: &[S]) -> String {
+    QuoteStyle::native().join(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn posix_leaves_plain_words_alone() {
+        assert_eq!(QuoteStyle::Posix.quote("src/main.rs"), "src/main.rs");
+        assert_eq!(QuoteStyle::Posix.quote("--level=3"), "--level=3");
+    }
+
+    #[test]
+    fn posix_quotes_special_words() {
+        assert_eq!(QuoteStyle::Posix.quote(""), "''");
+        assert_eq!(QuoteStyle::Posix.quote("a b"), "'a b'");
+        assert_eq!(QuoteStyle::Posix.quote("it's"), "'it'\\''s'");
+        assert_eq!(QuoteStyle::Posix.quote("=x"), "'=x'");
+        assert_eq!(QuoteStyle::Posix.quote("$HOME"), "'$HOME'");
+    }
+
+    #[test]
+    fn windows_doubles_backslashes_before_quotes() {
+        assert_eq!(QuoteStyle::Windows.quote(r"C:\dir"), r"C:\dir");
+        assert_eq!(QuoteStyle::Windows.quote(""), "\"\"");
+        assert_eq!(QuoteStyle::Windows.quote(r"a b\"), r#""a b\\""#);
+        assert_eq!(QuoteStyle::Windows.quote(r#"say "hi""#), r#""say \"hi\"""#);
+        assert_eq!(QuoteStyle::Windows.quote(r#"x\"y"#), r#""x\\\"y""#);
+    }
+
+    #[test]
+    fn join_separates_with_spaces() {
+        assert_eq!(QuoteStyle::Posix.join(&["run", "a b"]), "run 'a b'");
+    }
+}
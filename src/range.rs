@@ -0,0 +1,118 @@
+use std::fmt;
+use std::ops::{Bound, RangeBounds};
+
+use crate::ArgParser;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ValueRange {
+    pub min: Option<i64>,
+    pub max: Option<i64>,
+}
+
+impl ValueRange {
+    pub fn new(bounds: impl RangeBounds<i64>) -> Self {
+        ValueRange {
+            min: match bounds.start_bound() {
+                Bound::Included(&min) => Some(min),
+                Bound::Excluded(&min) => Some(min.saturating_add(1)),
+                Bound::Unbounded => None,
+            },
+            max: match bounds.end_bound() {
+                Bound::Included(&max) => Some(max),
+                Bound::Excluded(&max) => Some(max.saturating_sub(1)),
+                Bound::Unbounded => None,
+            },
+        }
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.min.is_none_or(|min| value >= min) && self.max.is_none_or(|max| value <= max)
+    }
+
+    pub(crate) fn values(&self, limit: usize) -> Option<Vec<String>> {
+        let (min, max) = (self.min?, self.max?);
+        if max < min || (max - min) as u64 >= limit as u64 {
+            return None;
+        }
+        Some((min..=max).map(|v| v.to_string()).collect())
+    }
+}
+
+impl fmt::Display for ValueRange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.min, self.max) {
+            (Some(min), Some(max)) => write!(f, "{}-{}", min, max),
+            (Some(min), None) => write!(f, ">={}", min),
+            (None, Some(max)) => write!(f, "<={}", max),
+            (None, None) => f.write_str("any"),
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn range(mut self, name: &str, bounds: impl RangeBounds<i64>) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.range = Some(ValueRange::new(bounds));
+        }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bounds_are_normalized_to_inclusive() {
+        assert_eq!(
+            ValueRange::new(1..10),
+            ValueRange {
+                min: Some(1),
+                max: Some(9)
+            }
+        );
+        assert_eq!(
+            ValueRange::new(..=5),
+            ValueRange {
+                min: None,
+                max: Some(5)
+            }
+        );
+        assert_eq!(
+            ValueRange::new(..),
+            ValueRange {
+                min: None,
+                max: None
+            }
+        );
+    }
+
+    #[test]
+    fn contains_respects_both_ends() {
+        let range = ValueRange::new(1..=64);
+        assert!(range.contains(1));
+        assert!(range.contains(64));
+        assert!(!range.contains(0));
+        assert!(!range.contains(65));
+        assert!(ValueRange::new(0..).contains(i64::MAX));
+    }
+
+    #[test]
+    fn display_forms() {
+        assert_eq!(ValueRange::new(1..=64).to_string(), "1-64");
+        assert_eq!(ValueRange::new(3..).to_string(), ">=3");
+        assert_eq!(ValueRange::new(..=7).to_string(), "<=7");
+        assert_eq!(ValueRange::new(..).to_string(), "any");
+    }
+
+    #[test]
+    fn small_ranges_enumerate_their_values() {
+        assert_eq!(
+            ValueRange::new(1..=3).values(10),
+            Some(vec!["1".to_string(), "2".to_string(), "3".to_string()])
+        );
+        assert_eq!(ValueRange::new(1..=100).values(10), None);
+        assert_eq!(ValueRange::new(1..).values(10), None);
+    }
+}
@@ -0,0 +1,141 @@
+use std::io::BufRead;
+
+use crate::{ArgMatches, ArgParser, ParseError};
+
+pub struct ScriptLines<'a, R> {
+    parser: &'a ArgParser,
+    lines: std::io::Lines<R>,
+    line_no: usize,
+}
+
+impl ArgParser {
+    pub fn parse_script<R: BufRead>(&self, reader: R) -> ScriptLines<'_, R> {
+        ScriptLines {
+            parser: self,
+            lines: reader.lines(),
+            line_no: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Iterator for ScriptLines<'_, R> {
+    type Item = (usize, Result<ArgMatches, ParseError>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            self.line_no += 1;
+            let start = self.line_no;
+            let mut command = match self.lines.next()? {
+                Ok(line) => line,
+                Err(err) => return Some((start, Err(script_error(err.to_string())))),
+            };
+            while command.ends_with('\\') {
+                command.pop();
+                match self.lines.next() {
+                    Some(Ok(line)) => {
+                        self.line_no += 1;
+                        command.push_str(&line);
+                    }
+                    Some(Err(err)) => return Some((start, Err(script_error(err.to_string())))),
+                    None => break,
+                }
+            }
+            let command = command.trim();
+            if command.is_empty() || command.starts_with('#') {
+                continue;
+            }
+            let result = split_words(command)
+                .map_err(|message| script_error(message.to_string()))
+                .and_then(|words| self.parser.parse_args_only(&words));
+            return Some((start, result));
+        }
+    }
+}
+
+fn script_error(message: String) -> ParseError {
+    ParseError::Script { message }
+}
+
+pub(crate) fn split_words(line: &str) -> Result<Vec<String>, &'static str> {
+    let mut words = Vec::new();
+    let mut word = String::new();
+    let mut in_word = false;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote"),
+                    }
+                }
+            }
+            '"' => {
+                in_word = true;
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c) => word.push(c),
+                            None => return Err("unterminated double quote"),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated double quote"),
+                    }
+                }
+            }
+            '\\' => {
+                in_word = true;
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            '#' if !in_word => break,
+            c if c.is_whitespace() => {
+                if in_word {
+                    words.push(std::mem::take(&mut word));
+                    in_word = false;
+                }
+            }
+            c => {
+                in_word = true;
+                word.push(c);
+            }
+        }
+    }
+    if in_word {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_like_a_shell() {
+        assert_eq!(
+            split_words(r#"run --name 'a b' "c \"d\"" e\ f # comment"#),
+            Ok(vec![
+                "run".to_string(),
+                "--name".to_string(),
+                "a b".to_string(),
+                "c \"d\"".to_string(),
+                "e f".to_string(),
+            ])
+        );
+        assert_eq!(split_words("x''"), Ok(vec!["x".to_string()]));
+        assert_eq!(split_words("''"), Ok(vec![String::new()]));
+        assert_eq!(split_words("a#b"), Ok(vec!["a#b".to_string()]));
+    }
+
+    #[test]
+    fn reports_unterminated_quotes() {
+        assert_eq!(split_words("'open"), Err("unterminated single quote"));
+        assert_eq!(split_words("\"open"), Err("unterminated double quote"));
+    }
+}
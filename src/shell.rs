@@ -0,0 +1,64 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::quote::{is_plain, QuoteStyle};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+    PowerShell,
+}
+
+impl Shell {
+    pub fn name(self) -> &'static str {
+        match self {
+            Shell::Bash => "bash",
+            Shell::Zsh => "zsh",
+            Shell::Fish => "fish",
+            Shell::PowerShell => "powershell",
+        }
+    }
+
+    pub fn quote(self, value: &str) -> String {
+        match self {
+            Shell::Bash | Shell::Zsh => QuoteStyle::Posix.quote(value),
+            _ if is_plain(value) => value.to_string(),
+            Shell::Fish => format!("'{}'", value.replace('\\', "\\\\").replace('\'', "\\'")),
+            Shell::PowerShell => format!("'{}'", value.replace('\'', "''")),
+        }
+    }
+
+    pub fn quote_command<S: AsRef<str>>(self, words: &[S]) -> String {
+        words
+            .iter()
+            .map(|word| self.quote(word.as_ref()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    pub fn escape_path(self, path: &str) -> String {
+        self.quote(path)
+    }
+}
+
+impl fmt::Display for Shell {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+impl FromStr for Shell {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "bash" => Ok(Shell::Bash),
+            "zsh" => Ok(Shell::Zsh),
+            "fish" => Ok(Shell::Fish),
+            "powershell" | "pwsh" => Ok(Shell::PowerShell),
+            other => Err(format!("unsupported shell: {}", other)),
+        }
+    }
+}
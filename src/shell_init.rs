@@ -0,0 +1,63 @@
+use crate::{help, ArgParser, Shell};
+
+pub const SHELL_INIT: &str = "shell-init";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ShellInit {
+    pub(crate) function: String,
+    pub(crate) template: String,
+}
+
+impl ArgParser {
+    pub fn shell_init(mut self, function: &str, template: &str) -> Self {
+        self.shell_init = Some(ShellInit {
+            function: function.to_string(),
+            template: template.to_string(),
+        });
+        self.arg(SHELL_INIT)
+            .long(SHELL_INIT, SHELL_INIT)
+            .takes_value(SHELL_INIT)
+            .choices(SHELL_INIT, &["bash", "zsh", "fish", "powershell"])
+    }
+
+    pub fn render_shell_init(&self, program: &str, shell: Shell) -> Option<String> {
+        let init = self.shell_init.as_ref()?;
+        let (command, args) = match shell {
+            Shell::Bash | Shell::Zsh => (format!("command {}", shell.quote(program)), "\"$@\""),
+            Shell::Fish => (format!("command {}", shell.quote(program)), "$argv"),
+            Shell::PowerShell => (
+                format!(
+                    "& (Get-Command {} -CommandType Application)",
+                    shell.quote(program)
+                ),
+                "@args",
+            ),
+        };
+        let body: String = init
+            .template
+            .replace("{program}", &command)
+            .replace("{args}", args)
+            .lines()
+            .map(|line| format!("    {}\n", line))
+            .collect();
+        let mut out = match shell {
+            Shell::Bash | Shell::Zsh => format!("{}() {{\n{}}}\n", init.function, body),
+            Shell::Fish => format!("function {}\n{}end\n", init.function, body),
+            Shell::PowerShell => format!("function {} {{\n{}}}\n", init.function, body),
+        };
+        out.push('\n');
+        out.push_str(&self.render_completion(program, shell));
+        Some(out)
+    }
+
+    pub(crate) fn shell_init_request(&self, args: &[String]) -> Option<String> {
+        let flag = format!("--{}", SHELL_INIT);
+        let mut tokens = args.iter().skip(1);
+        let value = match tokens.next()?.strip_prefix(&flag)? {
+            "" => tokens.next()?.as_str(),
+            rest => rest.strip_prefix('=')?,
+        };
+        let program = help::program_name(args.first()?);
+        self.render_shell_init(&program, value.parse().ok()?)
+    }
+}
@@ -0,0 +1,23 @@
+use crate::Shell;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Shortcut {
+    pub name: String,
+    pub args: Vec<String>,
+}
+
+impl Shortcut {
+    pub fn render(&self, program: &str, shell: Shell) -> String {
+        let mut words = vec![program.to_string()];
+        words.extend(self.args.iter().cloned());
+        let command = shell.quote_command(&words);
+        match shell {
+            Shell::Bash | Shell::Zsh => {
+                format!("alias {}={}", self.name, shell.quote(&command))
+            }
+            Shell::Fish => format!("alias {} {}", self.name, shell.quote(&command)),
+            Shell::PowerShell => format!("function {} {{ {} @args }}", self.name, command),
+        }
+    }
+}
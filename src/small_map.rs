@@ -0,0 +1,115 @@
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq)]
+pub(crate) struct SmallMap<V>(Vec<(Box<str>, V)>);
+
+impl<V> Default for SmallMap<V> {
+    fn default() -> Self {
+        SmallMap(Vec::new())
+    }
+}
+
+impl<V> SmallMap<V> {
+    fn position(&self, name: &str) -> Result<usize, usize> {
+        self.0.binary_search_by(|(key, _)| key.as_ref().cmp(name))
+    }
+
+    pub(crate) fn get(&self, name: &str) -> Option<&V> {
+        let index = self.position(name).ok()?;
+        Some(&self.0[index].1)
+    }
+
+    pub(crate) fn insert(&mut self, name: &str, value: V) {
+        match self.position(name) {
+            Ok(index) => self.0[index].1 = value,
+            Err(index) => self.0.insert(index, (name.into(), value)),
+        }
+    }
+
+    pub(crate) fn remove(&mut self, name: &str) -> Option<V> {
+        let index = self.position(name).ok()?;
+        Some(self.0.remove(index).1)
+    }
+
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&str, &V)> {
+        self.0.iter().map(|(key, value)| (key.as_ref(), value))
+    }
+
+    pub(crate) fn shrink_to_fit(&mut self) {
+        self.0.shrink_to_fit();
+    }
+}
+
+impl<V: Clone> SmallMap<V> {
+    pub(crate) fn copy_entry(&mut self, name: &str, from: &SmallMap<V>) {
+        match from.get(name) {
+            Some(value) => self.insert(name, value.clone()),
+            None => {
+                self.remove(name);
+            }
+        }
+    }
+}
+
+impl<V> FromIterator<(String, V)> for SmallMap<V> {
+    fn from_iter<I: IntoIterator<Item = (String, V)>>(iter: I) -> Self {
+        let mut entries: Vec<(Box<str>, V)> = iter
+            .into_iter()
+            .map(|(key, value)| (key.into_boxed_str(), value))
+            .collect();
+        entries.sort_by(|(a, _), (b, _)| a.cmp(b));
+        entries.dedup_by(|(a, _), (b, _)| a == b);
+        SmallMap(entries)
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for SmallMap<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn insert_keeps_keys_sorted_and_replaces() {
+        let mut map = SmallMap::default();
+        map.insert("b", 2);
+        map.insert("a", 1);
+        map.insert("c", 3);
+        map.insert("b", 20);
+        let entries: Vec<(&str, &i32)> = map.iter().collect();
+        assert_eq!(entries, vec![("a", &1), ("b", &20), ("c", &3)]);
+    }
+
+    #[test]
+    fn get_and_remove() {
+        let mut map: SmallMap<i32> = [("x".to_string(), 1)].into_iter().collect();
+        assert_eq!(map.get("x"), Some(&1));
+        assert_eq!(map.get("y"), None);
+        assert_eq!(map.remove("x"), Some(1));
+        assert_eq!(map.remove("x"), None);
+    }
+
+    #[test]
+    fn from_iter_sorts_and_deduplicates() {
+        let map: SmallMap<i32> = [("b", 1), ("a", 2), ("b", 3)]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v))
+            .collect();
+        assert_eq!(map.iter().count(), 2);
+        assert_eq!(map.get("a"), Some(&2));
+    }
+
+    #[test]
+    fn copy_entry_mirrors_presence() {
+        let from: SmallMap<i32> = [("a".to_string(), 1)].into_iter().collect();
+        let mut to: SmallMap<i32> = [("b".to_string(), 2)].into_iter().collect();
+        to.copy_entry("a", &from);
+        to.copy_entry("b", &from);
+        assert_eq!(to.get("a"), Some(&1));
+        assert_eq!(to.get("b"), None);
+    }
+}
@@ -0,0 +1,101 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::ArgMatches;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValueSource {
+    CommandLine,
+    Env(String),
+    EnvFile(PathBuf),
+    Profile(String),
+    ConfigFile(PathBuf),
+    Default,
+}
+
+impl fmt::Display for ValueSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ValueSource::CommandLine => write!(f, "command line"),
+            ValueSource::Env(var) => write!(f, "environment variable {}", var),
+            ValueSource::EnvFile(path) => write!(f, "env file {}", path.display()),
+            ValueSource::Profile(name) => write!(f, "profile '{}'", name),
+            ValueSource::ConfigFile(path) => write!(f, "config file {}", path.display()),
+            ValueSource::Default => write!(f, "default"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Provenance {
+    pub value: String,
+    pub source: ValueSource,
+    pub argv_index: Option<usize>,
+    pub key: Option<String>,
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "'{}' from {}", self.value, self.source)?;
+        if let Some(index) = self.argv_index {
+            write!(f, " (argv[{}])", index)?;
+        }
+        Ok(())
+    }
+}
+
+impl ArgMatches {
+    pub fn value_source(&self, name: &str) -> Option<&ValueSource> {
+        self.sources.get(name)
+    }
+
+    pub fn argv_index(&self, name: &str) -> Option<usize> {
+        self.argv_indices.get(name).copied()
+    }
+
+    pub fn source_map(&self) -> BTreeMap<String, Provenance> {
+        self.sources
+            .iter()
+            .filter_map(|(name, source)| {
+                let value = match self.redacted_value(name) {
+                    Some(value) => value.to_string(),
+                    None => self.flags.get(name)?.to_string(),
+                };
+                let provenance = Provenance {
+                    value,
+                    source: source.clone(),
+                    argv_index: self.argv_index(name),
+                    key: self.source_keys.get(name).cloned(),
+                };
+                Some((name.to_string(), provenance))
+            })
+            .collect()
+    }
+}
+
+pub(crate) fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "" | "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_bool_accepts_common_spellings() {
+        for value in ["1", "true", "YES", " on "] {
+            assert_eq!(parse_bool(value), Some(true), "{:?}", value);
+        }
+        for value in ["", "0", "False", "no", "OFF"] {
+            assert_eq!(parse_bool(value), Some(false), "{:?}", value);
+        }
+        assert_eq!(parse_bool("maybe"), None);
+    }
+}
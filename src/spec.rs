@@ -0,0 +1,645 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use crate::{
+    shell_init, Abbreviation, Alias, AmbiguityPolicy, Arg, ArgGroup, ArgParser, Capture, Condition,
+    DuplicatePolicy, Profile, Shortcut, ValueRange,
+};
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ArgSpec {
+    pub name: String,
+    pub short: Option<char>,
+    pub long: Option<String>,
+    pub takes_value: bool,
+    pub required: bool,
+    pub default: Option<String>,
+    pub conditional_defaults: Vec<(Condition, String)>,
+    pub is_path: bool,
+    pub sensitive: bool,
+    pub global: bool,
+    pub choices: Vec<String>,
+    pub on_duplicate: DuplicatePolicy,
+    pub multiple: bool,
+    pub hidden: Option<Condition>,
+    pub examples: Vec<String>,
+    pub aliases: Vec<Alias>,
+    pub env: Option<String>,
+    pub positional: bool,
+    pub introduced_in: Option<String>,
+    pub removed_in: Option<String>,
+    pub replaced_by: Option<String>,
+    pub display_name: Option<String>,
+    pub range: Option<ValueRange>,
+    pub soft_required: Option<String>,
+    pub experimental: Option<String>,
+    pub key_value: bool,
+    pub value_delimiter: Option<char>,
+    pub forbid_empty_values: bool,
+    pub levels: Vec<String>,
+    pub conflicts_with: Vec<String>,
+    pub requires: Vec<String>,
+    pub capture: Capture,
+    pub ignore_case: bool,
+    pub trailing_var_arg: bool,
+    pub required_when: Vec<Condition>,
+    pub glob_expand: bool,
+    pub negatable: bool,
+    pub num_values: usize,
+    pub help: Option<String>,
+    pub prefix_choices: bool,
+    pub required_if: Vec<(String, String)>,
+    pub required_unless: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct CommandSpec {
+    pub name: String,
+    pub aliases: Vec<Alias>,
+    pub args: Vec<ArgSpec>,
+    pub groups: Vec<ArgGroup>,
+    pub subcommands: Vec<CommandSpec>,
+    pub disallowed_globals: Vec<String>,
+    pub shortcuts: Vec<Shortcut>,
+    pub abbreviations: Vec<Abbreviation>,
+    pub profiles: Vec<Profile>,
+    pub collect_errors: bool,
+    pub hide_internal_aliases: bool,
+    pub version: Option<String>,
+    pub accept_removed_args: bool,
+    pub exec_style: bool,
+    pub dash_sections: bool,
+    pub default_subcommand: Option<String>,
+    pub ambiguity_policy: Option<AmbiguityPolicy>,
+    pub usage_examples: Vec<String>,
+    pub namespace: Option<String>,
+    pub about: Option<String>,
+    pub author: Option<String>,
+    pub config_files: Vec<PathBuf>,
+    pub env_files: Vec<PathBuf>,
+    pub shell_init: Option<(String, String)>,
+    pub quiet_arg: Option<String>,
+}
+
+impl From<&Arg> for ArgSpec {
+    fn from(arg: &Arg) -> Self {
+        ArgSpec {
+            name: arg.name.clone(),
+            short: arg.short,
+            long: arg.long.clone(),
+            takes_value: arg.takes_value,
+            required: arg.required,
+            default: arg.default.clone(),
+            conditional_defaults: arg.conditional_defaults.clone(),
+            is_path: arg.is_path,
+            sensitive: arg.sensitive,
+            global: arg.global,
+            choices: arg.choices.clone(),
+            on_duplicate: arg.on_duplicate,
+            multiple: arg.multiple,
+            hidden: arg.hidden.clone(),
+            examples: arg.examples.clone(),
+            aliases: arg.aliases.clone(),
+            env: arg.env.clone(),
+            positional: arg.positional,
+            introduced_in: arg.introduced_in.clone(),
+            removed_in: arg.removed_in.clone(),
+            replaced_by: arg.replaced_by.clone(),
+            display_name: arg.display_name.clone(),
+            range: arg.range,
+            soft_required: arg.soft_required.clone(),
+            experimental: arg.experimental.clone(),
+            key_value: arg.key_value,
+            value_delimiter: arg.value_delimiter,
+            forbid_empty_values: arg.forbid_empty_values,
+            levels: arg.levels.clone(),
+            conflicts_with: arg.conflicts_with.clone(),
+            requires: arg.requires.clone(),
+            capture: arg.capture,
+            ignore_case: arg.ignore_case,
+            trailing_var_arg: arg.trailing_var_arg,
+            required_when: arg.required_when.clone(),
+            glob_expand: arg.glob_expand,
+            negatable: arg.negatable,
+            num_values: arg.num_values,
+            help: arg.help.clone(),
+            prefix_choices: arg.prefix_choices,
+            required_if: arg.required_if.clone(),
+            required_unless: arg.required_unless.clone(),
+        }
+    }
+}
+
+impl From<ArgSpec> for Arg {
+    fn from(spec: ArgSpec) -> Self {
+        Arg {
+            name: spec.name,
+            short: spec.short,
+            long: spec.long,
+            takes_value: spec.takes_value,
+            required: spec.required,
+            default: spec.default,
+            validator: None,
+            context_validator: None,
+            is_path: spec.is_path,
+            sensitive: spec.sensitive,
+            global: spec.global,
+            choices: spec.choices,
+            on_duplicate: spec.on_duplicate,
+            multiple: spec.multiple,
+            conditional_defaults: spec.conditional_defaults,
+            hidden: spec.hidden,
+            examples: spec.examples,
+            aliases: spec.aliases,
+            env: spec.env,
+            positional: spec.positional,
+            value_parser: None,
+            introduced_in: spec.introduced_in,
+            removed_in: spec.removed_in,
+            replaced_by: spec.replaced_by,
+            display_name: spec.display_name,
+            range: spec.range,
+            soft_required: spec.soft_required,
+            experimental: spec.experimental,
+            key_value: spec.key_value,
+            value_delimiter: spec.value_delimiter,
+            forbid_empty_values: spec.forbid_empty_values,
+            levels: spec.levels,
+            conflicts_with: spec.conflicts_with,
+            requires: spec.requires,
+            capture: spec.capture,
+            ignore_case: spec.ignore_case,
+            trailing_var_arg: spec.trailing_var_arg,
+            required_when: spec.required_when,
+            glob_expand: spec.glob_expand,
+            negatable: spec.negatable,
+            num_values: spec.num_values.max(1),
+            help: spec.help,
+            prefix_choices: spec.prefix_choices,
+            required_if: spec.required_if,
+            required_unless: spec.required_unless,
+            key_validators: Vec::new(),
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn to_spec(&self, name: &str) -> CommandSpec {
+        let mut subcommands: Vec<CommandSpec> = self
+            .subcommands
+            .iter()
+            .map(|(name, sub)| sub.to_spec(name))
+            .collect();
+        subcommands.sort_by(|a, b| a.name.cmp(&b.name));
+        CommandSpec {
+            name: name.to_string(),
+            aliases: self.aliases.clone(),
+            args: self.args.iter().map(ArgSpec::from).collect(),
+            groups: self.groups.clone(),
+            subcommands,
+            disallowed_globals: self.disallowed_globals.clone(),
+            shortcuts: self.shortcuts.clone(),
+            abbreviations: self.abbreviations.clone(),
+            profiles: self.profiles.clone(),
+            collect_errors: self.collect_errors,
+            hide_internal_aliases: self.hide_internal_aliases,
+            version: self.version.clone(),
+            accept_removed_args: self.accept_removed_args,
+            exec_style: self.exec_style,
+            dash_sections: self.dash_sections,
+            default_subcommand: self.default_subcommand.clone(),
+            ambiguity_policy: self.ambiguity_policy,
+            usage_examples: self.usage_examples.clone(),
+            namespace: self.namespace.clone(),
+            about: self.about.clone(),
+            author: self.author.clone(),
+            config_files: self.config_files.clone(),
+            #[cfg(feature = "dotenv")]
+            env_files: self.env_files.clone(),
+            #[cfg(not(feature = "dotenv"))]
+            env_files: Vec::new(),
+            shell_init: self
+                .shell_init
+                .as_ref()
+                .map(|init| (init.function.clone(), init.template.clone())),
+            quiet_arg: self.quiet_arg.clone(),
+        }
+    }
+
+    pub fn from_spec(spec: CommandSpec) -> Self {
+        let name = Some(spec.name.clone()).filter(|name| !name.is_empty());
+        ArgParser {
+            name,
+            ..ArgParser::from_command_spec(spec)
+        }
+    }
+
+    fn from_command_spec(spec: CommandSpec) -> Self {
+        let mut parser = ArgParser::new();
+        parser.args = spec.args.into_iter().map(Arg::from).collect();
+        parser.groups = spec.groups;
+        parser.disallowed_globals = spec.disallowed_globals;
+        parser.shortcuts = spec.shortcuts;
+        parser.abbreviations = spec.abbreviations;
+        parser.profiles = spec.profiles;
+        parser.collect_errors = spec.collect_errors;
+        parser.aliases = spec.aliases;
+        parser.hide_internal_aliases = spec.hide_internal_aliases;
+        parser.version = spec.version;
+        parser.accept_removed_args = spec.accept_removed_args;
+        parser.exec_style = spec.exec_style;
+        parser.dash_sections = spec.dash_sections;
+        parser.default_subcommand = spec.default_subcommand;
+        parser.ambiguity_policy = spec.ambiguity_policy;
+        parser.usage_examples = spec.usage_examples;
+        parser.namespace = spec.namespace;
+        parser.about = spec.about;
+        parser.author = spec.author;
+        parser.config_files = spec.config_files;
+        #[cfg(feature = "dotenv")]
+        {
+            parser.env_files = spec.env_files;
+        }
+        parser.shell_init = spec
+            .shell_init
+            .map(|(function, template)| shell_init::ShellInit { function, template });
+        parser.quiet_arg = spec.quiet_arg;
+        for sub in spec.subcommands {
+            let name = sub.name.clone();
+            parser
+                .subcommands
+                .insert(name, ArgParser::from_command_spec(sub));
+        }
+        parser
+    }
+}
+
+impl From<CommandSpec> for ArgParser {
+    fn from(spec: CommandSpec) -> Self {
+        ArgParser::from_spec(spec)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Impact {
+    Breaking,
+    Additive,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChangeKind {
+    SubcommandRemoved,
+    SubcommandAdded,
+    SubcommandAliasesRemoved(Vec<String>),
+    SubcommandAliasesAdded(Vec<String>),
+    ArgRemoved,
+    ArgAdded {
+        required: bool,
+    },
+    LongChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    ShortChanged {
+        old: Option<char>,
+        new: Option<char>,
+    },
+    TakesValueChanged {
+        now: bool,
+    },
+    RequiredChanged {
+        now: bool,
+    },
+    DefaultChanged {
+        old: Option<String>,
+        new: Option<String>,
+    },
+    ChoicesRestricted,
+    ChoicesLifted,
+    ChoicesRemoved(Vec<String>),
+    ChoicesAdded(Vec<String>),
+    AliasesRemoved(Vec<String>),
+    AliasesAdded(Vec<String>),
+    PositionalChanged {
+        now: bool,
+    },
+    MultipleChanged {
+        now: bool,
+    },
+    RangeChanged {
+        old: Option<ValueRange>,
+        new: Option<ValueRange>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Change {
+    pub command: Vec<String>,
+    pub arg: Option<String>,
+    pub kind: ChangeKind,
+}
+
+impl Change {
+    pub fn impact(&self) -> Impact {
+        match &self.kind {
+            ChangeKind::SubcommandRemoved
+            | ChangeKind::ArgRemoved
+            | ChangeKind::ArgAdded { required: true }
+            | ChangeKind::TakesValueChanged { .. }
+            | ChangeKind::RequiredChanged { now: true }
+            | ChangeKind::DefaultChanged { .. }
+            | ChangeKind::ChoicesRestricted
+            | ChangeKind::ChoicesRemoved(_)
+            | ChangeKind::SubcommandAliasesRemoved(_)
+            | ChangeKind::AliasesRemoved(_)
+            | ChangeKind::PositionalChanged { .. }
+            | ChangeKind::MultipleChanged { .. } => Impact::Breaking,
+            ChangeKind::LongChanged { old: Some(_), .. }
+            | ChangeKind::ShortChanged { old: Some(_), .. } => Impact::Breaking,
+            ChangeKind::RangeChanged { old, new } if !covers(*new, *old) => Impact::Breaking,
+            _ => Impact::Additive,
+        }
+    }
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |value: &Option<String>| value.clone().unwrap_or_else(|| "none".to_string());
+        let mut subject = self.command.join(" ");
+        if let Some(arg) = &self.arg {
+            if !subject.is_empty() {
+                subject.push(' ');
+            }
+            subject.push_str(&format!("<{}>", arg));
+        }
+        if subject.is_empty() {
+            subject.push_str("<root>");
+        }
+        let impact = match self.impact() {
+            Impact::Breaking => "breaking",
+            Impact::Additive => "additive",
+        };
+        write!(f, "[{}] {}: ", impact, subject)?;
+        match &self.kind {
+            ChangeKind::SubcommandRemoved => write!(f, "subcommand removed"),
+            ChangeKind::SubcommandAdded => write!(f, "subcommand added"),
+            ChangeKind::SubcommandAliasesRemoved(aliases) => {
+                write!(f, "subcommand aliases removed: {}", aliases.join(", "))
+            }
+            ChangeKind::SubcommandAliasesAdded(aliases) => {
+                write!(f, "subcommand aliases added: {}", aliases.join(", "))
+            }
+            ChangeKind::ArgRemoved => write!(f, "argument removed"),
+            ChangeKind::ArgAdded { required: true } => write!(f, "required argument added"),
+            ChangeKind::ArgAdded { required: false } => write!(f, "argument added"),
+            ChangeKind::LongChanged { old, new } => {
+                write!(f, "long flag changed from {} to {}", show(old), show(new))
+            }
+            ChangeKind::ShortChanged { old, new } => write!(
+                f,
+                "short flag changed from {} to {}",
+                show(&old.map(String::from)),
+                show(&new.map(String::from))
+            ),
+            ChangeKind::TakesValueChanged { now: true } => write!(f, "now takes a value"),
+            ChangeKind::TakesValueChanged { now: false } => write!(f, "no longer takes a value"),
+            ChangeKind::RequiredChanged { now: true } => write!(f, "now required"),
+            ChangeKind::RequiredChanged { now: false } => write!(f, "no longer required"),
+            ChangeKind::DefaultChanged { old, new } => {
+                write!(f, "default changed from {} to {}", show(old), show(new))
+            }
+            ChangeKind::ChoicesRestricted => write!(f, "values are now restricted to choices"),
+            ChangeKind::ChoicesLifted => write!(f, "values are no longer restricted"),
+            ChangeKind::ChoicesRemoved(values) => {
+                write!(f, "choices removed: {}", values.join(", "))
+            }
+            ChangeKind::ChoicesAdded(values) => write!(f, "choices added: {}", values.join(", ")),
+            ChangeKind::AliasesRemoved(aliases) => {
+                write!(f, "aliases removed: {}", aliases.join(", "))
+            }
+            ChangeKind::AliasesAdded(aliases) => write!(f, "aliases added: {}", aliases.join(", ")),
+            ChangeKind::PositionalChanged { now: true } => write!(f, "now positional"),
+            ChangeKind::PositionalChanged { now: false } => write!(f, "no longer positional"),
+            ChangeKind::MultipleChanged { now: true } => write!(f, "now accepts multiple values"),
+            ChangeKind::MultipleChanged { now: false } => {
+                write!(f, "no longer accepts multiple values")
+            }
+            ChangeKind::RangeChanged { old, new } => {
+                let show = |range: &Option<ValueRange>| range.unwrap_or(ValueRange::new(..));
+                write!(f, "range changed from {} to {}", show(old), show(new))
+            }
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct SpecDiff {
+    pub changes: Vec<Change>,
+}
+
+impl SpecDiff {
+    pub fn is_breaking(&self) -> bool {
+        self.changes.iter().any(|c| c.impact() == Impact::Breaking)
+    }
+
+    pub fn breaking(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.impact() == Impact::Breaking)
+    }
+
+    pub fn additive(&self) -> impl Iterator<Item = &Change> {
+        self.changes
+            .iter()
+            .filter(|c| c.impact() == Impact::Additive)
+    }
+}
+
+impl fmt::Display for SpecDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{}", change)?;
+        }
+        Ok(())
+    }
+}
+
+pub fn diff(old: &CommandSpec, new: &CommandSpec) -> SpecDiff {
+    let mut changes = Vec::new();
+    diff_command(&mut Vec::new(), old, new, &mut changes);
+    SpecDiff { changes }
+}
+
+fn diff_command(
+    path: &mut Vec<String>,
+    old: &CommandSpec,
+    new: &CommandSpec,
+    changes: &mut Vec<Change>,
+) {
+    let mut change = |arg: Option<&str>, kind: ChangeKind| {
+        changes.push(Change {
+            command: path.clone(),
+            arg: arg.map(str::to_string),
+            kind,
+        })
+    };
+    let (removed, added) = diff_aliases(&old.aliases, &new.aliases);
+    if !removed.is_empty() {
+        change(None, ChangeKind::SubcommandAliasesRemoved(removed));
+    }
+    if !added.is_empty() {
+        change(None, ChangeKind::SubcommandAliasesAdded(added));
+    }
+    for old_arg in &old.args {
+        match new.args.iter().find(|a| a.name == old_arg.name) {
+            Some(new_arg) => {
+                for kind in diff_arg(old_arg, new_arg) {
+                    change(Some(&old_arg.name), kind);
+                }
+            }
+            None => change(Some(&old_arg.name), ChangeKind::ArgRemoved),
+        }
+    }
+    for new_arg in &new.args {
+        if !old.args.iter().any(|a| a.name == new_arg.name) {
+            let required = new_arg.required && new_arg.default.is_none();
+            change(Some(&new_arg.name), ChangeKind::ArgAdded { required });
+        }
+    }
+    for old_sub in &old.subcommands {
+        path.push(old_sub.name.clone());
+        match new.subcommands.iter().find(|s| s.name == old_sub.name) {
+            Some(new_sub) => diff_command(path, old_sub, new_sub, changes),
+            None => changes.push(Change {
+                command: path.clone(),
+                arg: None,
+                kind: ChangeKind::SubcommandRemoved,
+            }),
+        }
+        path.pop();
+    }
+    for new_sub in &new.subcommands {
+        if !old.subcommands.iter().any(|s| s.name == new_sub.name) {
+            path.push(new_sub.name.clone());
+            changes.push(Change {
+                command: path.clone(),
+                arg: None,
+                kind: ChangeKind::SubcommandAdded,
+            });
+            path.pop();
+        }
+    }
+}
+
+fn diff_arg(old: &ArgSpec, new: &ArgSpec) -> Vec<ChangeKind> {
+    let mut kinds = Vec::new();
+    if old.long != new.long {
+        kinds.push(ChangeKind::LongChanged {
+            old: old.long.clone(),
+            new: new.long.clone(),
+        });
+    }
+    if old.short != new.short {
+        kinds.push(ChangeKind::ShortChanged {
+            old: old.short,
+            new: new.short,
+        });
+    }
+    let (removed, added) = diff_aliases(&old.aliases, &new.aliases);
+    if !removed.is_empty() {
+        kinds.push(ChangeKind::AliasesRemoved(removed));
+    }
+    if !added.is_empty() {
+        kinds.push(ChangeKind::AliasesAdded(added));
+    }
+    if old.positional != new.positional {
+        kinds.push(ChangeKind::PositionalChanged {
+            now: new.positional,
+        });
+    }
+    if old.takes_value != new.takes_value {
+        kinds.push(ChangeKind::TakesValueChanged {
+            now: new.takes_value,
+        });
+    }
+    if old.multiple != new.multiple {
+        kinds.push(ChangeKind::MultipleChanged { now: new.multiple });
+    }
+    let old_required = old.required && old.default.is_none();
+    let new_required = new.required && new.default.is_none();
+    if old_required != new_required {
+        kinds.push(ChangeKind::RequiredChanged { now: new_required });
+    }
+    if old.default != new.default {
+        kinds.push(ChangeKind::DefaultChanged {
+            old: old.default.clone(),
+            new: new.default.clone(),
+        });
+    }
+    match (old.choices.is_empty(), new.choices.is_empty()) {
+        (true, false) => kinds.push(ChangeKind::ChoicesRestricted),
+        (false, true) => kinds.push(ChangeKind::ChoicesLifted),
+        (false, false) => {
+            let removed: Vec<String> = old
+                .choices
+                .iter()
+                .filter(|c| !new.choices.contains(c))
+                .cloned()
+                .collect();
+            let added: Vec<String> = new
+                .choices
+                .iter()
+                .filter(|c| !old.choices.contains(c))
+                .cloned()
+                .collect();
+            if !removed.is_empty() {
+                kinds.push(ChangeKind::ChoicesRemoved(removed));
+            }
+            if !added.is_empty() {
+                kinds.push(ChangeKind::ChoicesAdded(added));
+            }
+        }
+        (true, true) => {}
+    }
+    if old.range != new.range {
+        kinds.push(ChangeKind::RangeChanged {
+            old: old.range,
+            new: new.range,
+        });
+    }
+    kinds
+}
+
+fn diff_aliases(old: &[Alias], new: &[Alias]) -> (Vec<String>, Vec<String>) {
+    let missing = |from: &[Alias], to: &[Alias]| {
+        from.iter()
+            .filter(|a| !to.iter().any(|b| b.name == a.name))
+            .map(|a| a.name.clone())
+            .collect()
+    };
+    (missing(old, new), missing(new, old))
+}
+
+fn covers(outer: Option<ValueRange>, inner: Option<ValueRange>) -> bool {
+    let Some(outer) = outer else {
+        return true;
+    };
+    let Some(inner) = inner else {
+        return false;
+    };
+    let low = match (outer.min, inner.min) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(outer), Some(inner)) => outer <= inner,
+    };
+    let high = match (outer.max, inner.max) {
+        (None, _) => true,
+        (Some(_), None) => false,
+        (Some(outer), Some(inner)) => outer >= inner,
+    };
+    low && high
+}
@@ -0,0 +1,53 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::{ArgMatches, ArgParser, ParseError};
+
+pub trait ValueStore {
+    fn insert(&mut self, name: &str, value: &str);
+    fn get(&self, name: &str) -> Option<&str>;
+}
+
+impl ValueStore for HashMap<String, String> {
+    fn insert(&mut self, name: &str, value: &str) {
+        HashMap::insert(self, name.to_string(), value.to_string());
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        HashMap::get(self, name).map(String::as_str)
+    }
+}
+
+impl ValueStore for BTreeMap<String, String> {
+    fn insert(&mut self, name: &str, value: &str) {
+        BTreeMap::insert(self, name.to_string(), value.to_string());
+    }
+
+    fn get(&self, name: &str) -> Option<&str> {
+        BTreeMap::get(self, name).map(String::as_str)
+    }
+}
+
+impl ArgMatches {
+    pub fn store_into<S: ValueStore>(&self, store: &mut S) {
+        for (name, value) in &self.values {
+            store.insert(name, value);
+        }
+        for (name, set) in &self.flags {
+            store.insert(name, if *set { "true" } else { "false" });
+        }
+        if let Some((_, sub)) = self.subcommand() {
+            sub.store_into(store);
+        }
+    }
+}
+
+impl ArgParser {
+    pub fn try_parse_into<S: ValueStore>(
+        &self,
+        args: &[String],
+        store: &mut S,
+    ) -> Result<(), ParseError> {
+        self.try_parse(args)?.store_into(store);
+        Ok(())
+    }
+}
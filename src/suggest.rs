@@ -0,0 +1,54 @@
+pub(crate) fn levenshtein(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut previous = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous + usize::from(ca != *cb);
+            previous = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(previous + 1);
+        }
+    }
+    row[b.len()]
+}
+
+pub(crate) fn closest<'a, I>(input: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let threshold = input.chars().count().div_ceil(3).max(1);
+    candidates
+        .into_iter()
+        .map(|candidate| (levenshtein(input, candidate), candidate))
+        .filter(|(distance, _)| *distance <= threshold)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, candidate)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levenshtein_counts_edits() {
+        assert_eq!(levenshtein("verbose", "verbose"), 0);
+        assert_eq!(levenshtein("verbos", "verbose"), 1);
+        assert_eq!(levenshtein("kitten", "sitting"), 3);
+        assert_eq!(levenshtein("", "abc"), 3);
+        assert_eq!(levenshtein("größe", "grösse"), 2);
+    }
+
+    #[test]
+    fn closest_picks_the_nearest_candidate() {
+        let candidates = ["verbose", "version", "output"];
+        assert_eq!(closest("verbos", candidates), Some("verbose"));
+        assert_eq!(closest("outptu", candidates), Some("output"));
+    }
+
+    #[test]
+    fn closest_ignores_distant_candidates() {
+        assert_eq!(closest("xyz", ["verbose", "output"]), None);
+        assert_eq!(closest("a", ["bc"]), None);
+    }
+}
@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use crate::Arg;
+
+pub(crate) fn is_template(template: &str, known: &[&Arg]) -> bool {
+    placeholders(template)
+        .iter()
+        .any(|(_, name)| known.iter().any(|a| a.name == *name))
+}
+
+pub(crate) fn expand(
+    template: &str,
+    known: &[&Arg],
+    values: &HashMap<String, String>,
+    flags: &HashMap<String, bool>,
+) -> Option<String> {
+    let mut out = String::new();
+    let mut rest = 0;
+    for (start, name) in placeholders(template) {
+        let Some(arg) = known.iter().find(|a| a.name == name) else {
+            continue;
+        };
+        let value = if arg.takes_value {
+            values.get(name)?.clone()
+        } else {
+            flags.get(name).copied().unwrap_or(false).to_string()
+        };
+        out.push_str(&template[rest..start]);
+        out.push_str(&value);
+        rest = start + name.len() + 2;
+    }
+    out.push_str(&template[rest..]);
+    Some(out)
+}
+
+fn placeholders(template: &str) -> Vec<(usize, &str)> {
+    let mut found = Vec::new();
+    let mut offset = 0;
+    while let Some(open) = template[offset..].find('{') {
+        let start = offset + open;
+        let Some(close) = template[start + 1..].find(['{', '}']) else {
+            break;
+        };
+        let end = start + 1 + close;
+        if template[end..].starts_with('}') {
+            found.push((start, &template[start + 1..end]));
+            offset = end + 1;
+        } else {
+            offset = end;
+        }
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args() -> Vec<Arg> {
+        let mut dir = Arg::new("dir");
+        dir.takes_value = true;
+        vec![dir, Arg::new("force")]
+    }
+
+    #[test]
+    fn placeholders_skip_unbalanced_braces() {
+        assert_eq!(placeholders("{a}/{b}"), vec![(0, "a"), (4, "b")]);
+        assert_eq!(placeholders("{{a}"), vec![(1, "a")]);
+        assert!(placeholders("{open").is_empty());
+    }
+
+    #[test]
+    fn expands_known_names_only() {
+        let args = args();
+        let known: Vec<&Arg> = args.iter().collect();
+        let values = HashMap::from([("dir".to_string(), "/tmp".to_string())]);
+        let flags = HashMap::from([("force".to_string(), true)]);
+        assert!(is_template("{dir}/out", &known));
+        assert!(!is_template("{other}", &known));
+        assert_eq!(
+            expand("{dir}/{other}/{force}", &known, &values, &flags).as_deref(),
+            Some("/tmp/{other}/true")
+        );
+    }
+
+    #[test]
+    fn missing_values_leave_template_unexpanded() {
+        let args = args();
+        let known: Vec<&Arg> = args.iter().collect();
+        assert_eq!(
+            expand("{dir}", &known, &HashMap::new(), &HashMap::new()),
+            None
+        );
+        assert_eq!(
+            expand("{force}", &known, &HashMap::new(), &HashMap::new()).as_deref(),
+            Some("false")
+        );
+    }
+}
@@ -0,0 +1,313 @@
+use std::borrow::Cow;
+use std::io::IsTerminal;
+use std::sync::{Mutex, OnceLock};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ColorChoice {
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Stream {
+    Stdout,
+    Stderr,
+}
+
+impl Stream {
+    fn is_terminal(self) -> bool {
+        match self {
+            Stream::Stdout => std::io::stdout().is_terminal(),
+            Stream::Stderr => std::io::stderr().is_terminal(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ColorDepth {
+    None,
+    Basic,
+    Ansi256,
+    TrueColor,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TermCaps {
+    pub interactive: bool,
+    pub color: ColorDepth,
+    pub width: Option<usize>,
+    pub unicode: bool,
+    pub hyperlinks: bool,
+}
+
+fn caps_override() -> &'static Mutex<Option<TermCaps>> {
+    static OVERRIDE: OnceLock<Mutex<Option<TermCaps>>> = OnceLock::new();
+    OVERRIDE.get_or_init(Default::default)
+}
+
+pub fn set_term_caps(caps: Option<TermCaps>) {
+    *caps_override().lock().unwrap_or_else(|e| e.into_inner()) = caps;
+}
+
+impl TermCaps {
+    pub fn plain() -> Self {
+        TermCaps {
+            interactive: false,
+            color: ColorDepth::None,
+            width: None,
+            unicode: false,
+            hyperlinks: false,
+        }
+    }
+
+    pub fn get(stream: Stream) -> Self {
+        let forced = *caps_override().lock().unwrap_or_else(|e| e.into_inner());
+        forced.unwrap_or_else(|| TermCaps::detect(stream))
+    }
+
+    pub fn detect(stream: Stream) -> Self {
+        let var = |name: &str| std::env::var(name).ok().filter(|v| !v.is_empty());
+        let term = var("TERM").unwrap_or_default();
+        let is_terminal = stream.is_terminal();
+        let color = if !is_terminal
+            || var("NO_COLOR").is_some()
+            || term == "dumb"
+            || !enable_virtual_terminal()
+        {
+            ColorDepth::None
+        } else if var("COLORTERM").is_some_and(|v| v == "truecolor" || v == "24bit")
+            || var("WT_SESSION").is_some()
+        {
+            ColorDepth::TrueColor
+        } else if term.contains("256color") {
+            ColorDepth::Ansi256
+        } else {
+            ColorDepth::Basic
+        };
+        let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+            .into_iter()
+            .find_map(var)
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let unicode = if cfg!(windows) {
+            var("WT_SESSION").is_some() || term.starts_with("xterm")
+        } else {
+            locale.contains("utf-8") || locale.contains("utf8")
+        };
+        let hyperlinks = match var("FORCE_HYPERLINK") {
+            Some(forced) => forced != "0",
+            None => {
+                color != ColorDepth::None
+                    && (var("WT_SESSION").is_some()
+                        || var("VTE_VERSION")
+                            .and_then(|v| v.parse::<u32>().ok())
+                            .is_some_and(|v| v >= 5000)
+                        || term.contains("kitty")
+                        || var("TERM_PROGRAM").is_some_and(|p| {
+                            matches!(p.as_str(), "iTerm.app" | "WezTerm" | "vscode")
+                        }))
+            }
+        };
+        let width = var("COLUMNS")
+            .and_then(|v| v.parse().ok())
+            .or_else(|| platform::terminal_width(stream).filter(|_| is_terminal))
+            .filter(|&width| width > 0);
+        TermCaps {
+            interactive: is_terminal && std::io::stdin().is_terminal(),
+            color,
+            width,
+            unicode,
+            hyperlinks,
+        }
+    }
+
+    pub fn glyph<'a>(&self, unicode: &'a str, ascii: &'a str) -> &'a str {
+        if self.unicode {
+            unicode
+        } else {
+            ascii
+        }
+    }
+
+    pub fn link(&self, text: &str, url: &str) -> String {
+        if self.hyperlinks {
+            format!("\x1b]8;;{}\x1b\\{}\x1b]8;;\x1b\\", url, text)
+        } else if text == url {
+            text.to_string()
+        } else {
+            format!("{} <{}>", text, url)
+        }
+    }
+}
+
+impl ColorChoice {
+    pub fn use_color(self, stream: Stream) -> bool {
+        match self {
+            ColorChoice::Always => enable_virtual_terminal(),
+            ColorChoice::Never => false,
+            ColorChoice::Auto => TermCaps::get(stream).color != ColorDepth::None,
+        }
+    }
+
+    pub fn prepare<'a>(self, text: &'a str, stream: Stream) -> Cow<'a, str> {
+        if self.use_color(stream) {
+            Cow::Borrowed(text)
+        } else {
+            strip_ansi(text)
+        }
+    }
+}
+
+pub fn enable_virtual_terminal() -> bool {
+    static ENABLED: OnceLock<bool> = OnceLock::new();
+    *ENABLED.get_or_init(platform::enable_virtual_terminal)
+}
+
+pub fn strip_ansi(text: &str) -> Cow<'_, str> {
+    if !text.contains('\x1b') {
+        return Cow::Borrowed(text);
+    }
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '\x1b' {
+            out.push(c);
+            continue;
+        }
+        match chars.peek() {
+            Some('[') => {
+                chars.next();
+                for c in chars.by_ref() {
+                    if ('\x40'..='\x7e').contains(&c) {
+                        break;
+                    }
+                }
+            }
+            Some(']') => {
+                chars.next();
+                while let Some(c) = chars.next() {
+                    if c == '\x07' {
+                        break;
+                    }
+                    if c == '\x1b' && chars.peek() == Some(&'\\') {
+                        chars.next();
+                        break;
+                    }
+                }
+            }
+            Some(_) => {
+                chars.next();
+            }
+            None => {}
+        }
+    }
+    Cow::Owned(out)
+}
+
+#[cfg(windows)]
+mod platform {
+    use std::ffi::c_void;
+
+    const STD_OUTPUT_HANDLE: u32 = -11i32 as u32;
+    const STD_ERROR_HANDLE: u32 = -12i32 as u32;
+    const ENABLE_VIRTUAL_TERMINAL_PROCESSING: u32 = 0x0004;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetStdHandle(std_handle: u32) -> *mut c_void;
+        fn GetConsoleMode(handle: *mut c_void, mode: *mut u32) -> i32;
+        fn SetConsoleMode(handle: *mut c_void, mode: u32) -> i32;
+    }
+
+    pub(super) fn enable_virtual_terminal() -> bool {
+        [STD_OUTPUT_HANDLE, STD_ERROR_HANDLE]
+            .into_iter()
+            .all(|std_handle| unsafe {
+                let handle = GetStdHandle(std_handle);
+                let mut mode = 0;
+                if handle.is_null() || GetConsoleMode(handle, &mut mode) == 0 {
+                    // Not a console (redirected or a mintty pipe); escapes pass through untouched.
+                    return true;
+                }
+                mode & ENABLE_VIRTUAL_TERMINAL_PROCESSING != 0
+                    || SetConsoleMode(handle, mode | ENABLE_VIRTUAL_TERMINAL_PROCESSING) != 0
+            })
+    }
+
+    #[repr(C)]
+    #[derive(Default)]
+    struct ScreenBufferInfo {
+        size: [i16; 2],
+        cursor: [i16; 2],
+        attributes: u16,
+        window: [i16; 4],
+        max_window: [i16; 2],
+    }
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetConsoleScreenBufferInfo(handle: *mut c_void, info: *mut ScreenBufferInfo) -> i32;
+    }
+
+    pub(super) fn terminal_width(stream: super::Stream) -> Option<usize> {
+        let std_handle = match stream {
+            super::Stream::Stdout => STD_OUTPUT_HANDLE,
+            super::Stream::Stderr => STD_ERROR_HANDLE,
+        };
+        let mut info = ScreenBufferInfo::default();
+        unsafe {
+            let handle = GetStdHandle(std_handle);
+            if handle.is_null() || GetConsoleScreenBufferInfo(handle, &mut info) == 0 {
+                return None;
+            }
+        }
+        usize::try_from(info.window[2] - info.window[0] + 1).ok()
+    }
+}
+
+#[cfg(not(windows))]
+mod platform {
+    pub(super) fn enable_virtual_terminal() -> bool {
+        true
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    pub(super) fn terminal_width(stream: super::Stream) -> Option<usize> {
+        use std::ffi::{c_int, c_ulong};
+
+        #[cfg(target_os = "linux")]
+        const TIOCGWINSZ: c_ulong = 0x5413;
+        #[cfg(target_os = "macos")]
+        const TIOCGWINSZ: c_ulong = 0x4008_7468;
+
+        #[repr(C)]
+        #[derive(Default)]
+        struct WinSize {
+            rows: u16,
+            cols: u16,
+            x_pixels: u16,
+            y_pixels: u16,
+        }
+
+        extern "C" {
+            fn ioctl(fd: c_int, request: c_ulong, ...) -> c_int;
+        }
+
+        let fd = match stream {
+            super::Stream::Stdout => 1,
+            super::Stream::Stderr => 2,
+        };
+        let mut size = WinSize::default();
+        if unsafe { ioctl(fd, TIOCGWINSZ, &mut size) } != 0 {
+            return None;
+        }
+        Some(usize::from(size.cols))
+    }
+
+    #[cfg(not(any(target_os = "linux", target_os = "macos")))]
+    pub(super) fn terminal_width(_stream: super::Stream) -> Option<usize> {
+        None
+    }
+}
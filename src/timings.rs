@@ -0,0 +1,92 @@
+#[cfg(feature = "timings")]
+use std::sync::Mutex;
+#[cfg(feature = "timings")]
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "timings")]
+use crate::{display_width, pad_to_width, ArgMatches, ArgParser};
+
+#[cfg(feature = "timings")]
+pub const DEBUG_TIMINGS: &str = "debug-timings";
+
+#[cfg(feature = "timings")]
+static TIMINGS: Mutex<Vec<(String, Duration)>> = Mutex::new(Vec::new());
+
+#[cfg(feature = "timings")]
+pub(crate) fn record(label: &str, duration: Duration) {
+    TIMINGS
+        .lock()
+        .unwrap_or_else(|e| e.into_inner())
+        .push((label.to_string(), duration));
+}
+
+#[cfg(feature = "timings")]
+pub(crate) fn time<T>(label: &str, f: impl FnOnce() -> T) -> T {
+    let start = Instant::now();
+    let result = f();
+    record(label, start.elapsed());
+    result
+}
+
+#[cfg(not(feature = "timings"))]
+pub(crate) fn time<T>(_label: &str, f: impl FnOnce() -> T) -> T {
+    f()
+}
+
+#[cfg(feature = "timings")]
+pub fn timings() -> Vec<(String, Duration)> {
+    TIMINGS.lock().unwrap_or_else(|e| e.into_inner()).clone()
+}
+
+#[cfg(feature = "timings")]
+pub fn reset_timings() {
+    TIMINGS.lock().unwrap_or_else(|e| e.into_inner()).clear();
+}
+
+#[cfg(feature = "timings")]
+pub fn timing_report() -> String {
+    let timings = timings();
+    let width = timings
+        .iter()
+        .map(|(label, _)| display_width(label))
+        .max()
+        .unwrap_or(0);
+    let mut out = String::from("timings:\n");
+    for (label, duration) in &timings {
+        out.push_str(&format!(
+            "  {}  {:>10.3} ms\n",
+            pad_to_width(label, width),
+            duration.as_secs_f64() * 1000.0
+        ));
+    }
+    let total: Duration = timings.iter().map(|(_, duration)| *duration).sum();
+    out.push_str(&format!(
+        "  {}  {:>10.3} ms\n",
+        pad_to_width("total", width),
+        total.as_secs_f64() * 1000.0
+    ));
+    out
+}
+
+#[cfg(feature = "timings")]
+impl ArgParser {
+    pub fn debug_timings_flag(self) -> Self {
+        self.arg(DEBUG_TIMINGS)
+            .long(DEBUG_TIMINGS, DEBUG_TIMINGS)
+            .global(DEBUG_TIMINGS)
+            .hidden(DEBUG_TIMINGS)
+    }
+
+    pub(crate) fn record_construction(&self) {
+        if self.construction_recorded.set(()).is_ok() {
+            record("construction", self.created.elapsed());
+        }
+    }
+}
+
+#[cfg(feature = "timings")]
+impl ArgMatches {
+    pub fn debug_timings(&self) -> bool {
+        self.flags.get(DEBUG_TIMINGS).copied().unwrap_or(false)
+    }
+}
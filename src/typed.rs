@@ -0,0 +1,336 @@
+use crate::{
+    convert, validation, Alias, Arg, ArgParser, Capture, Condition, DuplicatePolicy, FromArgValue,
+    Validation,
+};
+
+pub struct Flag(Arg);
+
+pub struct Opt(Arg);
+
+pub struct Positional(Arg);
+
+impl Flag {
+    pub fn new(name: &str) -> Self {
+        Flag(Arg::new(name))
+    }
+
+    pub fn short(mut self, short: char) -> Self {
+        self.0.short = Some(short);
+        self
+    }
+
+    pub fn long(mut self, long: &str) -> Self {
+        self.0.long = Some(long.to_string());
+        self
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.0.help = Some(help.to_string());
+        self
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.0.aliases.push(Alias {
+            name: alias.to_string(),
+            visible: false,
+        });
+        self
+    }
+
+    pub fn visible_alias(mut self, alias: &str) -> Self {
+        self.0.aliases.push(Alias {
+            name: alias.to_string(),
+            visible: true,
+        });
+        self
+    }
+
+    pub fn env(mut self, var: &str) -> Self {
+        self.0.env = Some(var.to_string());
+        self
+    }
+
+    pub fn negatable(mut self) -> Self {
+        self.0.negatable = true;
+        self
+    }
+
+    pub fn global(mut self) -> Self {
+        self.0.global = true;
+        self
+    }
+
+    pub fn hide_when(mut self, condition: Condition) -> Self {
+        self.0.hidden = Some(condition);
+        self
+    }
+
+    pub fn on_duplicate(mut self, policy: DuplicatePolicy) -> Self {
+        self.0.on_duplicate = policy;
+        self
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.0.multiple = true;
+        self
+    }
+}
+
+impl Opt {
+    pub fn new(name: &str) -> Self {
+        let mut arg = Arg::new(name);
+        arg.takes_value = true;
+        Opt(arg)
+    }
+
+    pub fn short(mut self, short: char) -> Self {
+        self.0.short = Some(short);
+        self
+    }
+
+    pub fn long(mut self, long: &str) -> Self {
+        self.0.long = Some(long.to_string());
+        self
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.0.help = Some(help.to_string());
+        self
+    }
+
+    pub fn alias(mut self, alias: &str) -> Self {
+        self.0.aliases.push(Alias {
+            name: alias.to_string(),
+            visible: false,
+        });
+        self
+    }
+
+    pub fn visible_alias(mut self, alias: &str) -> Self {
+        self.0.aliases.push(Alias {
+            name: alias.to_string(),
+            visible: true,
+        });
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.0.required = true;
+        self
+    }
+
+    pub fn default(mut self, default: &str) -> Self {
+        self.0.default = Some(default.to_string());
+        self
+    }
+
+    pub fn default_when(mut self, condition: Condition, default: &str) -> Self {
+        self.0
+            .conditional_defaults
+            .push((condition, default.to_string()));
+        self
+    }
+
+    pub fn env(mut self, var: &str) -> Self {
+        self.0.env = Some(var.to_string());
+        self
+    }
+
+    pub fn validator<F, R>(mut self, validator: F) -> Self
+    where
+        F: 'static + Fn(&str) -> R + Send + Sync,
+        R: Validation,
+    {
+        self.0.validator = Some(validation::validator(validator));
+        self
+    }
+
+    pub fn value_parser<T: FromArgValue>(mut self) -> Self {
+        self.0.value_parser = Some(convert::value_parser::<T>(&self.0.name));
+        self
+    }
+
+    pub fn choices(mut self, choices: &[&str]) -> Self {
+        self.0.choices = choices.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn ignore_case(mut self) -> Self {
+        self.0.ignore_case = true;
+        self
+    }
+
+    pub fn prefix_choices(mut self) -> Self {
+        self.0.prefix_choices = true;
+        self
+    }
+
+    pub fn path(mut self) -> Self {
+        self.0.is_path = true;
+        self
+    }
+
+    pub fn sensitive(mut self) -> Self {
+        self.0.sensitive = true;
+        self
+    }
+
+    pub fn global(mut self) -> Self {
+        self.0.global = true;
+        self
+    }
+
+    pub fn hide_when(mut self, condition: Condition) -> Self {
+        self.0.hidden = Some(condition);
+        self
+    }
+
+    pub fn example(mut self, example: &str) -> Self {
+        self.0.examples.push(example.to_string());
+        self
+    }
+
+    pub fn on_duplicate(mut self, policy: DuplicatePolicy) -> Self {
+        self.0.on_duplicate = policy;
+        self
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.0.multiple = true;
+        self
+    }
+
+    pub fn value_delimiter(mut self, delimiter: char) -> Self {
+        self.0.value_delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn num_values(mut self, count: usize) -> Self {
+        self.0.num_values = count.max(1);
+        self
+    }
+}
+
+impl Positional {
+    pub fn new(name: &str) -> Self {
+        let mut arg = Arg::new(name);
+        arg.takes_value = true;
+        arg.positional = true;
+        Positional(arg)
+    }
+
+    pub fn help(mut self, help: &str) -> Self {
+        self.0.help = Some(help.to_string());
+        self
+    }
+
+    pub fn capture(mut self, capture: Capture) -> Self {
+        self.0.capture = capture;
+        self
+    }
+
+    pub fn required(mut self) -> Self {
+        self.0.required = true;
+        self
+    }
+
+    pub fn default(mut self, default: &str) -> Self {
+        self.0.default = Some(default.to_string());
+        self
+    }
+
+    pub fn env(mut self, var: &str) -> Self {
+        self.0.env = Some(var.to_string());
+        self
+    }
+
+    pub fn validator<F, R>(mut self, validator: F) -> Self
+    where
+        F: 'static + Fn(&str) -> R + Send + Sync,
+        R: Validation,
+    {
+        self.0.validator = Some(validation::validator(validator));
+        self
+    }
+
+    pub fn value_parser<T: FromArgValue>(mut self) -> Self {
+        self.0.value_parser = Some(convert::value_parser::<T>(&self.0.name));
+        self
+    }
+
+    pub fn choices(mut self, choices: &[&str]) -> Self {
+        self.0.choices = choices.iter().map(|c| c.to_string()).collect();
+        self
+    }
+
+    pub fn ignore_case(mut self) -> Self {
+        self.0.ignore_case = true;
+        self
+    }
+
+    pub fn prefix_choices(mut self) -> Self {
+        self.0.prefix_choices = true;
+        self
+    }
+
+    pub fn path(mut self) -> Self {
+        self.0.is_path = true;
+        self
+    }
+
+    pub fn sensitive(mut self) -> Self {
+        self.0.sensitive = true;
+        self
+    }
+
+    pub fn example(mut self, example: &str) -> Self {
+        self.0.examples.push(example.to_string());
+        self
+    }
+
+    pub fn multiple(mut self) -> Self {
+        self.0.multiple = true;
+        self
+    }
+
+    pub fn value_delimiter(mut self, delimiter: char) -> Self {
+        self.0.value_delimiter = Some(delimiter);
+        self
+    }
+
+    pub fn glob_expand(mut self, expand: bool) -> Self {
+        self.0.glob_expand = expand;
+        self
+    }
+
+    pub fn trailing_var_arg(mut self) -> Self {
+        self.0.multiple = true;
+        self.0.trailing_var_arg = true;
+        self
+    }
+}
+
+impl From<Flag> for Arg {
+    fn from(flag: Flag) -> Self {
+        flag.0
+    }
+}
+
+impl From<Opt> for Arg {
+    fn from(opt: Opt) -> Self {
+        opt.0
+    }
+}
+
+impl From<Positional> for Arg {
+    fn from(positional: Positional) -> Self {
+        positional.0
+    }
+}
+
+impl ArgParser {
+    pub fn with_arg(mut self, arg: impl Into<Arg>) -> Self {
+        self.args.push(arg.into());
+        self
+    }
+}
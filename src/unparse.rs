@@ -0,0 +1,149 @@
+use crate::{join, Arg, ArgMatches, ArgParser, ValueSource, REDACTED};
+
+impl ArgParser {
+    pub fn unparse(&self, matches: &ArgMatches) -> Vec<String> {
+        self.unparse_with(matches, &[], &|name, value| {
+            if sensitive_at_any_level(matches, name) {
+                REDACTED.to_string()
+            } else {
+                value.to_string()
+            }
+        })
+    }
+
+    fn unparse_with<F>(&self, matches: &ArgMatches, globals: &[&Arg], show: &F) -> Vec<String>
+    where
+        F: Fn(&str, &str) -> String,
+    {
+        let given = |name: &str| matches.value_source(name) == Some(&ValueSource::CommandLine);
+        let mut tokens = Vec::new();
+        let options = self.args.iter().chain(globals.iter().copied());
+        for arg in options.filter(|a| !a.positional && given(&a.name)) {
+            let flag = match (&arg.long, arg.short) {
+                (Some(long), _) => format!("--{}", long),
+                (None, Some(short)) => format!("-{}", short),
+                (None, None) => continue,
+            };
+            if arg.key_value {
+                for (key, value) in matches.key_values(&arg.name) {
+                    let entry = match value {
+                        Some(value) => format!("{}={}", key, value),
+                        None => key.clone(),
+                    };
+                    tokens.push(flag.clone());
+                    tokens.push(show(&arg.name, &entry));
+                }
+            } else if arg.num_values > 1 {
+                for group in matches.get_occurrences(&arg.name) {
+                    tokens.push(flag.clone());
+                    tokens.extend(group.iter().map(|value| show(&arg.name, value)));
+                }
+            } else if let Some(delimiter) = arg.value_delimiter.filter(|_| !arg.multiple) {
+                let joined = matches.get_many(&arg.name).join(&delimiter.to_string());
+                tokens.push(flag);
+                tokens.push(show(&arg.name, &joined));
+            } else if arg.takes_value {
+                for value in matches.get_many(&arg.name) {
+                    tokens.push(flag.clone());
+                    tokens.push(show(&arg.name, &value));
+                }
+            } else if matches.flags.get(&arg.name) == Some(&true) {
+                let count = if arg.multiple {
+                    matches.occurrences_of(&arg.name)
+                } else {
+                    1
+                };
+                tokens.extend(std::iter::repeat_n(flag, count));
+            } else if let Some(long) = arg.long.as_ref().filter(|_| arg.negatable) {
+                tokens.push(format!("--no-{}", long));
+            }
+        }
+        let mut words = Vec::new();
+        for arg in self.args.iter().filter(|a| a.positional) {
+            match matches.values.get(&arg.name) {
+                Some(_) if given(&arg.name) && arg.value_delimiter.is_some() => {
+                    let delimiter = arg.value_delimiter.unwrap_or_default().to_string();
+                    words.push(show(
+                        &arg.name,
+                        &matches.get_many(&arg.name).join(&delimiter),
+                    ));
+                }
+                Some(_) if given(&arg.name) && arg.multiple => words.extend(
+                    matches
+                        .get_many(&arg.name)
+                        .iter()
+                        .map(|value| show(&arg.name, value)),
+                ),
+                Some(value) if given(&arg.name) => words.push(show(&arg.name, value)),
+                _ if arg.multiple => continue,
+                _ => break,
+            }
+        }
+        words.extend(matches.positionals.iter().cloned());
+        if matches.subcommand().is_none()
+            && !self.dash_sections
+            && words.iter().any(|w| w.starts_with('-'))
+        {
+            tokens.push("--".to_string());
+        }
+        tokens.extend(words);
+        for section in matches.sections() {
+            tokens.push("--".to_string());
+            tokens.extend(section.iter().cloned());
+        }
+        if let Some((name, sub_matches)) = matches.subcommand() {
+            let sub = &self.subcommands[name];
+            let inherited: Vec<&Arg> = self
+                .args
+                .iter()
+                .chain(globals.iter().copied())
+                .filter(|a| a.global && !given(&a.name))
+                .filter(|a| !sub.args.iter().any(|s| s.name == a.name))
+                .collect();
+            tokens.push(name.to_string());
+            tokens.extend(sub.unparse_with(sub_matches, &inherited, show));
+        }
+        tokens
+    }
+
+    pub fn check_roundtrip(&self, matches: &ArgMatches) -> Result<(), String> {
+        let tokens = self.unparse(matches);
+        let raw = self.unparse_with(matches, &[], &|_, value| value.to_string());
+        let reparsed = self
+            .parse_args_only(&raw)
+            .map_err(|err| format!("`{}` failed to parse: {}", join(&tokens), err))?;
+        if !same_matches(&reparsed, matches) {
+            return Err(format!(
+                "`{}` parsed to {:?}, expected {:?}",
+                join(&tokens),
+                reparsed,
+                matches
+            ));
+        }
+        Ok(())
+    }
+}
+
+fn same_matches(a: &ArgMatches, b: &ArgMatches) -> bool {
+    let same_sub = match (a.subcommand(), b.subcommand()) {
+        (Some((a_name, a_sub)), Some((b_name, b_sub))) => {
+            a_name == b_name && same_matches(a_sub, b_sub)
+        }
+        (None, None) => true,
+        _ => false,
+    };
+    same_sub
+        && a.values == b.values
+        && a.flags == b.flags
+        && a.positionals == b.positionals
+        && a.sections == b.sections
+        && a.key_values == b.key_values
+        && a.multi_values == b.multi_values
+}
+
+fn sensitive_at_any_level(matches: &ArgMatches, name: &str) -> bool {
+    matches.is_sensitive(name)
+        || matches
+            .subcommand()
+            .is_some_and(|(_, sub)| sensitive_at_any_level(sub, name))
+}
@@ -0,0 +1,37 @@
+use std::sync::Arc;
+
+use crate::Validator;
+
+pub trait Validation {
+    fn into_result(self) -> Result<(), String>;
+}
+
+impl Validation for bool {
+    fn into_result(self) -> Result<(), String> {
+        if self {
+            Ok(())
+        } else {
+            Err(String::new())
+        }
+    }
+}
+
+impl Validation for Result<(), String> {
+    fn into_result(self) -> Result<(), String> {
+        self
+    }
+}
+
+impl Validation for Result<(), &'static str> {
+    fn into_result(self) -> Result<(), String> {
+        self.map_err(str::to_string)
+    }
+}
+
+pub(crate) fn validator<F, R>(validator: F) -> Validator
+where
+    F: 'static + Fn(&str) -> R + Send + Sync,
+    R: Validation,
+{
+    Arc::new(move |value| validator(value).into_result())
+}
@@ -0,0 +1,193 @@
+use std::cmp::Ordering;
+use std::collections::BTreeMap;
+
+use crate::source::parse_bool;
+use crate::{Arg, ArgParser, Condition};
+
+pub(crate) fn compare_versions(a: &str, b: &str) -> Ordering {
+    let mut left = a.split('.');
+    let mut right = b.split('.');
+    loop {
+        let ordering = match (left.next(), right.next()) {
+            (None, None) => return Ordering::Equal,
+            (l, r) => {
+                let l = l.unwrap_or("0");
+                let r = r.unwrap_or("0");
+                match (l.parse::<u64>(), r.parse::<u64>()) {
+                    (Ok(l), Ok(r)) => l.cmp(&r),
+                    _ => l.cmp(r),
+                }
+            }
+        };
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+}
+
+impl Arg {
+    pub fn is_removed(&self, version: Option<&str>) -> bool {
+        match (&self.removed_in, version) {
+            (Some(removed), Some(version)) => compare_versions(version, removed) != Ordering::Less,
+            _ => false,
+        }
+    }
+
+    pub fn is_required(&self, version: Option<&str>) -> bool {
+        match (&self.soft_required, version) {
+            _ if self.required || self.required_when.iter().any(Condition::holds) => true,
+            (Some(target), Some(version)) => compare_versions(version, target) != Ordering::Less,
+            _ => false,
+        }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.experimental
+            .as_ref()
+            .is_none_or(|var| std::env::var(var).ok().and_then(|v| parse_bool(&v)) == Some(true))
+    }
+
+    pub(crate) fn soft_required_warning(&self) -> Option<String> {
+        self.soft_required.as_ref().map(|target| {
+            format!(
+                "{} was not given; it will become required in {}",
+                self.label(),
+                target
+            )
+        })
+    }
+
+    pub(crate) fn label(&self) -> String {
+        match (&self.long, self.short) {
+            (Some(long), _) => format!("--{}", long),
+            (None, Some(short)) => format!("-{}", short),
+            (None, None) => self.display().to_string(),
+        }
+    }
+}
+
+pub(crate) fn replacement_label<'a>(
+    arg: &Arg,
+    known: impl IntoIterator<Item = &'a Arg>,
+) -> Option<String> {
+    let replacement = arg.replaced_by.as_ref()?;
+    Some(
+        known
+            .into_iter()
+            .find(|a| &a.name == replacement)
+            .map(Arg::label)
+            .unwrap_or_else(|| replacement.clone()),
+    )
+}
+
+impl ArgParser {
+    pub fn version(mut self, version: &str) -> Self {
+        self.version = Some(version.to_string());
+        self
+    }
+
+    pub fn introduced_in(mut self, name: &str, version: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.introduced_in = Some(version.to_string());
+        }
+        self
+    }
+
+    pub fn removed_in(mut self, name: &str, version: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.removed_in = Some(version.to_string());
+        }
+        self
+    }
+
+    pub fn replaced_by(mut self, name: &str, replacement: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.replaced_by = Some(replacement.to_string());
+        }
+        self
+    }
+
+    pub fn soft_required(mut self, name: &str, version: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.soft_required = Some(version.to_string());
+        }
+        self
+    }
+
+    pub fn experimental(mut self, name: &str, var: &str) -> Self {
+        if let Some(arg) = self.args.iter_mut().find(|a| a.name == name) {
+            arg.experimental = Some(var.to_string());
+        }
+        self
+    }
+
+    pub fn accept_removed_args(mut self) -> Self {
+        self.accept_removed_args = true;
+        self
+    }
+
+    pub fn render_changelog(&self) -> String {
+        let mut entries: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        self.collect_changelog("", &mut entries);
+        let mut versions: Vec<(String, Vec<String>)> = entries.into_iter().collect();
+        versions.sort_by(|(a, _), (b, _)| compare_versions(b, a));
+        let mut out = String::new();
+        for (version, lines) in versions {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("## {}\n\n", version));
+            for line in lines {
+                out.push_str(&format!("- {}\n", line));
+            }
+        }
+        out
+    }
+
+    fn collect_changelog(&self, prefix: &str, entries: &mut BTreeMap<String, Vec<String>>) {
+        for arg in &self.args {
+            let label = format!("`{}{}`", prefix, arg.label());
+            if let Some(version) = &arg.introduced_in {
+                entries
+                    .entry(version.clone())
+                    .or_default()
+                    .push(format!("Added {}.", label));
+            }
+            if let Some(version) = &arg.removed_in {
+                let line = match replacement_label(arg, &self.args) {
+                    Some(replacement) => format!(
+                        "Removed {}; use `{}{}` instead.",
+                        label, prefix, replacement
+                    ),
+                    None => format!("Removed {}.", label),
+                };
+                entries.entry(version.clone()).or_default().push(line);
+            }
+            if let Some(version) = &arg.soft_required {
+                entries
+                    .entry(version.clone())
+                    .or_default()
+                    .push(format!("{} is now required.", label));
+            }
+        }
+        let mut names: Vec<&String> = self.subcommands.keys().collect();
+        names.sort();
+        for name in names {
+            let prefix = format!("{}{} ", prefix, name);
+            self.subcommands[name].collect_changelog(&prefix, entries);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn versions_compare_numerically() {
+        assert_eq!(compare_versions("1.10", "1.9"), Ordering::Greater);
+        assert_eq!(compare_versions("2.0", "2"), Ordering::Equal);
+        assert_eq!(compare_versions("1.2.3", "1.2.4"), Ordering::Less);
+        assert_eq!(compare_versions("1.0-beta", "1.0-alpha"), Ordering::Greater);
+    }
+}
@@ -0,0 +1,73 @@
+use std::collections::HashSet;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use crate::{ArgMatches, ArgParser};
+
+pub const QUIET_WARNINGS: &str = "quiet-warnings";
+
+pub type WarningHandler = Arc<dyn Fn(&str) + Send + Sync>;
+
+#[derive(Default)]
+struct Warnings {
+    seen: HashSet<String>,
+    handler: Option<WarningHandler>,
+    quiet: bool,
+}
+
+fn warnings() -> &'static Mutex<Warnings> {
+    static WARNINGS: OnceLock<Mutex<Warnings>> = OnceLock::new();
+    WARNINGS.get_or_init(Default::default)
+}
+
+pub fn warn(message: &str) {
+    let handler = {
+        let mut state = warnings().lock().unwrap_or_else(|e| e.into_inner());
+        if state.quiet || !state.seen.insert(message.to_string()) {
+            return;
+        }
+        state.handler.clone()
+    };
+    match handler {
+        Some(handler) => handler(message),
+        None => emit(message),
+    }
+}
+
+#[cfg(feature = "log")]
+fn emit(message: &str) {
+    log::warn!("{}", message);
+}
+
+#[cfg(not(feature = "log"))]
+fn emit(message: &str) {
+    let line = format!("\x1b[33mwarning\x1b[0m: {}", message);
+    eprintln!(
+        "{}",
+        crate::ColorChoice::Auto.prepare(&line, crate::Stream::Stderr)
+    );
+}
+
+pub fn set_warning_handler<F>(handler: F)
+where
+    F: 'static + Fn(&str) + Send + Sync,
+{
+    warnings().lock().unwrap_or_else(|e| e.into_inner()).handler = Some(Arc::new(handler));
+}
+
+pub fn set_quiet_warnings(quiet: bool) {
+    warnings().lock().unwrap_or_else(|e| e.into_inner()).quiet = quiet;
+}
+
+impl ArgParser {
+    pub fn quiet_warnings_flag(self) -> Self {
+        self.arg(QUIET_WARNINGS)
+            .long(QUIET_WARNINGS, QUIET_WARNINGS)
+            .global(QUIET_WARNINGS)
+    }
+}
+
+impl ArgMatches {
+    pub fn quiet_warnings(&self) -> bool {
+        self.flags.get(QUIET_WARNINGS).copied().unwrap_or(false)
+    }
+}
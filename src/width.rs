@@ -0,0 +1,128 @@
+pub fn display_width(s: &str) -> usize {
+    s.chars().map(char_width).sum()
+}
+
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let mut padded = s.to_string();
+    for _ in display_width(s)..width {
+        padded.push(' ');
+    }
+    padded
+}
+
+pub(crate) fn break_units(word: &str) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    let mut after_wide = false;
+    for (i, c) in word.char_indices() {
+        let wide = char_width(c) == 2;
+        if i > start && (wide || (after_wide && char_width(c) > 0)) {
+            units.push(&word[start..i]);
+            start = i;
+        }
+        if char_width(c) > 0 {
+            after_wide = wide;
+        }
+    }
+    units.push(&word[start..]);
+    units
+}
+
+fn char_width(c: char) -> usize {
+    let c = c as u32;
+    if c < 0x20 || (0x7f..0xa0).contains(&c) || is_zero_width(c) {
+        0
+    } else if is_wide(c) {
+        2
+    } else {
+        1
+    }
+}
+
+fn is_zero_width(c: u32) -> bool {
+    matches!(
+        c,
+        0x0300..=0x036f
+            | 0x0483..=0x0489
+            | 0x0591..=0x05bd
+            | 0x0610..=0x061a
+            | 0x064b..=0x065f
+            | 0x1ab0..=0x1aff
+            | 0x1dc0..=0x1dff
+            | 0x200b..=0x200f
+            | 0x20d0..=0x20ff
+            | 0x302a..=0x302f
+            | 0x3099..=0x309a
+            | 0xfe00..=0xfe0f
+            | 0xfe20..=0xfe2f
+            | 0xe0100..=0xe01ef
+    )
+}
+
+fn is_wide(c: u32) -> bool {
+    matches!(
+        c,
+        0x1100..=0x115f
+            | 0x2e80..=0x303e
+            | 0x3041..=0x33ff
+            | 0x3400..=0x4dbf
+            | 0x4e00..=0x9fff
+            | 0xa000..=0xa4cf
+            | 0xac00..=0xd7a3
+            | 0xf900..=0xfaff
+            | 0xfe30..=0xfe4f
+            | 0xff00..=0xff60
+            | 0xffe0..=0xffe6
+            | 0x1f300..=0x1f64f
+            | 0x1f900..=0x1f9ff
+            | 0x20000..=0x2fffd
+            | 0x30000..=0x3fffd
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ascii_is_one_column_per_char() {
+        assert_eq!(display_width("--verbose"), 9);
+    }
+
+    #[test]
+    fn cjk_and_fullwidth_are_two_columns() {
+        assert_eq!(display_width("名前"), 4);
+        assert_eq!(display_width("ｆｕｌｌ"), 8);
+        assert_eq!(display_width("한국어"), 6);
+    }
+
+    #[test]
+    fn combining_marks_and_zero_width_take_no_columns() {
+        assert_eq!(display_width("e\u{301}"), 1);
+        assert_eq!(display_width("a\u{200b}b"), 2);
+        assert_eq!(display_width("\x1b"), 0);
+    }
+
+    #[test]
+    fn pad_to_width_counts_columns_not_bytes() {
+        assert_eq!(pad_to_width("名", 4), "名  ");
+        assert_eq!(pad_to_width("e\u{301}", 3), "e\u{301}  ");
+        assert_eq!(pad_to_width("long", 2), "long");
+    }
+
+    #[test]
+    fn break_units_split_between_wide_characters() {
+        assert_eq!(break_units("hello"), vec!["hello"]);
+        assert_eq!(
+            break_units("設定ファイル"),
+            vec!["設", "定", "フ", "ァ", "イ", "ル"]
+        );
+        assert_eq!(break_units("ab名cd"), vec!["ab", "名", "cd"]);
+    }
+
+    #[test]
+    fn break_units_keep_combining_marks_attached() {
+        assert_eq!(break_units("か\u{3099}き"), vec!["か\u{3099}", "き"]);
+        assert_eq!(break_units("e\u{301}x"), vec!["e\u{301}x"]);
+    }
+}
@@ -0,0 +1,63 @@
+use rust_arguments::{ArgParser, Shell};
+
+fn parser() -> ArgParser {
+    ArgParser::new()
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .help("verbose", "Print [more] output")
+        .arg("level")
+        .long("level", "level")
+        .takes_value("level")
+        .choices("level", &["low", "high"])
+        .arg("include")
+        .short("include", 'I')
+        .long("include", "include")
+        .takes_value("include")
+        .multiple("include")
+        .path("include")
+        .subcommand(
+            "run",
+            ArgParser::new()
+                .with_about("Run the pipeline")
+                .arg("fast")
+                .long("fast", "fast")
+                .help("fast", "Skip checks")
+                .arg("target")
+                .positional("target")
+                .path("target"),
+        )
+        .subcommand_alias("run", &["r"])
+}
+
+#[test]
+fn zsh_completion_is_a_native_arguments_function() {
+    let script = parser().render_completion("my-tool", Shell::Zsh);
+    assert!(script.starts_with("#compdef my-tool\n"));
+    assert!(!script.contains("bashcompinit"));
+    assert!(!script.contains("complete -F"));
+    assert!(script.contains("\n_my_tool() {\n"));
+    assert!(script.contains("\n_my_tool__run() {\n"));
+    assert!(script.contains("    _my_tool \"$@\"\n"));
+    assert!(script.contains("    compdef _my_tool my-tool\n"));
+}
+
+#[test]
+fn zsh_completion_describes_flags_and_values() {
+    let script = parser().render_completion("my-tool", Shell::Zsh);
+    assert!(script.contains("'(--verbose -v)--verbose[Print \\[more\\] output]'"));
+    assert!(script.contains("'(--verbose -v)-v[Print \\[more\\] output]'"));
+    assert!(script.contains("'--level=:level:(low high)'"));
+    assert!(script.contains("'*--include=:include:_files'"));
+    assert!(script.contains("'*-I+:include:_files'"));
+    assert!(script.contains("'--fast[Skip checks]'"));
+    assert!(script.contains(" :target:_files && ret=0\n"));
+}
+
+#[test]
+fn zsh_completion_describes_subcommands() {
+    let script = parser().render_completion("my-tool", Shell::Zsh);
+    assert!(script.contains("'run:Run the pipeline'"));
+    assert!(script.contains("'r:alias of run'"));
+    assert!(script.contains("run|r) _my_tool__run && ret=0 ;;"));
+}
@@ -0,0 +1,90 @@
+#![cfg(feature = "toml")]
+
+use rust_arguments::{ArgParser, ParseError};
+
+fn config_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "rust-arguments-{}-{}.toml",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn parser(path: &str) -> ArgParser {
+    ArgParser::new()
+        .arg("tags")
+        .long("tags", "tags")
+        .takes_value("tags")
+        .multiple("tags")
+        .arg("note")
+        .long("note", "note")
+        .takes_value("note")
+        .arg("host")
+        .long("host", "host")
+        .takes_value("host")
+        .global("host")
+        .with_config_file(path)
+        .subcommand(
+            "run",
+            ArgParser::new()
+                .arg("jobs")
+                .long("jobs", "jobs")
+                .takes_value("jobs"),
+        )
+}
+
+#[test]
+fn multi_line_arrays_and_strings() {
+    let path = config_file(
+        "multiline",
+        "tags = [\n  \"a\",\n  \"b\",\n]\nnote = \"\"\"\nfirst\nsecond\"\"\"\n",
+    );
+    let matches = parser(&path).try_parse_from(Vec::<String>::new()).unwrap();
+    assert_eq!(matches.get_many("tags"), vec!["a", "b"]);
+    assert_eq!(
+        matches.get::<String>("note").as_deref(),
+        Some("first\nsecond")
+    );
+}
+
+#[test]
+fn inline_tables_and_array_tables() {
+    let path = config_file(
+        "tables",
+        "run = { jobs = 4 }\n\n[[plugins]]\nname = \"lint\"\n",
+    );
+    let matches = parser(&path).try_parse_from(["run"]).unwrap();
+    let (_, run) = matches.subcommand().unwrap();
+    assert_eq!(run.get::<u32>("jobs"), Some(4));
+}
+
+#[test]
+fn command_line_overrides_config() {
+    let path = config_file("override", "note = \"from file\"\n");
+    let matches = parser(&path)
+        .try_parse_from(["--note", "from argv"])
+        .unwrap();
+    assert_eq!(matches.get::<String>("note").as_deref(), Some("from argv"));
+}
+
+#[test]
+fn malformed_toml_reports_the_line() {
+    let path = config_file("broken", "note = \"ok\"\ntags = [1,\n");
+    match parser(&path).try_parse_from(Vec::<String>::new()) {
+        Err(ParseError::ConfigFile { line, .. }) => assert!(line.is_some()),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn nested_arrays_are_rejected() {
+    let path = config_file("nested", "tags = [[1, 2]]\n");
+    match parser(&path).try_parse_from(Vec::<String>::new()) {
+        Err(ParseError::ConfigFile { message, .. }) => {
+            assert_eq!(message, "unsupported value for 'tags'")
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
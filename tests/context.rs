@@ -0,0 +1,69 @@
+use rust_arguments::{ArgParser, ParseError, REDACTED};
+
+fn unique_names() -> ArgParser {
+    ArgParser::new()
+        .arg("name")
+        .long("name", "name")
+        .takes_value("name")
+        .multiple("name")
+        .validator_with_context("name", |value, ctx| {
+            if ctx.get_many("name").contains(&value) {
+                Err(format!("'{}' was already given", value))
+            } else {
+                Ok(())
+            }
+        })
+}
+
+#[test]
+fn context_validators_see_earlier_values() {
+    let matches = unique_names()
+        .try_parse_from(["--name", "a", "--name", "b"])
+        .unwrap();
+    assert_eq!(matches.get_many("name"), vec!["a", "b"]);
+}
+
+#[test]
+fn context_rejections_are_invalid_values() {
+    assert_eq!(
+        unique_names()
+            .try_parse_from(["--name", "a", "--name", "a"])
+            .err(),
+        Some(ParseError::InvalidValue {
+            arg: "--name".to_string(),
+            value: "a".to_string(),
+            message: Some("'a' was already given".to_string()),
+        })
+    );
+}
+
+#[test]
+fn context_rejections_redact_sensitive_values() {
+    let error = unique_names()
+        .sensitive("name")
+        .try_parse_from(["--name", "hunter2", "--name", "hunter2"])
+        .unwrap_err();
+    match error {
+        ParseError::InvalidValue { value, .. } => assert_eq!(value, REDACTED),
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn context_exposes_the_command_path() {
+    let deploy = ArgParser::new()
+        .arg("target")
+        .positional("target")
+        .validator_with_context("target", |_, ctx| {
+            if ctx.command_path() == ["deploy"] {
+                Ok(())
+            } else {
+                Err(format!("unexpected path {:?}", ctx.command_path()))
+            }
+        });
+    let matches = ArgParser::new()
+        .subcommand("deploy", deploy)
+        .try_parse_from(["deploy", "prod"])
+        .unwrap();
+    assert!(matches.subcommand().is_some());
+}
@@ -0,0 +1,350 @@
+use rust_arguments::{ArgParser, DuplicatePolicy, ParseError, SpecError};
+
+#[test]
+fn collect_errors_reports_everything() {
+    let error = ArgParser::new()
+        .arg("name")
+        .long("name", "name")
+        .takes_value("name")
+        .required("name")
+        .collect_errors()
+        .try_parse_from(["--bogus", "--other"])
+        .unwrap_err();
+    assert_eq!(error.errors().len(), 3);
+}
+
+fn duplicated(policy: DuplicatePolicy) -> Result<Option<String>, ParseError> {
+    ArgParser::new()
+        .arg("name")
+        .long("name", "name")
+        .takes_value("name")
+        .on_duplicate("name", policy)
+        .try_parse_from(["--name", "first", "--name=second"])
+        .map(|matches| matches.get::<String>("name"))
+}
+
+#[test]
+fn duplicate_policy_last_wins_by_default() {
+    assert_eq!(
+        duplicated(DuplicatePolicy::LastWins),
+        Ok(Some("second".to_string()))
+    );
+}
+
+#[test]
+fn duplicate_policy_first_wins() {
+    assert_eq!(
+        duplicated(DuplicatePolicy::FirstWins),
+        Ok(Some("first".to_string()))
+    );
+}
+
+#[test]
+fn duplicate_policy_error() {
+    assert!(matches!(
+        duplicated(DuplicatePolicy::Error),
+        Err(ParseError::DuplicateArgument { .. })
+    ));
+}
+
+#[test]
+fn multiple_takes_precedence_over_duplicate_policy() {
+    let matches = ArgParser::new()
+        .arg("include")
+        .short("include", 'I')
+        .takes_value("include")
+        .multiple("include")
+        .on_duplicate("include", DuplicatePolicy::Error)
+        .try_parse_from(["-I", "a", "-Ib"])
+        .unwrap();
+    assert_eq!(matches.get_many("include"), vec!["a", "b"]);
+}
+
+fn tar() -> ArgParser {
+    ArgParser::new()
+        .arg("extract")
+        .short("extract", 'x')
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .arg("file")
+        .short("file", 'f')
+        .long("file", "file")
+        .takes_value("file")
+        .arg("input")
+        .positional("input")
+}
+
+#[test]
+fn short_clusters_take_values_from_the_rest_or_the_next_token() {
+    for tokens in [
+        &["-xvf", "archive.tar"][..],
+        &["-xvfarchive.tar"][..],
+        &["-xvf=archive.tar"][..],
+    ] {
+        let matches = tar().try_parse_from(tokens.iter().copied()).unwrap();
+        assert_eq!(matches.flags.get("extract"), Some(&true));
+        assert_eq!(matches.flags.get("verbose"), Some(&true));
+        assert_eq!(
+            matches.get::<String>("file").as_deref(),
+            Some("archive.tar")
+        );
+    }
+}
+
+#[test]
+fn value_short_followed_by_known_shorts_is_rejected() {
+    assert!(matches!(
+        tar().try_parse_from(["-fxv", "archive.tar"]),
+        Err(ParseError::ValueInCluster { .. })
+    ));
+}
+
+#[test]
+fn empty_argv_is_well_defined() {
+    let matches = tar().try_parse(&[]).unwrap();
+    assert!(matches.positionals.is_empty());
+    assert!(tar().parse_args_only(&[]).is_ok());
+}
+
+fn args(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+#[test]
+fn argv_zero_is_skipped_only_by_try_parse() {
+    let matches = tar().try_parse(&args(&["tar", "-x"])).unwrap();
+    assert_eq!(matches.flags.get("extract"), Some(&true));
+    assert_eq!(matches.get::<String>("input"), None);
+
+    let matches = tar().parse_args_only(&args(&["tar", "-x"])).unwrap();
+    assert_eq!(matches.get::<String>("input").as_deref(), Some("tar"));
+
+    let matches = tar()
+        .parse_with_program_name("tar", &args(&["-x", "data"]))
+        .unwrap();
+    assert_eq!(matches.get::<String>("input").as_deref(), Some("data"));
+}
+
+#[test]
+fn long_values_come_from_the_next_token_or_after_equals() {
+    for tokens in [&["--file", "a.tar"][..], &["--file=a.tar"][..]] {
+        let matches = tar().try_parse_from(tokens.iter().copied()).unwrap();
+        assert_eq!(matches.get::<String>("file").as_deref(), Some("a.tar"));
+    }
+    let matches = tar().try_parse_from(["--file="]).unwrap();
+    assert_eq!(matches.get::<String>("file").as_deref(), Some(""));
+}
+
+#[test]
+fn long_flags_reject_attached_values() {
+    assert!(matches!(
+        tar().try_parse_from(["--verbose=1"]),
+        Err(ParseError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn missing_value_is_an_error() {
+    assert!(matches!(
+        tar().try_parse_from(["--file"]),
+        Err(ParseError::MissingValue { .. })
+    ));
+}
+
+fn point() -> ArgParser {
+    ArgParser::new()
+        .arg("point")
+        .long("point", "point")
+        .takes_value("point")
+        .value_delimiter("point", ',')
+}
+
+#[test]
+fn equals_and_delimiters_compose() {
+    let matches = point()
+        .multiple("point")
+        .try_parse_from(["--point=1,2", "--point", "3"])
+        .unwrap();
+    assert_eq!(matches.get_many("point"), vec!["1", "2", "3"]);
+}
+
+#[test]
+fn later_delimited_occurrence_replaces_without_multiple() {
+    let matches = point()
+        .try_parse_from(["--point=1,2", "--point=3,4"])
+        .unwrap();
+    assert_eq!(matches.get_many("point"), vec!["3", "4"]);
+}
+
+#[test]
+fn empty_elements_are_kept_by_default() {
+    let matches = point().try_parse_from(["--point=1,,3"]).unwrap();
+    assert_eq!(matches.get_many("point"), vec!["1", "", "3"]);
+    let matches = point().try_parse_from(["--point=1,2,"]).unwrap();
+    assert_eq!(matches.get_many("point"), vec!["1", "2", ""]);
+}
+
+#[test]
+fn forbid_empty_values_rejects_empty_elements() {
+    for token in ["--point=", "--point=1,,3", "--point=1,2,"] {
+        assert!(
+            matches!(
+                point().forbid_empty_values("point").try_parse_from([token]),
+                Err(ParseError::EmptyValue { .. })
+            ),
+            "{}",
+            token
+        );
+    }
+}
+
+#[test]
+fn unknown_arguments_suggest_the_closest_option() {
+    assert_eq!(
+        tar().try_parse_from(["--verbos"]).err(),
+        Some(ParseError::UnknownArgument {
+            token: "--verbos".to_string(),
+            suggestion: Some("--verbose".to_string()),
+        })
+    );
+}
+
+#[test]
+fn variadic_positionals_leave_room_for_later_ones() {
+    let matches = ArgParser::new()
+        .arg("srcs")
+        .positional_multiple("srcs")
+        .arg("dest")
+        .positional("dest")
+        .try_parse_from(["a", "b", "c", "dest"])
+        .unwrap();
+    assert_eq!(matches.get_many("srcs"), vec!["a", "b", "c"]);
+    assert_eq!(matches.get::<String>("dest").as_deref(), Some("dest"));
+}
+
+#[test]
+fn counted_flags_and_levels() {
+    let parser = ArgParser::new()
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .count_levels("verbose", &["warn", "info", "debug", "trace"]);
+    let count = |tokens: &[&str]| parser.try_parse_from(tokens.iter().copied()).unwrap();
+    assert_eq!(count(&[]).level("verbose"), Some("warn"));
+    assert_eq!(count(&["-v"]).level("verbose"), Some("info"));
+    let matches = count(&["-vv", "--verbose"]);
+    assert_eq!(matches.count("verbose"), 3);
+    assert_eq!(matches.level("verbose"), Some("trace"));
+    assert_eq!(count(&["-vvvvv"]).level("verbose"), Some("trace"));
+}
+
+#[test]
+fn lone_dash_is_a_positional() {
+    let matches = tar().try_parse_from(["-"]).unwrap();
+    assert_eq!(matches.get::<String>("input").as_deref(), Some("-"));
+}
+
+#[test]
+fn double_dash_ends_options() {
+    let matches = tar().try_parse_from(["--", "--verbose"]).unwrap();
+    assert_eq!(matches.flags.get("verbose"), None);
+    assert_eq!(matches.get::<String>("input").as_deref(), Some("--verbose"));
+}
+
+#[test]
+fn trailing_var_arg_swallows_flags() {
+    let matches = ArgParser::new()
+        .arg("timeout")
+        .long("timeout", "timeout")
+        .takes_value("timeout")
+        .arg("command")
+        .trailing_var_arg("command")
+        .try_parse_from(["--timeout", "5", "ls", "-la", "--color"])
+        .unwrap();
+    assert_eq!(matches.get::<u32>("timeout"), Some(5));
+    assert_eq!(matches.get_many("command"), vec!["ls", "-la", "--color"]);
+}
+
+#[test]
+fn trailing_var_arg_must_be_the_last_positional() {
+    assert!(matches!(
+        ArgParser::new()
+            .arg("command")
+            .trailing_var_arg("command")
+            .arg("last")
+            .positional("last")
+            .check_spec(),
+        Err(SpecError::MisplacedTrailingVarArg { .. })
+    ));
+}
+
+#[test]
+fn negatable_flags_last_one_wins() {
+    let parser = ArgParser::new()
+        .arg("color")
+        .long("color", "color")
+        .default("color", "true")
+        .negatable("color");
+    let matches = parser.try_parse_from(Vec::<String>::new()).unwrap();
+    assert_eq!(matches.flags.get("color"), Some(&true));
+    let matches = parser.try_parse_from(["--no-color"]).unwrap();
+    assert_eq!(matches.flags.get("color"), Some(&false));
+    let matches = parser.try_parse_from(["--no-color", "--color"]).unwrap();
+    assert_eq!(matches.flags.get("color"), Some(&true));
+}
+
+#[test]
+fn negatable_arguments_cannot_take_values() {
+    assert!(matches!(
+        ArgParser::new()
+            .arg("name")
+            .long("name", "name")
+            .takes_value("name")
+            .negatable("name")
+            .check_spec(),
+        Err(SpecError::InvalidNegatable { .. })
+    ));
+}
+
+#[test]
+fn num_values_groups_occurrences() {
+    let parser = ArgParser::new()
+        .arg("point")
+        .long("point", "point")
+        .takes_value("point")
+        .num_values("point", 2)
+        .multiple("point");
+    let matches = parser
+        .try_parse_from(["--point", "1", "-4", "--point", "3", "4"])
+        .unwrap();
+    assert_eq!(matches.get_many("point"), vec!["1", "-4", "3", "4"]);
+    assert_eq!(
+        matches.get_occurrences("point"),
+        vec![vec!["1", "-4"], vec!["3", "4"]]
+    );
+    assert!(matches!(
+        parser.try_parse_from(["--point", "1"]),
+        Err(ParseError::WrongValueCount {
+            expected: 2,
+            actual: 1,
+            ..
+        })
+    ));
+}
+
+#[test]
+fn dash_sections_capture_each_slice() {
+    let matches = ArgParser::new()
+        .arg("jobs")
+        .short("jobs", 'j')
+        .takes_value("jobs")
+        .dash_sections(true)
+        .try_parse_from(["-j", "4", "--", "build", "--release", "--", "test", "--all"])
+        .unwrap();
+    assert_eq!(matches.get::<u32>("jobs"), Some(4));
+    assert_eq!(
+        matches.sections(),
+        &[args(&["build", "--release"]), args(&["test", "--all"]),]
+    );
+}
@@ -0,0 +1,80 @@
+use rust_arguments::ArgParser;
+
+fn args(words: &[&str]) -> Vec<String> {
+    std::iter::once("tool")
+        .chain(words.iter().copied())
+        .map(str::to_string)
+        .collect()
+}
+
+fn parser() -> ArgParser {
+    ArgParser::new()
+        .quiet_flag()
+        .arg("msg")
+        .short("msg", 'm')
+        .long("msg", "msg")
+        .takes_value("msg")
+        .subcommand(
+            "exec",
+            ArgParser::new()
+                .arg("command")
+                .positional("command")
+                .trailing_var_arg("command"),
+        )
+        .subcommand("fetch", ArgParser::new().arg("quick").short("quick", 'q'))
+}
+
+#[test]
+fn quiet_flag_is_detected_anywhere_on_the_line() {
+    let parser = parser();
+    assert!(parser.quiet_requested(&args(&["-q"])));
+    assert!(parser.quiet_requested(&args(&["--quiet", "--bogus"])));
+    assert!(parser.quiet_requested(&args(&["--bogus", "-q"])));
+    assert!(parser.quiet_requested(&args(&["exec", "-q", "ls"])));
+}
+
+#[test]
+fn option_values_are_not_quiet_flags() {
+    let parser = parser();
+    assert!(!parser.quiet_requested(&args(&["--msg", "-q"])));
+    assert!(!parser.quiet_requested(&args(&["-m", "-q", "--bogus"])));
+}
+
+#[test]
+fn captured_words_are_not_quiet_flags() {
+    let parser = parser();
+    assert!(!parser.quiet_requested(&args(&["exec", "grep", "-q", "x"])));
+    assert!(!parser.quiet_requested(&args(&["--", "-q"])));
+}
+
+#[test]
+fn subcommand_shorts_shadow_the_quiet_flag() {
+    assert!(!parser().quiet_requested(&args(&["fetch", "-q", "--bogus"])));
+}
+
+#[test]
+fn negation_turns_quiet_off() {
+    let parser = ArgParser::new().quiet_flag().negatable("quiet");
+    assert!(!parser.quiet_requested(&args(&["-q", "--no-quiet"])));
+    assert!(parser.quiet_requested(&args(&["--no-quiet", "-q"])));
+}
+
+#[test]
+fn quiet_errors_drop_suggestions() {
+    let parser = parser();
+    let err = parser.try_parse(&args(&["-q", "--msgg", "x"])).unwrap_err();
+    let quiet = parser.render_error(&args(&["-q", "--msgg", "x"]), &err);
+    assert!(!quiet.contains("did you mean"));
+    let err = parser
+        .try_parse(&args(&["--msg", "-q", "--msgg"]))
+        .unwrap_err();
+    let loud = parser.render_error(&args(&["--msg", "-q", "--msgg"]), &err);
+    assert!(loud.contains("did you mean '--msg'"));
+}
+
+#[test]
+fn quiet_designation_survives_spec_roundtrip() {
+    let restored = ArgParser::from_spec(parser().to_spec("tool"));
+    assert!(restored.quiet_requested(&args(&["-q"])));
+    assert!(!restored.quiet_requested(&args(&["--msg", "-q"])));
+}
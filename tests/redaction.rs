@@ -0,0 +1,88 @@
+use rust_arguments::{ArgParser, ParseError, REDACTED};
+
+#[cfg(feature = "json")]
+fn config_file(name: &str, contents: &str) -> String {
+    let path = std::env::temp_dir().join(format!(
+        "rust-arguments-{}-{}.json",
+        name,
+        std::process::id()
+    ));
+    std::fs::write(&path, contents).unwrap();
+    path.to_string_lossy().into_owned()
+}
+
+fn invalid_value(err: ParseError) -> String {
+    match err {
+        ParseError::InvalidValue { value, .. } => value,
+        other => panic!("unexpected error: {:?}", other),
+    }
+}
+
+#[test]
+fn command_line_errors_redact_sensitive_values() {
+    let parser = ArgParser::new()
+        .arg("pin")
+        .long("pin", "pin")
+        .takes_value("pin")
+        .sensitive("pin")
+        .validator("pin", |v| v.len() == 4);
+    let err = parser.try_parse_from(["--pin", "123456"]).unwrap_err();
+    assert!(!err.to_string().contains("123456"));
+    assert_eq!(invalid_value(err), REDACTED);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn config_file_list_errors_redact_sensitive_values() {
+    let path = config_file("list", r#"{ "token": ["hunter2", "hunter3"] }"#);
+    let parser = ArgParser::new()
+        .arg("token")
+        .long("token", "token")
+        .takes_value("token")
+        .sensitive("token")
+        .with_config_file(&path);
+    let err = parser.try_parse_from(Vec::<String>::new()).unwrap_err();
+    assert!(!err.to_string().contains("hunter"));
+    assert_eq!(invalid_value(err), REDACTED);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn config_file_boolean_errors_redact_sensitive_values() {
+    let path = config_file("bool", r#"{ "secret-mode": "hunter2" }"#);
+    let parser = ArgParser::new()
+        .arg("secret-mode")
+        .long("secret-mode", "secret-mode")
+        .sensitive("secret-mode")
+        .with_config_file(&path);
+    let err = parser.try_parse_from(Vec::<String>::new()).unwrap_err();
+    assert_eq!(invalid_value(err), REDACTED);
+}
+
+#[cfg(feature = "json")]
+#[test]
+fn config_file_errors_show_ordinary_values() {
+    let path = config_file("plain", r#"{ "level": ["1", "2"] }"#);
+    let parser = ArgParser::new()
+        .arg("level")
+        .long("level", "level")
+        .takes_value("level")
+        .with_config_file(&path);
+    let err = parser.try_parse_from(Vec::<String>::new()).unwrap_err();
+    assert_eq!(invalid_value(err), "1,2");
+}
+
+#[test]
+fn env_boolean_errors_redact_sensitive_values() {
+    let var = format!("RUST_ARGUMENTS_TEST_DEBUG_{}", std::process::id());
+    std::env::set_var(&var, "hunter2");
+    let parser = ArgParser::new()
+        .arg("debug")
+        .long("debug", "debug")
+        .env("debug", &var)
+        .sensitive("debug");
+    let err = parser.try_parse_from(Vec::<String>::new()).unwrap_err();
+    std::env::remove_var(&var);
+    assert!(!err.to_string().contains("hunter2"));
+    assert_eq!(invalid_value(err), REDACTED);
+}
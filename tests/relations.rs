@@ -0,0 +1,242 @@
+use rust_arguments::{ArgParser, ParseError};
+
+fn formats() -> ArgParser {
+    ArgParser::new()
+        .arg("json")
+        .long("json", "json")
+        .arg("yaml")
+        .short("yaml", 'y')
+        .arg("out")
+        .positional("out")
+        .conflicts_with("json", "yaml")
+        .requires("yaml", "out")
+}
+
+#[test]
+fn conflict_names_the_flags_as_typed() {
+    let err = formats().try_parse_from(["--json", "-y", "x"]).unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::Conflict {
+            arg: "--json".to_string(),
+            other: "-y".to_string(),
+        }
+    );
+    assert_eq!(err.to_string(), "Argument --json cannot be used with -y");
+}
+
+#[test]
+fn missing_dependency_names_flags_and_positionals() {
+    let err = formats().try_parse_from(["-y"]).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Argument -y requires <out> to also be given"
+    );
+}
+
+#[test]
+fn satisfied_relations_parse() {
+    assert!(formats().try_parse_from(["-y", "x"]).is_ok());
+    assert!(formats().try_parse_from(["--json"]).is_ok());
+}
+
+fn server() -> ArgParser {
+    ArgParser::new()
+        .arg("tls")
+        .long("tls", "tls")
+        .arg("key")
+        .long("key", "key-file")
+        .takes_value("key")
+        .required_if("key", "tls", "true")
+        .arg("mode")
+        .short("mode", 'm')
+        .takes_value("mode")
+        .arg("cert")
+        .long("cert", "cert")
+        .takes_value("cert")
+        .required_if("cert", "mode", "secure")
+        .arg("password")
+        .long("password", "password")
+        .takes_value("password")
+        .required_unless("password", "password-file")
+        .arg("password-file")
+        .long("password-file", "password-file")
+        .takes_value("password-file")
+}
+
+#[test]
+fn required_if_flag_names_the_flags() {
+    let err = server()
+        .try_parse_from(["--tls", "--password", "p"])
+        .unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Argument --key-file is required when --tls is given"
+    );
+}
+
+#[test]
+fn required_if_value_names_the_value() {
+    let err = server()
+        .try_parse_from(["-m", "secure", "--password", "p"])
+        .unwrap_err();
+    assert_eq!(
+        err,
+        ParseError::RequiredIf {
+            arg: "--cert".to_string(),
+            other: "-m".to_string(),
+            value: Some("secure".to_string()),
+        }
+    );
+    assert!(server()
+        .try_parse_from(["-m", "fast", "--password", "p"])
+        .is_ok());
+}
+
+#[test]
+fn required_unless_lists_the_alternatives() {
+    let err = server().try_parse_from(Vec::<String>::new()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Argument --password is required unless --password-file is given"
+    );
+    assert!(server()
+        .try_parse_from(["--password-file", "secret.txt"])
+        .is_ok());
+}
+
+fn layered() -> ArgParser {
+    ArgParser::new()
+        .arg("password")
+        .long("password", "password")
+        .takes_value("password")
+        .required_unless("password", "password-file")
+        .arg("password-file")
+        .long("password-file", "password-file")
+        .takes_value("password-file")
+        .arg("mode")
+        .long("mode", "mode")
+        .takes_value("mode")
+        .arg("cert")
+        .long("cert", "cert")
+        .takes_value("cert")
+        .required_if("cert", "mode", "secure")
+        .arg("quiet")
+        .long("quiet", "quiet")
+        .conflicts_with("quiet", "mode")
+}
+
+fn unique_var(name: &str) -> String {
+    format!("RUST_ARGUMENTS_RELATIONS_{}_{}", name, std::process::id())
+}
+
+#[test]
+fn defaults_never_satisfy_or_trigger_relations() {
+    let parser = layered()
+        .default("password-file", "secret.txt")
+        .default("mode", "secure");
+    assert!(matches!(
+        parser.try_parse_from(Vec::<String>::new()),
+        Err(ParseError::RequiredUnless { .. })
+    ));
+    assert!(parser
+        .try_parse_from(["--password", "p", "--quiet"])
+        .is_ok());
+}
+
+#[test]
+fn env_values_count_as_given() {
+    let file = unique_var("FILE");
+    let mode = unique_var("MODE");
+    std::env::set_var(&file, "secret.txt");
+    std::env::set_var(&mode, "secure");
+    let parser = layered().env("password-file", &file).env("mode", &mode);
+    assert!(matches!(
+        parser.try_parse_from(Vec::<String>::new()),
+        Err(ParseError::RequiredIf { .. })
+    ));
+    assert!(parser.try_parse_from(["--cert", "c"]).is_ok());
+    assert!(matches!(
+        parser.try_parse_from(["--cert", "c", "--quiet"]),
+        Err(ParseError::Conflict { .. })
+    ));
+}
+
+#[test]
+fn profile_values_count_as_given() {
+    let parser = layered().profile("ci", &[("password-file", "secret.txt"), ("mode", "secure")]);
+    assert!(matches!(
+        parser.try_parse_from(["--profile", "ci"]),
+        Err(ParseError::RequiredIf { .. })
+    ));
+    assert!(parser
+        .try_parse_from(["--profile", "ci", "--cert", "c"])
+        .is_ok());
+    assert!(matches!(
+        parser.try_parse_from(["--profile", "ci", "--cert", "c", "--quiet"]),
+        Err(ParseError::Conflict { .. })
+    ));
+}
+
+#[test]
+fn profile_values_satisfy_requires_and_groups() {
+    let parser = ArgParser::new()
+        .arg("upload")
+        .long("upload", "upload")
+        .arg("bucket")
+        .long("bucket", "bucket")
+        .takes_value("bucket")
+        .requires("upload", "bucket")
+        .arg("json")
+        .long("json", "json")
+        .arg("yaml")
+        .long("yaml", "yaml")
+        .group(
+            "format",
+            rust_arguments::GroupRule::ExactlyOne,
+            &["json", "yaml"],
+        )
+        .profile("prod", &[("bucket", "b"), ("yaml", "true")]);
+    assert!(parser
+        .try_parse_from(["--profile", "prod", "--upload"])
+        .is_ok());
+    assert!(matches!(
+        parser.try_parse_from(["--profile", "prod", "--json"]),
+        Err(ParseError::GroupViolation { .. })
+    ));
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn config_file_values_count_as_given() {
+    let path = std::env::temp_dir().join(format!(
+        "rust-arguments-relations-{}.toml",
+        std::process::id()
+    ));
+    std::fs::write(&path, "password-file = \"secret.txt\"\nmode = \"secure\"\n").unwrap();
+    let parser = layered().with_config_file(&path.to_string_lossy());
+    assert!(matches!(
+        parser.try_parse_from(Vec::<String>::new()),
+        Err(ParseError::RequiredIf { .. })
+    ));
+    assert!(parser.try_parse_from(["--cert", "c"]).is_ok());
+    assert!(matches!(
+        parser.try_parse_from(["--cert", "c", "--quiet"]),
+        Err(ParseError::Conflict { .. })
+    ));
+}
+
+#[cfg(feature = "dotenv")]
+#[test]
+fn env_file_values_count_as_given() {
+    let var = unique_var("ENV_FILE");
+    let path = std::env::temp_dir().join(format!(
+        "rust-arguments-relations-{}.env",
+        std::process::id()
+    ));
+    std::fs::write(&path, format!("{}=secret.txt\n", var)).unwrap();
+    let parser = layered()
+        .env("password-file", &var)
+        .env_file(&path.to_string_lossy());
+    assert!(parser.try_parse_from(Vec::<String>::new()).is_ok());
+}
@@ -0,0 +1,134 @@
+use proptest::prelude::*;
+use rust_arguments::ArgParser;
+
+#[derive(Debug, Clone, Copy)]
+enum Kind {
+    Flag,
+    Counted,
+    Negatable,
+    Value,
+    Multiple,
+    Delimited,
+    Sensitive,
+}
+
+#[derive(Debug, Clone)]
+struct Spec {
+    kinds: Vec<Kind>,
+    positional: bool,
+    subcommand: bool,
+}
+
+fn kind() -> impl Strategy<Value = Kind> {
+    prop_oneof![
+        Just(Kind::Flag),
+        Just(Kind::Counted),
+        Just(Kind::Negatable),
+        Just(Kind::Value),
+        Just(Kind::Multiple),
+        Just(Kind::Delimited),
+        Just(Kind::Sensitive),
+    ]
+}
+
+fn spec() -> impl Strategy<Value = Spec> {
+    (
+        prop::collection::vec(kind(), 1..6),
+        any::<bool>(),
+        any::<bool>(),
+    )
+        .prop_map(|(kinds, positional, subcommand)| Spec {
+            kinds,
+            positional,
+            subcommand,
+        })
+}
+
+fn build(spec: &Spec) -> ArgParser {
+    let mut parser = ArgParser::new();
+    for (i, kind) in spec.kinds.iter().enumerate() {
+        let name = format!("opt{}", i);
+        parser = parser.arg(&name).long(&name, &name);
+        parser = match kind {
+            Kind::Flag => parser,
+            Kind::Counted => parser.multiple(&name),
+            Kind::Negatable => parser.negatable(&name),
+            Kind::Value => parser.takes_value(&name),
+            Kind::Multiple => parser.takes_value(&name).multiple(&name),
+            Kind::Delimited => parser.takes_value(&name).value_delimiter(&name, ','),
+            Kind::Sensitive => parser.takes_value(&name).sensitive(&name),
+        };
+    }
+    if spec.positional {
+        parser = parser.arg("input").positional("input");
+    }
+    if spec.subcommand {
+        let run = ArgParser::new()
+            .arg("fast")
+            .long("fast", "fast")
+            .arg("level")
+            .long("level", "level")
+            .takes_value("level");
+        parser = parser.subcommand("run", run);
+    }
+    parser
+}
+
+fn tokens(spec: &Spec, picks: &[(usize, bool, String)], words: &[String]) -> Vec<String> {
+    let mut args = vec!["app".to_string()];
+    for (index, toggle, value) in picks {
+        let i = index % spec.kinds.len();
+        let long = format!("--opt{}", i);
+        match spec.kinds[i] {
+            Kind::Flag | Kind::Counted => args.push(long),
+            Kind::Negatable if *toggle => args.push(format!("--no-opt{}", i)),
+            Kind::Negatable => args.push(long),
+            Kind::Value | Kind::Multiple | Kind::Sensitive => {
+                args.push(long);
+                args.push(value.clone());
+            }
+            Kind::Delimited => {
+                args.push(long);
+                args.push(value.replace('_', ","));
+            }
+        }
+    }
+    match words.first() {
+        Some(word) if spec.positional && !word.starts_with('-') => args.push(word.clone()),
+        Some(word) if spec.positional && !spec.subcommand => {
+            args.push("--".to_string());
+            args.push(word.clone());
+        }
+        _ => {}
+    }
+    if spec.subcommand {
+        args.push("run".to_string());
+        args.push("--fast".to_string());
+        args.extend(words.get(1).map(|w| format!("--level={}", w)));
+    }
+    args
+}
+
+proptest! {
+    #[test]
+    fn parsed_invocations_round_trip(
+        spec in spec(),
+        picks in prop::collection::vec((0usize..8, any::<bool>(), "[a-z0-9]{1,4}(_[a-z]{1,3}){0,2}"), 0..8),
+        words in prop::collection::vec("-?[a-z]{1,6}", 0..3),
+    ) {
+        let parser = build(&spec);
+        let args = tokens(&spec, &picks, &words);
+        let matches = parser.try_parse(&args);
+        prop_assert!(matches.is_ok(), "{:?} failed to parse: {:?}", args, matches.err());
+        prop_assert_eq!(parser.check_roundtrip(&matches.unwrap()), Ok(()));
+    }
+
+    #[test]
+    fn unparse_never_reveals_sensitive_values(secret in "[a-z]{4,12}") {
+        let parser = build(&Spec { kinds: vec![Kind::Sensitive], positional: false, subcommand: false });
+        let matches = parser.try_parse_from(["--opt0", secret.as_str()]).unwrap();
+        let words = parser.unparse(&matches);
+        prop_assert!(!words.contains(&secret));
+        prop_assert_eq!(words, vec!["--opt0".to_string(), "<redacted>".to_string()]);
+    }
+}
@@ -0,0 +1,217 @@
+use rust_arguments::{ArgParser, CommandSpec};
+
+fn tool() -> ArgParser {
+    ArgParser::new()
+        .with_name("tool")
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .help("verbose", "Print more")
+        .subcommand("run", ArgParser::new().arg("fast").long("fast", "fast"))
+}
+
+#[test]
+fn program_name_survives_spec_roundtrip() {
+    let parser = tool();
+    let restored = ArgParser::from_spec(parser.to_spec("tool"));
+    assert_eq!(restored.render_help(), parser.render_help());
+    assert_eq!(restored.to_spec("tool"), parser.to_spec("tool"));
+}
+
+#[test]
+fn empty_spec_name_leaves_program_unnamed() {
+    let spec = CommandSpec::default();
+    let parser = ArgParser::from_spec(spec);
+    assert!(!parser.render_help().starts_with("\x1b[1m"));
+}
+
+#[test]
+fn spec_roundtrip_keeps_parsing_behaviour() {
+    let restored = ArgParser::from(tool().to_spec("tool"));
+    let matches = restored.try_parse_from(["-v", "run", "--fast"]).unwrap();
+    assert_eq!(matches.flags.get("verbose"), Some(&true));
+    let (name, sub) = matches.subcommand().unwrap();
+    assert_eq!(name, "run");
+    assert_eq!(sub.flags.get("fast"), Some(&true));
+}
+
+mod diff {
+    use rust_arguments::spec::{diff, ChangeKind, Impact};
+    use rust_arguments::{Arg, ArgParser, ValueRange};
+
+    fn base() -> ArgParser {
+        ArgParser::new()
+            .arg("color")
+            .long("color", "color")
+            .alias("color", "colour")
+            .arg("jobs")
+            .long("jobs", "jobs")
+            .takes_value("jobs")
+            .range("jobs", 1..=16)
+            .arg("include")
+            .long("include", "include")
+            .takes_value("include")
+            .multiple("include")
+            .arg("input")
+            .long("input", "input")
+            .takes_value("input")
+            .subcommand("remove", ArgParser::new())
+            .subcommand_alias("remove", &["rm"])
+    }
+
+    fn base_with(edit: impl Fn(&mut Arg)) -> ArgParser {
+        base()
+            .into_iter()
+            .map(|mut arg| {
+                edit(&mut arg);
+                arg
+            })
+            .collect::<ArgParser>()
+            .subcommand("remove", ArgParser::new())
+            .subcommand_alias("remove", &["rm"])
+    }
+
+    fn kinds(old: ArgParser, new: ArgParser) -> Vec<(ChangeKind, Impact)> {
+        diff(&old.to_spec("tool"), &new.to_spec("tool"))
+            .changes
+            .into_iter()
+            .map(|c| {
+                let impact = c.impact();
+                (c.kind, impact)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn identical_specs_have_no_changes() {
+        assert!(kinds(base(), base()).is_empty());
+    }
+
+    #[test]
+    fn removed_aliases_are_breaking() {
+        let new = base_with(|arg| arg.aliases.clear());
+        assert_eq!(
+            kinds(base(), new),
+            vec![(
+                ChangeKind::AliasesRemoved(vec!["colour".to_string()]),
+                Impact::Breaking
+            )]
+        );
+        assert_eq!(
+            kinds(base(), base().alias("color", "kolor")),
+            vec![(
+                ChangeKind::AliasesAdded(vec!["kolor".to_string()]),
+                Impact::Additive
+            )]
+        );
+    }
+
+    #[test]
+    fn removed_subcommand_aliases_are_breaking() {
+        let without = |aliases: &[&str]| {
+            base()
+                .subcommand("remove", ArgParser::new())
+                .subcommand_alias("remove", aliases)
+        };
+        let changes = diff(&base().to_spec("tool"), &without(&[]).to_spec("tool")).changes;
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].command, vec!["remove"]);
+        assert_eq!(
+            changes[0].kind,
+            ChangeKind::SubcommandAliasesRemoved(vec!["rm".to_string()])
+        );
+        assert_eq!(changes[0].impact(), Impact::Breaking);
+        assert_eq!(
+            kinds(base(), without(&["rm", "del"])),
+            vec![(
+                ChangeKind::SubcommandAliasesAdded(vec!["del".to_string()]),
+                Impact::Additive
+            )]
+        );
+    }
+
+    #[test]
+    fn positional_and_multiple_changes_are_breaking() {
+        assert_eq!(
+            kinds(base(), base().positional("input")),
+            vec![(
+                ChangeKind::PositionalChanged { now: true },
+                Impact::Breaking
+            )]
+        );
+        assert_eq!(
+            kinds(base(), base().multiple("input")),
+            vec![(ChangeKind::MultipleChanged { now: true }, Impact::Breaking)]
+        );
+        let single = base_with(|arg| arg.multiple = false);
+        assert_eq!(
+            kinds(base(), single),
+            vec![(ChangeKind::MultipleChanged { now: false }, Impact::Breaking)]
+        );
+    }
+
+    #[test]
+    fn narrowed_ranges_are_breaking_and_widened_ones_additive() {
+        let range = |old: ValueRange, new: ValueRange| ChangeKind::RangeChanged {
+            old: Some(old),
+            new: Some(new),
+        };
+        assert_eq!(
+            kinds(base(), base().range("jobs", 1..=8)),
+            vec![(
+                range(ValueRange::new(1..=16), ValueRange::new(1..=8)),
+                Impact::Breaking
+            )]
+        );
+        assert_eq!(
+            kinds(base(), base().range("jobs", 0..=64)),
+            vec![(
+                range(ValueRange::new(1..=16), ValueRange::new(0..=64)),
+                Impact::Additive
+            )]
+        );
+        assert_eq!(
+            kinds(base(), base().range("jobs", 2..)),
+            vec![(
+                range(ValueRange::new(1..=16), ValueRange::new(2..)),
+                Impact::Breaking
+            )]
+        );
+        assert_eq!(
+            kinds(base(), base().range("input", 0..=9)),
+            vec![(
+                ChangeKind::RangeChanged {
+                    old: None,
+                    new: Some(ValueRange::new(0..=9)),
+                },
+                Impact::Breaking
+            )]
+        );
+        let unbounded = base_with(|arg| arg.range = None);
+        assert_eq!(
+            kinds(base(), unbounded),
+            vec![(
+                ChangeKind::RangeChanged {
+                    old: Some(ValueRange::new(1..=16)),
+                    new: None,
+                },
+                Impact::Additive
+            )]
+        );
+    }
+
+    #[test]
+    fn changes_describe_themselves() {
+        let changes = diff(
+            &base().to_spec("tool").clone(),
+            &base()
+                .range("jobs", 1..=8)
+                .positional("input")
+                .to_spec("tool"),
+        );
+        let text = changes.to_string();
+        assert!(text.contains("[breaking] <input>: now positional"));
+        assert!(text.contains("[breaking] <jobs>: range changed from 1-16 to 1-8"));
+        assert!(changes.is_breaking());
+    }
+}
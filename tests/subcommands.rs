@@ -0,0 +1,196 @@
+use rust_arguments::{AmbiguityPolicy, ArgParser, ParseError, SpecError};
+
+fn build() -> ArgParser {
+    ArgParser::new()
+        .arg("release")
+        .long("release", "release")
+        .arg("target")
+        .positional("target")
+}
+
+#[test]
+fn disallowed_globals_are_rejected() {
+    let parser = ArgParser::new()
+        .arg("verbose")
+        .long("verbose", "verbose")
+        .global("verbose")
+        .subcommand("build", build().disallow_global("verbose"));
+    assert!(matches!(
+        parser.try_parse_from(["build", "--verbose"]),
+        Err(ParseError::DisallowedGlobal { .. })
+    ));
+}
+
+#[test]
+fn exec_style_captures_the_rest_verbatim() {
+    let run = ArgParser::new()
+        .arg("quiet")
+        .long("quiet", "quiet")
+        .exec_style(true);
+    let matches = ArgParser::new()
+        .subcommand("run", run)
+        .try_parse_from(["run", "--quiet", "ls", "--quiet", "-la"])
+        .unwrap();
+    let (_, run) = matches.subcommand().unwrap();
+    assert_eq!(run.flags.get("quiet"), Some(&true));
+    assert_eq!(run.positionals, vec!["ls", "--quiet", "-la"]);
+}
+
+#[test]
+fn preprocessors_rewrite_tokens_before_matching() {
+    let matches = ArgParser::new()
+        .arg("verbose")
+        .long("verbose", "verbose")
+        .preprocessor(|tokens: Vec<String>| {
+            tokens
+                .into_iter()
+                .map(|t| {
+                    if t == "-V" {
+                        "--verbose".to_string()
+                    } else {
+                        t
+                    }
+                })
+                .collect()
+        })
+        .try_parse_from(["-V"])
+        .unwrap();
+    assert_eq!(matches.flags.get("verbose"), Some(&true));
+}
+
+fn cli() -> ArgParser {
+    ArgParser::new()
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .global("verbose")
+        .subcommand("build", build())
+        .subcommand("remove", ArgParser::new().arg("path").positional("path"))
+        .subcommand_alias("remove", &["rm", "delete"])
+}
+
+#[test]
+fn default_subcommand_takes_unmatched_positionals() {
+    let parser = cli().default_subcommand("build");
+    let matches = parser.try_parse_from(["app"]).unwrap();
+    let (name, build) = matches.subcommand().unwrap();
+    assert_eq!(name, "build");
+    assert_eq!(build.get::<String>("target").as_deref(), Some("app"));
+}
+
+#[test]
+fn default_subcommand_must_exist() {
+    assert!(matches!(
+        ArgParser::new()
+            .subcommand("run", ArgParser::new())
+            .default_subcommand("build")
+            .check_spec(),
+        Err(SpecError::UnknownDefaultSubcommand { name }) if name == "build"
+    ));
+}
+
+fn ambiguous(policy: AmbiguityPolicy) -> Result<(Option<String>, Option<String>), ParseError> {
+    ArgParser::new()
+        .arg("file")
+        .positional("file")
+        .subcommand("build", build())
+        .ambiguity_policy(policy)
+        .try_parse_from(["build"])
+        .map(|matches| {
+            (
+                matches.get::<String>("file"),
+                matches.subcommand().map(|(name, _)| name.to_string()),
+            )
+        })
+}
+
+#[test]
+fn ambiguity_policy_decides_between_positional_and_subcommand() {
+    assert_eq!(
+        ambiguous(AmbiguityPolicy::PreferSubcommand),
+        Ok((None, Some("build".to_string())))
+    );
+    assert_eq!(
+        ambiguous(AmbiguityPolicy::PreferPositional),
+        Ok((Some("build".to_string()), None))
+    );
+    assert!(matches!(
+        ambiguous(AmbiguityPolicy::Error),
+        Err(ParseError::AmbiguousToken { .. })
+    ));
+}
+
+#[test]
+fn subcommand_matches_are_nested() {
+    let matches = cli().try_parse_from(["build", "--release", "app"]).unwrap();
+    let (name, build) = matches.subcommand().unwrap();
+    assert_eq!(name, "build");
+    assert_eq!(build.flags.get("release"), Some(&true));
+    assert_eq!(build.get::<String>("target").as_deref(), Some("app"));
+}
+
+#[test]
+fn subcommand_options_are_not_known_at_the_top_level() {
+    assert!(matches!(
+        cli().try_parse_from(["--release", "build"]),
+        Err(ParseError::UnknownArgument { .. })
+    ));
+}
+
+#[test]
+fn abbreviations_expand_outside_values() {
+    let parser = ArgParser::new()
+        .arg("env")
+        .long("env", "env")
+        .takes_value("env")
+        .arg("name")
+        .long("name", "name")
+        .takes_value("name")
+        .abbreviation("prod", &["--env", "production"]);
+    let matches = parser.try_parse_from(["prod"]).unwrap();
+    assert_eq!(matches.get::<String>("env").as_deref(), Some("production"));
+    let matches = parser.try_parse_from(["--name", "prod"]).unwrap();
+    assert_eq!(matches.get::<String>("name").as_deref(), Some("prod"));
+    assert_eq!(matches.get::<String>("env"), None);
+}
+
+#[test]
+fn double_dash_keeps_subcommand_names_as_positionals() {
+    let matches = ArgParser::new()
+        .arg("file")
+        .positional("file")
+        .subcommand("build", build())
+        .try_parse_from(["--", "build"])
+        .unwrap();
+    assert_eq!(matches.get::<String>("file").as_deref(), Some("build"));
+    assert!(matches.subcommand().is_none());
+}
+
+#[test]
+fn globals_are_visible_at_every_level() {
+    let matches = cli().try_parse_from(["build", "-v"]).unwrap();
+    assert_eq!(matches.flags.get("verbose"), Some(&true));
+    let (_, build) = matches.subcommand().unwrap();
+    assert_eq!(build.flags.get("verbose"), Some(&true));
+}
+
+#[test]
+fn aliases_report_the_canonical_name() {
+    for alias in ["rm", "delete", "remove"] {
+        let matches = cli().try_parse_from([alias, "file"]).unwrap();
+        let (name, remove) = matches.subcommand().unwrap();
+        assert_eq!(name, "remove");
+        assert_eq!(remove.get::<String>("path").as_deref(), Some("file"));
+    }
+}
+
+#[test]
+fn long_aliases_answer_for_the_argument() {
+    let matches = ArgParser::new()
+        .arg("color")
+        .long("color", "color")
+        .alias("color", "colour")
+        .try_parse_from(["--colour"])
+        .unwrap();
+    assert_eq!(matches.flags.get("color"), Some(&true));
+}
@@ -0,0 +1,155 @@
+use rust_arguments::{ArgParser, ExplainStep, LintRule, ParseError, ValueSource};
+
+fn parser() -> ArgParser {
+    ArgParser::new()
+        .arg("verbose")
+        .short("verbose", 'v')
+        .long("verbose", "verbose")
+        .help("verbose", "Print more")
+        .arg("level")
+        .long("level", "level")
+        .takes_value("level")
+        .default("level", "1")
+        .help("level", "Log level")
+        .arg("input")
+        .positional("input")
+        .help("input", "Input file")
+}
+
+#[test]
+fn scripts_parse_line_by_line() {
+    let script = "# setup\n-v first\n\n--level 2 \\\n  'second file'\n--bogus\n";
+    let results: Vec<(usize, Result<Option<String>, ParseError>)> = parser()
+        .parse_script(script.as_bytes())
+        .map(|(line, result)| (line, result.map(|m| m.get::<String>("input"))))
+        .collect();
+    assert_eq!(results.len(), 3);
+    assert_eq!(results[0], (2, Ok(Some("first".to_string()))));
+    assert_eq!(results[1], (4, Ok(Some("second file".to_string()))));
+    assert_eq!(results[2].0, 6);
+    assert!(results[2].1.is_err());
+}
+
+fn args(words: &[&str]) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+#[test]
+fn completion_candidates_follow_the_current_word() {
+    let parser = parser().subcommand("run", ArgParser::new());
+    let values: Vec<String> = parser
+        .completion_candidates(&args(&["--le"]))
+        .into_iter()
+        .map(|c| c.value)
+        .collect();
+    assert_eq!(values, vec!["--level"]);
+    let values: Vec<String> = parser
+        .completion_candidates(&args(&["r"]))
+        .into_iter()
+        .map(|c| c.value)
+        .collect();
+    assert_eq!(values, vec!["run"]);
+}
+
+#[test]
+fn help_lists_arguments() {
+    let help = parser().render_help();
+    assert!(help.contains("-v, --verbose"));
+    assert!(help.contains("Print more"));
+    assert!(help.contains("--level <level>"));
+    assert!(help.contains("<input>"));
+    assert!(matches!(
+        parser().try_parse_from(["--help"]),
+        Err(ParseError::HelpRequested { .. })
+    ));
+}
+
+#[test]
+fn explain_traces_every_token_and_fallback() {
+    let explanation = parser().explain(&args(&["app", "-v", "data"]));
+    assert_eq!(explanation.error, None);
+    assert_eq!(
+        explanation.steps,
+        vec![
+            ExplainStep::Flag {
+                index: 1,
+                token: "-v".to_string(),
+                arg: "verbose".to_string(),
+            },
+            ExplainStep::Positional {
+                index: 2,
+                arg: "input".to_string(),
+                value: "data".to_string(),
+            },
+            ExplainStep::Fallback {
+                command: String::new(),
+                arg: "level".to_string(),
+                value: "1".to_string(),
+                source: ValueSource::Default,
+            },
+        ]
+    );
+}
+
+#[test]
+fn explain_reports_the_error() {
+    let explanation = parser().explain(&args(&["app", "--bogus"]));
+    assert!(matches!(
+        explanation.error,
+        Some(ParseError::UnknownArgument { .. })
+    ));
+    assert!(explanation.to_string().contains("parsing would fail"));
+}
+
+#[test]
+fn frozen_parsers_parse_like_the_original() {
+    let frozen = parser().freeze();
+    let matches = frozen.try_parse_from(["-v", "x"]).unwrap();
+    assert_eq!(matches.flags.get("verbose"), Some(&true));
+    assert_eq!(frozen.thaw().render_help(), parser().render_help());
+}
+
+#[cfg(unix)]
+#[test]
+fn os_arguments_keep_invalid_bytes() {
+    use std::ffi::{OsStr, OsString};
+    use std::os::unix::ffi::OsStrExt;
+
+    let raw = OsStr::from_bytes(b"caf\xe9.txt");
+    let matches = parser()
+        .try_parse_os(&[OsString::from("app"), raw.to_os_string()])
+        .unwrap();
+    assert_eq!(matches.get_os("input").as_deref(), Some(raw));
+    assert_eq!(
+        matches.get::<String>("input").as_deref(),
+        Some("caf\u{fffd}.txt")
+    );
+}
+
+#[test]
+fn lint_flags_missing_help_and_case_collisions() {
+    let parser = ArgParser::new()
+        .arg("output")
+        .long("output", "output")
+        .arg("all")
+        .short("all", 'a')
+        .help("all", "Everything")
+        .arg("append")
+        .short("append", 'A')
+        .help("append", "Append")
+        .subcommand("run", ArgParser::new());
+    let rules: Vec<LintRule> = parser.lint().into_iter().map(|f| f.rule).collect();
+    assert_eq!(
+        rules,
+        vec![
+            LintRule::MissingHelp,
+            LintRule::FlagLooksLikeOption,
+            LintRule::CaseCollidingShorts,
+            LintRule::MissingAbout,
+        ]
+    );
+    assert!(parser
+        .lint_with(&[LintRule::MissingAbout])
+        .iter()
+        .all(|f| f.command == ["run"]));
+}
@@ -0,0 +1,141 @@
+use rust_arguments::{
+    ArgMatches, ArgParser, FieldError, Flag, FromArgMatches, Opt, ParseError, Positional,
+};
+
+#[derive(Debug, PartialEq)]
+struct Config {
+    verbose: bool,
+    port: u16,
+    host: Option<String>,
+    files: Vec<String>,
+}
+
+impl FromArgMatches for Config {
+    fn from_arg_matches(matches: &ArgMatches) -> Result<Self, FieldError> {
+        Ok(Config {
+            verbose: matches.flags.get("verbose").copied().unwrap_or(false),
+            port: matches.required_field("port", "port")?,
+            host: matches.field("host", "host")?,
+            files: matches.field_many("files", "files")?,
+        })
+    }
+}
+
+#[test]
+fn field_errors_name_the_field() {
+    let matches = ArgParser::new()
+        .arg("port")
+        .long("port", "port")
+        .takes_value("port")
+        .try_parse_from(["--port", "http"])
+        .unwrap();
+    assert!(matches!(
+        matches.try_into_struct::<Config>(),
+        Err(FieldError::Invalid { field, value, .. }) if field == "port" && value == "http"
+    ));
+    let matches = ArgParser::new()
+        .try_parse_from(Vec::<String>::new())
+        .unwrap();
+    assert!(matches!(
+        matches.try_into_struct::<Config>(),
+        Err(FieldError::Missing { field, .. }) if field == "port"
+    ));
+}
+
+fn parser() -> ArgParser {
+    ArgParser::new()
+        .with_arg(Flag::new("verbose").short('v').long("verbose"))
+        .with_arg(
+            Opt::new("port")
+                .short('p')
+                .long("port")
+                .default("8080")
+                .value_parser::<u16>(),
+        )
+        .with_arg(Opt::new("host").long("host"))
+        .with_arg(Positional::new("files").multiple())
+}
+
+#[test]
+fn typed_constructors_build_a_working_parser() {
+    let config: Config = parser()
+        .try_parse_from(["-v", "a", "--host", "example.org", "b"])
+        .unwrap()
+        .try_into_struct()
+        .unwrap();
+    assert_eq!(
+        config,
+        Config {
+            verbose: true,
+            port: 8080,
+            host: Some("example.org".to_string()),
+            files: vec!["a".to_string(), "b".to_string()],
+        }
+    );
+}
+
+#[test]
+fn value_parsers_reject_bad_values_at_parse_time() {
+    assert!(matches!(
+        parser().try_parse_from(["--port", "http"]),
+        Err(ParseError::ConversionFailed { .. })
+    ));
+}
+
+#[cfg(feature = "derive")]
+mod derive {
+    use rust_arguments::{Arguments, ParseError};
+
+    #[derive(Arguments)]
+    struct Cli {
+        #[arg(short)]
+        verbose: bool,
+        #[arg(short = 'p', default = "8080")]
+        port: u16,
+        db_url: Option<String>,
+        #[arg(value_delimiter = ',')]
+        features: Vec<String>,
+        #[arg(positional)]
+        files: Vec<String>,
+    }
+
+    fn argv(words: &[&str]) -> Vec<String> {
+        std::iter::once("app")
+            .chain(words.iter().copied())
+            .map(String::from)
+            .collect()
+    }
+
+    #[test]
+    fn derived_parser_fills_every_field() {
+        let cli = Cli::try_from_args(&argv(&[
+            "-v",
+            "--db-url",
+            "postgres://db",
+            "--features",
+            "a,b",
+            "--features",
+            "c",
+            "x",
+            "y",
+        ]))
+        .unwrap();
+        assert!(cli.verbose);
+        assert_eq!(cli.port, 8080);
+        assert_eq!(cli.db_url.as_deref(), Some("postgres://db"));
+        assert_eq!(cli.features, vec!["a", "b", "c"]);
+        assert_eq!(cli.files, vec!["x", "y"]);
+    }
+
+    #[test]
+    fn derived_parser_checks_types() {
+        assert!(matches!(
+            Cli::try_from_args(&argv(&["-p", "nope"])),
+            Err(ParseError::ConversionFailed { .. })
+        ));
+        let cli = Cli::try_from_args(&argv(&["-p", "1"])).unwrap();
+        assert_eq!(cli.port, 1);
+        assert!(!cli.verbose);
+        assert!(cli.files.is_empty());
+    }
+}
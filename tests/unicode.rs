@@ -0,0 +1,74 @@
+use rust_arguments::{display_width, ArgParser, ParseError};
+
+fn parser() -> ArgParser {
+    ArgParser::new()
+        .arg("strasse")
+        .short("strasse", 'ß')
+        .arg("name")
+        .short("name", '名')
+        .long("name", "名前")
+        .takes_value("name")
+        .help("name", "設定ファイルの名前")
+        .arg("size")
+        .long("size", "größe")
+        .takes_value("size")
+        .help("size", "Größe in Bytes")
+        .arg("input")
+        .positional("input")
+}
+
+#[test]
+fn multi_byte_shorts_cluster_with_attached_values() {
+    let matches = parser().try_parse_from(["-ß名東京"]).unwrap();
+    assert_eq!(matches.flags.get("strasse"), Some(&true));
+    assert_eq!(matches.get::<String>("name").as_deref(), Some("東京"));
+}
+
+#[test]
+fn multi_byte_short_takes_separate_value() {
+    let matches = parser().try_parse_from(["-名", "大阪", "-ß"]).unwrap();
+    assert_eq!(matches.get::<String>("name").as_deref(), Some("大阪"));
+    assert_eq!(matches.flags.get("strasse"), Some(&true));
+}
+
+#[test]
+fn non_ascii_long_names_and_values() {
+    let matches = parser()
+        .try_parse_from(["--名前=ü\u{301}ber", "--größe", "42", "データ.txt"])
+        .unwrap();
+    assert_eq!(
+        matches.get::<String>("name").as_deref(),
+        Some("ü\u{301}ber")
+    );
+    assert_eq!(matches.get::<u32>("size"), Some(42));
+    assert_eq!(
+        matches.get::<String>("input").as_deref(),
+        Some("データ.txt")
+    );
+}
+
+#[test]
+fn unknown_non_ascii_long_suggests_closest_name() {
+    match parser().try_parse_from(["--grösse", "1"]) {
+        Err(ParseError::UnknownArgument { token, suggestion }) => {
+            assert_eq!(token, "--grösse");
+            assert_eq!(suggestion.as_deref(), Some("--größe"));
+        }
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[test]
+fn help_aligns_cjk_names_by_display_width() {
+    let help = parser().render_help();
+    let columns: Vec<usize> = help
+        .lines()
+        .filter(|line| line.contains("設定ファイル") || line.contains("Größe"))
+        .map(|line| {
+            let start = line.find(['設', 'G']).unwrap();
+            display_width(&line[..start])
+        })
+        .collect();
+    assert_eq!(columns.len(), 2);
+    assert_eq!(columns[0], columns[1]);
+}
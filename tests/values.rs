@@ -0,0 +1,314 @@
+use rust_arguments::{ArgParser, Condition, ParseError, ValueRange, ValueSource};
+
+fn format() -> ArgParser {
+    ArgParser::new()
+        .arg("format")
+        .long("format", "format")
+        .takes_value("format")
+        .choices("format", &["json", "yaml", "yml", "toml"])
+}
+
+#[test]
+fn choices_suggest_the_closest_value() {
+    assert_eq!(
+        format().try_parse_from(["--format", "jsno"]).err(),
+        Some(ParseError::InvalidChoice {
+            arg: "--format".to_string(),
+            value: "jsno".to_string(),
+            choices: vec![
+                "json".to_string(),
+                "yaml".to_string(),
+                "yml".to_string(),
+                "toml".to_string()
+            ],
+            suggestion: Some("json".to_string()),
+        })
+    );
+}
+
+#[test]
+fn conditional_defaults_win_over_plain_defaults() {
+    let parser = ArgParser::new()
+        .arg("mode")
+        .long("mode", "mode")
+        .takes_value("mode")
+        .default("mode", "plain")
+        .default_when("mode", !Condition::Always, "never")
+        .default_when("mode", Condition::Always, "always");
+    let matches = parser.try_parse_from(Vec::<String>::new()).unwrap();
+    assert_eq!(matches.get::<String>("mode").as_deref(), Some("always"));
+}
+
+#[test]
+fn profiles_sit_between_explicit_values_and_defaults() {
+    let parser = ArgParser::new()
+        .arg("opt-level")
+        .long("opt-level", "opt-level")
+        .takes_value("opt-level")
+        .default("opt-level", "0")
+        .arg("debug")
+        .long("debug", "debug")
+        .takes_value("debug")
+        .profile("release", &[("opt-level", "3"), ("debug", "false")]);
+    let matches = parser.try_parse_from(["--profile", "release"]).unwrap();
+    assert_eq!(matches.get::<u8>("opt-level"), Some(3));
+    assert_eq!(
+        matches.value_source("opt-level"),
+        Some(&ValueSource::Profile("release".to_string()))
+    );
+    let matches = parser
+        .try_parse_from(["--profile", "release", "--opt-level", "2"])
+        .unwrap();
+    assert_eq!(matches.get::<u8>("opt-level"), Some(2));
+    assert_eq!(
+        parser
+            .try_parse_from(Vec::<String>::new())
+            .unwrap()
+            .get::<u8>("opt-level"),
+        Some(0)
+    );
+}
+
+#[test]
+fn value_parsers_convert_positionals() {
+    let parser = ArgParser::new()
+        .arg("port")
+        .positional("port")
+        .value_parser::<u16>("port");
+    assert_eq!(
+        parser.try_parse_from(["80"]).unwrap().get::<u16>("port"),
+        Some(80)
+    );
+    assert!(matches!(
+        parser.try_parse_from(["99999"]),
+        Err(ParseError::ConversionFailed { .. })
+    ));
+}
+
+#[test]
+fn defaults_fill_in_and_reference_other_arguments() {
+    let parser = ArgParser::new()
+        .arg("input")
+        .positional("input")
+        .arg("output")
+        .long("output", "output")
+        .takes_value("output")
+        .default("output", "{input}.out");
+    let matches = parser.try_parse_from(["data"]).unwrap();
+    assert_eq!(matches.get::<String>("output").as_deref(), Some("data.out"));
+    assert_eq!(matches.value_source("output"), Some(&ValueSource::Default));
+    let matches = parser.try_parse_from(["data", "--output", "x"]).unwrap();
+    assert_eq!(matches.get::<String>("output").as_deref(), Some("x"));
+    assert_eq!(
+        matches.value_source("output"),
+        Some(&ValueSource::CommandLine)
+    );
+    let matches = parser.try_parse_from(Vec::<String>::new()).unwrap();
+    assert_eq!(matches.get::<String>("output"), None);
+}
+
+fn versioned(version: &str) -> ArgParser {
+    ArgParser::new()
+        .version(version)
+        .arg("old")
+        .long("old", "old")
+        .removed_in("old", "2.0")
+        .replaced_by("old", "new")
+        .arg("new")
+        .long("new", "new")
+        .introduced_in("new", "1.5")
+        .arg("target")
+        .long("target", "target")
+        .takes_value("target")
+        .soft_required("target", "3.0")
+}
+
+#[test]
+fn deprecated_arguments_record_a_deprecation() {
+    let matches = versioned("1.9")
+        .try_parse_from(["--old", "--target", "x"])
+        .unwrap();
+    assert_eq!(matches.flags.get("old"), Some(&true));
+    assert_eq!(matches.deprecations().len(), 1);
+}
+
+#[test]
+fn removed_arguments_are_unknown_unless_accepted() {
+    assert!(matches!(
+        versioned("2.0").try_parse_from(["--old", "--target", "x"]),
+        Err(ParseError::UnknownArgument { .. })
+    ));
+    assert!(matches!(
+        versioned("2.0")
+            .accept_removed_args()
+            .try_parse_from(["--old", "--target", "x"]),
+        Err(ParseError::RemovedArgument { version, .. }) if version == "2.0"
+    ));
+}
+
+#[test]
+fn ranges_reject_out_of_bounds_and_non_integers() {
+    let parser = ArgParser::new()
+        .arg("jobs")
+        .long("jobs", "jobs")
+        .takes_value("jobs")
+        .range("jobs", 1..=64);
+    assert_eq!(
+        parser
+            .try_parse_from(["--jobs", "8"])
+            .unwrap()
+            .get::<u8>("jobs"),
+        Some(8)
+    );
+    assert_eq!(
+        parser.try_parse_from(["--jobs", "65"]).err(),
+        Some(ParseError::OutOfRange {
+            arg: "--jobs".to_string(),
+            value: "65".to_string(),
+            range: ValueRange::new(1..=64),
+        })
+    );
+    assert!(matches!(
+        parser.try_parse_from(["--jobs", "many"]),
+        Err(ParseError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn soft_required_becomes_required_at_its_version() {
+    let matches = versioned("2.5")
+        .try_parse_from(Vec::<String>::new())
+        .unwrap();
+    assert_eq!(matches.deprecations().len(), 1);
+    assert!(matches!(
+        versioned("3.0").try_parse_from(Vec::<String>::new()),
+        Err(ParseError::MissingRequired { .. })
+    ));
+}
+
+#[test]
+fn experimental_arguments_need_an_opt_in() {
+    let var = format!("RUST_ARGUMENTS_TEST_NIGHTLY_{}", std::process::id());
+    let parser = ArgParser::new()
+        .arg("turbo")
+        .long("turbo", "turbo")
+        .experimental("turbo", &var);
+    assert!(matches!(
+        parser.try_parse_from(["--turbo"]),
+        Err(ParseError::ExperimentalArgument { .. })
+    ));
+    std::env::set_var(&var, "1");
+    assert_eq!(
+        parser
+            .try_parse_from(["--turbo"])
+            .unwrap()
+            .flags
+            .get("turbo"),
+        Some(&true)
+    );
+}
+
+#[test]
+fn env_values_fall_back_after_the_command_line() {
+    let var = format!("RUST_ARGUMENTS_TEST_LEVEL_{}", std::process::id());
+    std::env::set_var(&var, "7");
+    let parser = ArgParser::new()
+        .arg("level")
+        .long("level", "level")
+        .takes_value("level")
+        .env("level", &var)
+        .default("level", "1");
+    let matches = parser.try_parse_from(Vec::<String>::new()).unwrap();
+    assert_eq!(matches.get::<u8>("level"), Some(7));
+    assert_eq!(matches.value_source("level"), Some(&ValueSource::Env(var)));
+    let matches = parser.try_parse_from(["--level", "3"]).unwrap();
+    assert_eq!(matches.get::<u8>("level"), Some(3));
+}
+
+#[test]
+fn version_flag_is_added_with_a_version() {
+    assert!(matches!(
+        versioned("1.0").try_parse_from(["--version"]),
+        Err(ParseError::VersionRequested { .. })
+    ));
+    assert!(matches!(
+        ArgParser::new().try_parse_from(["--version"]),
+        Err(ParseError::UnknownArgument { .. })
+    ));
+}
+
+#[test]
+fn namespaces_name_env_variables_per_subcommand() {
+    let prefix = format!("RUST_ARGUMENTS_NS{}", std::process::id());
+    std::env::set_var(format!("{}_DRY_RUN", prefix), "yes");
+    std::env::set_var(format!("{}_BUILD_JOBS", prefix), "4");
+    let build = ArgParser::new()
+        .arg("jobs")
+        .long("jobs", "jobs")
+        .takes_value("jobs");
+    let matches = ArgParser::new()
+        .arg("dry-run")
+        .long("dry-run", "dry-run")
+        .namespace(&prefix)
+        .subcommand("build", build)
+        .try_parse_from(["build"])
+        .unwrap();
+    assert_eq!(matches.flags.get("dry-run"), Some(&true));
+    let (_, build) = matches.subcommand().unwrap();
+    assert_eq!(build.get::<u8>("jobs"), Some(4));
+}
+
+#[test]
+fn ignore_case_stores_the_choice_spelling() {
+    let matches = format()
+        .ignore_case("format")
+        .try_parse_from(["--format", "JSON"])
+        .unwrap();
+    assert_eq!(matches.get::<String>("format").as_deref(), Some("json"));
+    assert!(format().try_parse_from(["--format", "JSON"]).is_err());
+}
+
+#[test]
+fn required_when_only_applies_while_the_condition_holds() {
+    let parser = |condition: Condition| {
+        ArgParser::new()
+            .arg("token")
+            .long("token", "token")
+            .takes_value("token")
+            .required_when("token", condition)
+    };
+    assert!(matches!(
+        parser(Condition::Always).try_parse_from(Vec::<String>::new()),
+        Err(ParseError::MissingRequired { .. })
+    ));
+    assert!(parser(!Condition::Always)
+        .try_parse_from(Vec::<String>::new())
+        .is_ok());
+}
+
+#[test]
+fn validators_reject_values() {
+    let parser = ArgParser::new()
+        .arg("input")
+        .long("input", "input")
+        .takes_value("input")
+        .validator("input", |v: &str| v.ends_with(".txt"));
+    assert!(parser.try_parse_from(["--input", "a.txt"]).is_ok());
+    assert!(matches!(
+        parser.try_parse_from(["--input", "a.csv"]),
+        Err(ParseError::InvalidValue { .. })
+    ));
+}
+
+#[test]
+fn prefix_choices_expand_unambiguous_prefixes() {
+    let parser = format().prefix_choices("format");
+    let matches = parser.try_parse_from(["--format", "t"]).unwrap();
+    assert_eq!(matches.get::<String>("format").as_deref(), Some("toml"));
+    let matches = parser.try_parse_from(["--format", "yml"]).unwrap();
+    assert_eq!(matches.get::<String>("format").as_deref(), Some("yml"));
+    assert!(matches!(
+        parser.try_parse_from(["--format", "y"]),
+        Err(ParseError::AmbiguousChoice { candidates, .. }) if candidates == ["yaml", "yml"]
+    ));
+}